@@ -0,0 +1,245 @@
+use proc_macros::wasm_struct;
+use std::f64::consts::{LN_10, PI, TAU};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use super::spring::Spring;
+
+const BEZIER_ITERATIONS: usize = 20;
+// How small the spring's oscillation gets by the end of the animation, as a power of ten
+const SPRING_SETTLE_DIGITS: f64 = 3.;
+// Below this the spring would keep oscillating well past the end of the animation
+const MIN_SPRING_DAMPING_RATIO: f64 = 0.05;
+// (1 + t)e^-t, the distance left on a critically damped spring, reaches a thousandth at about t = 9.23
+const CRITICAL_SPRING_SETTLE_TIME: f64 = 9.23;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum EasingType {
+	Linear,
+	EaseInCubic,
+	EaseOutCubic,
+	EaseInOutCubic,
+	EaseOutSine,
+	EaseOutBack,
+	EaseOutElastic,
+	EaseOutBounce,
+	CubicBezier,
+	Spring,
+}
+
+#[wasm_struct]
+pub struct Easing {
+	easing_type: EasingType,
+	// Control points of the cubic-bezier easing, the same as CSS cubic-bezier(x1, y1, x2, y2)
+	x1: f32,
+	y1: f32,
+	x2: f32,
+	y2: f32,
+	// The spring easing, sped up so that it has settled by the end of the animation
+	stiffness: f32,
+	damping: f32,
+}
+
+impl Easing {
+	// How far along the value is, given how far along the animation is (0 to 1)
+	pub fn ease(&self, x: f64) -> f32 {
+		let x = x.clamp(0., 1.);
+		// Rounding, the bisection and a spring that has only nearly settled would leave the ends slightly off
+		if x == 0. || x == 1. {
+			return x as f32;
+		}
+
+		let eased = match self.easing_type {
+			EasingType::Linear => x,
+			EasingType::EaseInCubic => x * x * x,
+			EasingType::EaseOutCubic => 1. - (1. - x).powi(3),
+			EasingType::EaseInOutCubic => {
+				if x < 0.5 {
+					4. * x * x * x
+				} else {
+					1. - (-2. * x + 2.).powi(3) / 2.
+				}
+			}
+			EasingType::EaseOutSine => ((x * PI) / 2.).sin(),
+			EasingType::EaseOutBack => {
+				let overshoot = 1.70158;
+				1. + (overshoot + 1.) * (x - 1.).powi(3) + overshoot * (x - 1.).powi(2)
+			}
+			EasingType::EaseOutElastic => 2f64.powf(-10. * x) * ((x * 10. - 0.75) * TAU / 3.).sin() + 1.,
+			EasingType::EaseOutBounce => ease_out_bounce(x),
+			EasingType::CubicBezier => cubic_bezier(
+				(
+					self.x1 as f64,
+					self.y1 as f64,
+					self.x2 as f64,
+					self.y2 as f64,
+				),
+				x,
+			),
+			EasingType::Spring => spring(self.stiffness as f64, self.damping as f64, x),
+		};
+
+		eased as f32
+	}
+
+	// The physical spring, for animations that can follow it instead of a fixed duration
+	pub fn spring(&self) -> Option<Spring> {
+		match self.easing_type {
+			EasingType::Spring => Some(Spring {
+				stiffness: self.stiffness as f64,
+				damping: self.damping as f64,
+			}),
+			_ => None,
+		}
+	}
+}
+
+fn ease_out_bounce(x: f64) -> f64 {
+	let strength = 7.5625;
+	let width = 2.75;
+
+	if x < 1. / width {
+		strength * x * x
+	} else if x < 2. / width {
+		let x = x - 1.5 / width;
+		strength * x * x + 0.75
+	} else if x < 2.5 / width {
+		let x = x - 2.25 / width;
+		strength * x * x + 0.9375
+	} else {
+		let x = x - 2.625 / width;
+		strength * x * x + 0.984375
+	}
+}
+
+// Finds where the curve reaches x and returns its y there
+fn cubic_bezier((x1, y1, x2, y2): (f64, f64, f64, f64), x: f64) -> f64 {
+	let bezier = |p1: f64, p2: f64, t: f64| {
+		let inverse = 1. - t;
+		3. * p1 * t * inverse * inverse + 3. * p2 * t * t * inverse + t * t * t
+	};
+
+	// x only ever increases along the curve when its control points are within 0 to 1, so bisection finds it
+	let (x1, x2) = (x1.clamp(0., 1.), x2.clamp(0., 1.));
+	let (mut low, mut high) = (0., 1.);
+	for _ in 0..BEZIER_ITERATIONS {
+		let t = (low + high) / 2.;
+		if bezier(x1, x2, t) < x {
+			low = t;
+		} else {
+			high = t;
+		}
+	}
+
+	bezier(y1, y2, (low + high) / 2.)
+}
+
+// A mass on a spring let go at 0 that settles at 1
+fn spring(stiffness: f64, damping: f64, x: f64) -> f64 {
+	let angular_frequency = stiffness.max(f64::EPSILON).sqrt();
+	let damping_ratio = (damping.max(0.) / (2. * angular_frequency)).max(MIN_SPRING_DAMPING_RATIO);
+
+	if damping_ratio < 1. {
+		let decay = damping_ratio * angular_frequency;
+		let damped_frequency = angular_frequency * (1. - damping_ratio * damping_ratio).sqrt();
+		let sine_weight = decay / damped_frequency;
+
+		// Close to critical damping the oscillation starts out larger, so it takes longer to settle
+		let amplitude = (1. + sine_weight * sine_weight).sqrt();
+		let t = x * (SPRING_SETTLE_DIGITS * LN_10 + amplitude.ln()) / decay;
+
+		1. - (-decay * t).exp()
+			* ((damped_frequency * t).cos() + sine_weight * (damped_frequency * t).sin())
+	} else {
+		// Overdamped springs never overshoot either, so they are treated as critically damped
+		let t = x * CRITICAL_SPRING_SETTLE_TIME;
+		1. - (1. + t) * (-t).exp()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EASING_TYPES: [EasingType; 10] = [
+		EasingType::Linear,
+		EasingType::EaseInCubic,
+		EasingType::EaseOutCubic,
+		EasingType::EaseInOutCubic,
+		EasingType::EaseOutSine,
+		EasingType::EaseOutBack,
+		EasingType::EaseOutElastic,
+		EasingType::EaseOutBounce,
+		EasingType::CubicBezier,
+		EasingType::Spring,
+	];
+
+	// CSS ease for the bezier and a slightly bouncy spring
+	fn easing(easing_type: EasingType) -> Easing {
+		Easing::new(easing_type, 0.25, 0.1, 0.25, 1., 170., 26.)
+	}
+
+	fn bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> Easing {
+		Easing::new(EasingType::CubicBezier, x1, y1, x2, y2, 0., 0.)
+	}
+
+	fn spring(stiffness: f32, damping: f32) -> Easing {
+		Easing::new(EasingType::Spring, 0., 0., 0., 0., stiffness, damping)
+	}
+
+	fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+		assert!(
+			(actual - expected).abs() <= tolerance,
+			"{actual} is not within {tolerance} of {expected}"
+		);
+	}
+
+	#[test]
+	fn every_easing_starts_at_0_and_ends_at_1() {
+		for easing_type in EASING_TYPES {
+			let easing = easing(easing_type);
+			assert_eq!(easing.ease(0.), 0., "{easing_type:?}");
+			assert_eq!(easing.ease(1.), 1., "{easing_type:?}");
+			// Outside of the animation it stays at the ends
+			assert_eq!(easing.ease(-1.), 0., "{easing_type:?}");
+			assert_eq!(easing.ease(2.), 1., "{easing_type:?}");
+		}
+	}
+
+	#[test]
+	fn cubic_bezier_follows_css_ease() {
+		let ease = bezier(0.25, 0.1, 0.25, 1.);
+		assert_close(ease.ease(0.25), 0.408_51, 1e-4);
+		assert_close(ease.ease(0.5), 0.802_40, 1e-4);
+		assert_close(ease.ease(0.75), 0.960_46, 1e-4);
+	}
+
+	#[test]
+	fn cubic_bezier_with_straight_control_points_is_linear() {
+		let linear = bezier(0., 0., 1., 1.);
+		let ease_in_out = bezier(0.42, 0., 0.58, 1.);
+		for x in [0.1, 0.25, 0.5, 0.75, 0.9] {
+			assert_close(linear.ease(x), x as f32, 1e-4);
+			// Symmetric around the middle
+			assert_close(ease_in_out.ease(x) + ease_in_out.ease(1. - x), 1., 1e-4);
+		}
+	}
+
+	#[test]
+	fn spring_settles_by_the_end() {
+		for (stiffness, damping) in [(170., 26.), (100., 5.), (100., 20.), (100., 60.), (1., 0.)] {
+			let spring = spring(stiffness, damping);
+			for step in 90..100 {
+				assert_close(spring.ease(step as f64 / 100.), 1., 0.003);
+			}
+		}
+	}
+
+	#[test]
+	fn only_underdamped_springs_overshoot() {
+		let overshoots = |easing: Easing| (1..100).any(|step| easing.ease(step as f64 / 100.) > 1.);
+		assert!(overshoots(spring(100., 5.)));
+		assert!(!overshoots(spring(100., 20.)));
+		assert!(!overshoots(spring(100., 60.)));
+	}
+}
@@ -1,10 +1,123 @@
-use std::f64::consts::PI;
+pub mod easing;
+pub mod spring;
+
+use proc_macros::wasm_struct;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use easing::{Easing, EasingType};
+use spring::Spring;
+
+// Longest a color fade runs for with reduced motion
+const REDUCED_FADE_DURATION: f64 = 150.;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum AnimationPolicy {
+	Full,
+	// Only short color fades, anything that moves or resizes jumps straight to where it ends up
+	Reduced,
+	None,
+}
 
 pub trait AnimationData<T> {
 	fn get_timestamp(&self) -> f64;
 	fn get_start_state(&self) -> T;
 	fn get_end_state(&self) -> T;
 	fn get_current(&self, ratio: f32) -> T;
+	// The state after elapsed milliseconds on a physical spring and whether it has settled, for data
+	// that carries a velocity
+	fn get_spring_state(&self, _spring: Spring, _elapsed: f64) -> Option<(T, bool)> {
+		None
+	}
+}
+
+#[wasm_struct]
+pub struct AnimationTiming {
+	duration: f64,
+	delay: f64,
+	// Total time over which a group of elements start one after another, like bars growing in
+	stagger: f64,
+	easing: Easing,
+}
+
+impl AnimationTiming {
+	pub fn delay(&self) -> f64 {
+		self.delay
+	}
+
+	pub fn spring(&self) -> Option<Spring> {
+		self.easing.spring()
+	}
+
+	fn with_policy(&self, policy: AnimationPolicy, is_fade: bool) -> AnimationTiming {
+		let duration = match policy {
+			AnimationPolicy::Full => return self.clone(),
+			AnimationPolicy::Reduced if is_fade => self.duration.min(REDUCED_FADE_DURATION),
+			_ => 0.,
+		};
+
+		AnimationTiming {
+			duration,
+			delay: 0.,
+			stagger: 0.,
+			easing: Easing::new(EasingType::Linear, 0., 0., 0., 0., 0., 0.),
+		}
+	}
+
+	// How much later the element at index starts than the first of count elements
+	pub fn stagger_delay(&self, index: usize, count: usize) -> f64 {
+		self.stagger / count.max(1) as f64 * index as f64
+	}
+}
+
+#[wasm_struct]
+pub struct AnimationOptions {
+	// Elements appearing, like bars growing from the baseline or the area filling in
+	enter: AnimationTiming,
+	hover: AnimationTiming,
+	select: AnimationTiming,
+	click: AnimationTiming,
+	// Removed elements shrinking away
+	exit: AnimationTiming,
+	// Zooming and panning between viewports
+	viewport: AnimationTiming,
+}
+
+impl AnimationOptions {
+	pub fn enter(&self) -> &AnimationTiming {
+		&self.enter
+	}
+
+	pub fn hover(&self) -> &AnimationTiming {
+		&self.hover
+	}
+
+	pub fn select(&self) -> &AnimationTiming {
+		&self.select
+	}
+
+	pub fn click(&self) -> &AnimationTiming {
+		&self.click
+	}
+
+	pub fn exit(&self) -> &AnimationTiming {
+		&self.exit
+	}
+
+	pub fn viewport(&self) -> &AnimationTiming {
+		&self.viewport
+	}
+
+	pub fn with_policy(&self, policy: AnimationPolicy) -> AnimationOptions {
+		AnimationOptions {
+			enter: self.enter.with_policy(policy, false),
+			hover: self.hover.with_policy(policy, false),
+			select: self.select.with_policy(policy, true),
+			click: self.click.with_policy(policy, true),
+			exit: self.exit.with_policy(policy, false),
+			viewport: self.viewport.with_policy(policy, false),
+		}
+	}
 }
 
 pub struct Animation<'a, T> {
@@ -12,23 +125,39 @@ pub struct Animation<'a, T> {
 
 	timestamp: f64,
 	animation_time: f64,
+	easing: &'a Easing,
+	spring: Option<Spring>,
 }
 
 impl<'a, T> Animation<'a, T> {
 	pub fn new(
 		anim_data: &'a impl AnimationData<T>,
 		current_timestamp: f64,
-		animation_time: f64,
-		delay: f64,
+		timing: &'a AnimationTiming,
 	) -> Self {
 		Self {
 			anim_data,
-			timestamp: current_timestamp - anim_data.get_timestamp() - delay,
-			animation_time,
+			timestamp: current_timestamp - anim_data.get_timestamp() - timing.delay,
+			animation_time: timing.duration,
+			easing: &timing.easing,
+			spring: timing.spring(),
 		}
 	}
 
+	fn get_spring_state(&self) -> Option<(T, bool)> {
+		let spring = self.spring?;
+		if self.timestamp < 0. {
+			return None;
+		}
+
+		self.anim_data.get_spring_state(spring, self.timestamp)
+	}
+
 	pub fn get_current(&self) -> T {
+		if let Some((current, _)) = self.get_spring_state() {
+			return current;
+		}
+
 		if self.is_completed() {
 			return self.anim_data.get_end_state();
 		}
@@ -37,18 +166,27 @@ impl<'a, T> Animation<'a, T> {
 			return self.anim_data.get_start_state();
 		}
 
+		if self.animation_time <= 0. {
+			return self.anim_data.get_end_state();
+		}
+
 		let ratio = self.timestamp / self.animation_time;
 
-		self.anim_data.get_current(ease_out_sine(ratio))
+		self.anim_data.get_current(self.easing.ease(ratio))
 	}
 
 	pub fn is_completed(&self) -> bool {
-		self.timestamp > self.animation_time
-	}
-}
+		if let Some((_, settled)) = self.get_spring_state() {
+			return settled;
+		}
 
-fn ease_out_sine(x: f64) -> f32 {
-	((x * PI) / 2.0).sin() as f32
+		// Still waiting out its delay, even when it jumps straight to the end once that is over
+		if self.timestamp < 0. {
+			return false;
+		}
+
+		self.timestamp >= self.animation_time
+	}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -98,3 +236,167 @@ macro_rules! DefineAnimation {
 		}
 	};
 }
+
+// Like DefineAnimation, but when the timing uses a spring easing the values follow a physical spring
+// instead of a fixed duration, and keep their velocity when retargeted mid-flight
+#[macro_export]
+macro_rules! DefineSpringAnimation {
+	($name:ident, $name2:ident, $($field_name:ident),*) => {
+		#[derive(Debug, Clone)]
+		struct $name {
+			timestamp: f64,
+			$($field_name: SpringStateData),*
+		}
+
+		#[derive(Debug, Clone)]
+		struct $name2 {
+			$($field_name: f32),*
+		}
+
+		impl $name {
+			// Starts heading for to from wherever the animation currently is
+			fn retarget(&self, timing: &AnimationTiming, timestamp: f64, to: $name2) -> Self {
+				let current = Animation::new(self, timestamp, timing).get_current();
+				let elapsed = timestamp - self.timestamp - timing.delay();
+				let spring = timing.spring().filter(|_| elapsed >= 0.);
+
+				$name {
+					timestamp,
+					$($field_name: SpringStateData {
+						from: current.$field_name,
+						to: to.$field_name,
+						velocity: spring.map_or(0., |spring| self.$field_name.at(spring, elapsed).1),
+					}),*
+				}
+			}
+		}
+
+		impl AnimationData<$name2> for $name {
+			fn get_timestamp(&self) -> f64 {
+				self.timestamp
+			}
+
+			fn get_start_state(&self) -> $name2 {
+				$name2 {
+					$($field_name: self.$field_name.from),*
+				}
+			}
+
+			fn get_end_state(&self) -> $name2 {
+				$name2 {
+					$($field_name: self.$field_name.to),*
+				}
+			}
+
+			fn get_current(&self, ratio: f32) -> $name2 {
+				let start_state = self.get_start_state();
+				let end_state = self.get_end_state();
+				$name2 {
+					$($field_name: lerp(start_state.$field_name, end_state.$field_name, ratio)),*
+				}
+			}
+
+			fn get_spring_state(&self, spring: Spring, elapsed: f64) -> Option<($name2, bool)> {
+				let mut settled = true;
+				let current = $name2 {
+					$($field_name: {
+						let (value, velocity) = self.$field_name.at(spring, elapsed);
+						settled &= Spring::is_settled(value - self.$field_name.to, velocity);
+						value
+					}),*
+				};
+
+				Some((current, settled))
+			}
+		}
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::lerp;
+
+	DefineAnimation!(TestAnimData, CurrentTestAnimData, value);
+
+	const FROM_0_TO_1: TestAnimData = TestAnimData {
+		timestamp: 1000.,
+		value: AnimationStateData { from: 0., to: 1. },
+	};
+
+	fn timing(duration: f64, delay: f64) -> AnimationTiming {
+		AnimationTiming::new(
+			duration,
+			delay,
+			0.,
+			Easing::new(EasingType::Linear, 0., 0., 0., 0., 0., 0.),
+		)
+	}
+
+	fn options(movement: AnimationTiming, fade: AnimationTiming) -> AnimationOptions {
+		AnimationOptions::new(
+			movement.clone(),
+			movement.clone(),
+			fade.clone(),
+			fade,
+			movement.clone(),
+			movement,
+		)
+	}
+
+	// The value and whether it is done, elapsed milliseconds after the animation was started
+	fn at(timing: &AnimationTiming, elapsed: f64) -> (f32, bool) {
+		let animation = Animation::new(&FROM_0_TO_1, FROM_0_TO_1.timestamp + elapsed, timing);
+		(animation.get_current().value, animation.is_completed())
+	}
+
+	#[test]
+	fn full_policy_waits_out_the_delay_then_runs_for_the_duration() {
+		let animations =
+			options(timing(100., 50.), timing(100., 0.)).with_policy(AnimationPolicy::Full);
+		let enter = animations.enter();
+		assert_eq!(at(enter, 0.), (0., false));
+		assert_eq!(at(enter, 49.), (0., false));
+		assert_eq!(at(enter, 100.), (0.5, false));
+		assert_eq!(at(enter, 150.), (1., true));
+		assert_eq!(at(enter, 1000.), (1., true));
+	}
+
+	#[test]
+	fn full_policy_keeps_the_start_of_a_delayed_jump_until_the_delay_is_over() {
+		let animations = options(timing(0., 50.), timing(0., 0.)).with_policy(AnimationPolicy::Full);
+		assert_eq!(at(animations.enter(), 0.), (0., false));
+		assert_eq!(at(animations.enter(), 49.), (0., false));
+		assert_eq!(at(animations.enter(), 50.), (1., true));
+		assert_eq!(at(animations.select(), 0.), (1., true));
+	}
+
+	#[test]
+	fn reduced_policy_only_keeps_short_fades() {
+		let animations =
+			options(timing(100., 50.), timing(300., 50.)).with_policy(AnimationPolicy::Reduced);
+		// Movement jumps to the end without waiting for the delay
+		assert_eq!(at(animations.enter(), 0.), (1., true));
+		assert_eq!(at(animations.viewport(), 0.), (1., true));
+		// Fades start straight away and are cut down to REDUCED_FADE_DURATION
+		assert_eq!(at(animations.select(), 0.), (0., false));
+		assert_eq!(at(animations.select(), 75.), (0.5, false));
+		assert_eq!(at(animations.click(), REDUCED_FADE_DURATION), (1., true));
+	}
+
+	#[test]
+	fn none_policy_finishes_everything_straight_away() {
+		let animations =
+			options(timing(100., 50.), timing(300., 50.)).with_policy(AnimationPolicy::None);
+		for timing in [
+			animations.enter(),
+			animations.hover(),
+			animations.select(),
+			animations.click(),
+			animations.exit(),
+			animations.viewport(),
+		] {
+			assert_eq!(at(timing, 0.), (1., true));
+		}
+	}
+}
@@ -0,0 +1,84 @@
+// Close enough to the target, and slow enough, to count as settled
+const REST_DISTANCE: f32 = 0.001;
+const REST_VELOCITY: f32 = 0.01;
+// Below this the spring would oscillate for a very long time before settling
+const MIN_DAMPING_RATIO: f64 = 0.05;
+
+// A unit mass on a damped spring
+#[derive(Debug, Clone, Copy)]
+pub struct Spring {
+	pub stiffness: f64,
+	pub damping: f64,
+}
+
+impl Spring {
+	// Displacement from the target and velocity (per second) after seconds, solved exactly so the frame
+	// rate doesn't change the motion
+	pub fn at(&self, displacement: f64, velocity: f64, seconds: f64) -> (f64, f64) {
+		let angular_frequency = self.stiffness.max(f64::EPSILON).sqrt();
+		let damping_ratio = (self.damping.max(0.) / (2. * angular_frequency)).max(MIN_DAMPING_RATIO);
+		let t = seconds;
+
+		if damping_ratio < 1. {
+			let decay = damping_ratio * angular_frequency;
+			let damped_frequency = angular_frequency * (1. - damping_ratio * damping_ratio).sqrt();
+			let (sin, cos) = (damped_frequency * t).sin_cos();
+			let envelope = (-decay * t).exp();
+			let sine_weight = (velocity + decay * displacement) / damped_frequency;
+
+			(
+				envelope * (displacement * cos + sine_weight * sin),
+				envelope
+					* (velocity * cos
+						- (decay * velocity + angular_frequency * angular_frequency * displacement)
+							/ damped_frequency
+							* sin),
+			)
+		} else if damping_ratio == 1. {
+			let envelope = (-angular_frequency * t).exp();
+			let slope = velocity + angular_frequency * displacement;
+
+			(
+				envelope * (displacement + slope * t),
+				envelope * (velocity - angular_frequency * slope * t),
+			)
+		} else {
+			let spread = angular_frequency * (damping_ratio * damping_ratio - 1.).sqrt();
+			let slow_rate = -damping_ratio * angular_frequency + spread;
+			let fast_rate = -damping_ratio * angular_frequency - spread;
+			let fast = (velocity - slow_rate * displacement) / (fast_rate - slow_rate);
+			let slow = displacement - fast;
+			let (slow_decay, fast_decay) = ((slow_rate * t).exp(), (fast_rate * t).exp());
+
+			(
+				slow * slow_decay + fast * fast_decay,
+				slow_rate * slow * slow_decay + fast_rate * fast * fast_decay,
+			)
+		}
+	}
+
+	pub fn is_settled(displacement: f32, velocity: f32) -> bool {
+		displacement.abs() < REST_DISTANCE && velocity.abs() < REST_VELOCITY
+	}
+}
+
+// A value heading for its target, starting out with a velocity (per second) carried over from before
+#[derive(Debug, Clone, Copy)]
+pub struct SpringStateData {
+	pub from: f32,
+	pub to: f32,
+	pub velocity: f32,
+}
+
+impl SpringStateData {
+	// Value and velocity after elapsed milliseconds
+	pub fn at(&self, spring: Spring, elapsed: f64) -> (f32, f32) {
+		let (displacement, velocity) = spring.at(
+			(self.from - self.to) as f64,
+			self.velocity as f64,
+			elapsed.max(0.) / 1000.,
+		);
+
+		(self.to + displacement as f32, velocity as f32)
+	}
+}
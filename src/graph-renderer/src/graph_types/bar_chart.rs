@@ -4,37 +4,67 @@ use std::cmp::min;
 use wasm_bindgen::prelude::*;
 
 use crate::DefineAnimation;
+use crate::DefineSpringAnimation;
+use crate::animation::spring::{Spring, SpringStateData};
 use crate::animation::*;
 use crate::graph_types::shared::consts::VERTICES_PER_QUAD;
+use crate::graph_types::shared::focus_ring::{FOCUS_RING_QUADS, FocusRing, FocusRingOptions};
+use crate::graph_types::shared::gestures::{GestureOptions, GestureRecognizer};
+use crate::graph_types::shared::keys;
+use crate::graph_types::shared::raster::Raster;
+use crate::graph_types::shared::scale::{self, Scale};
+use crate::graph_types::shared::selection::{self, BRUSH_QUADS, Brush, SelectionOptions};
+use crate::graph_types::shared::spatial_index::SpatialIndex;
+use crate::graph_types::shared::tooltip::{Tooltip, TooltipEntry, TooltipMode, TooltipOptions};
 use crate::graph_types::shared::types::ClickingState;
 use crate::graph_types::shared::types::PointerState;
 use crate::graph_types::shared::types::Positioning;
 use crate::graph_types::shared::types::ScaleLineObject;
 use crate::graph_types::shared::types::SelectedState;
+use crate::graph_types::shared::types::ValueAxisDomain;
 use crate::graph_types::shared::types::ValueAxisLayout;
+use crate::graph_types::shared::types::XAxisLayout;
 use crate::graph_types::utils::*;
-use crate::log_warn;
-use crate::trace;
 use crate::utils::*;
+use crate::{log_warn, trace};
 
-DefineAnimation!(BarHoverAnimationData, CurrentBarHoverAnimData, scale);
+DefineSpringAnimation!(BarHoverAnimationData, CurrentBarHoverAnimData, scale);
 
 DefineAnimation!(BarHeightAnimData, CurrentBarHeightAnimData, scale_t);
+DefineAnimation!(BarExitAnimData, CurrentBarExitAnimData, height);
 
 DefineAnimation!(SelectBarAnimData, CurrentSelectBarAnimData, color_t);
 DefineAnimation!(ClickingBarAnimData, CurrentClickingBarAnimData, color_t);
 
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum BarMode {
+	Grouped,
+	Stacked,
+}
+
 #[wasm_struct]
 pub struct BarChartDataPoint {
-	value: f32,
+	values: Vec<f32>,
+	// Identifies the category across updates so it animates from where it was, even when it moves
+	key: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct BarId {
+	category: usize,
+	series: usize,
+}
+
+#[derive(Debug, Clone)]
 struct BarData {
+	id: BarId,
 	x: u32,
 	y: u32,
 	width: u32,
 	height: u32,
+	// Extends down from the zero baseline
+	negative: bool,
 	scale: f32,
 	color_t: f32,
 	color: Color,
@@ -42,14 +72,27 @@ struct BarData {
 	selected_state: SelectedState,
 
 	start_scale_t: f32,
+	// When the bar starts growing to its height, staggered by category
+	height_anim_timestamp: f64,
 	hover_anim: BarHoverAnimationData,
 	clicking_state: ClickingState,
 	clicking_bar_anim: ClickingBarAnimData,
 }
 
+// A removed bar shrinking towards the baseline from where it was last drawn
+#[derive(Debug)]
+struct ExitingBar {
+	bar: BarData,
+	// The end of the bar on the baseline
+	anchor_y: u32,
+	exit_anim: BarExitAnimData,
+}
+
 #[wasm_struct]
 pub struct BarLayout {
+	mode: BarMode,
 	gap: u32,
+	series_gap: u32,
 	bar_corner_radius: u32,
 	min_width: u32,
 	min_height: u32,
@@ -61,15 +104,20 @@ pub struct BarChartLayout {
 
 	bar_layout: BarLayout,
 	value_axis_layout: ValueAxisLayout,
+	x_axis_layout: XAxisLayout,
 }
 
 #[wasm_struct]
 pub struct BarOptions {
 	color: Color,
+	series_colors: Vec<Color>,
+	series_names: Vec<String>,
 	hover_color: Color,
 	selected_color: Color,
 	hover_scale: f32,
 	max_bars: usize,
+	// Categories kept when streaming, the oldest are dropped past it. 0 disables the window
+	window: usize,
 }
 
 #[wasm_struct]
@@ -77,6 +125,12 @@ pub struct BarChartOptions {
 	background_color: Color,
 	bar_options: BarOptions,
 	value_axis_color: Color,
+	focus_ring_options: FocusRingOptions,
+	selection_options: SelectionOptions,
+	tooltip_options: TooltipOptions,
+	gesture_options: GestureOptions,
+	animation_options: AnimationOptions,
+	animation_policy: AnimationPolicy,
 }
 
 #[wasm_bindgen]
@@ -90,16 +144,19 @@ pub struct WasmBarChartData {
 
 #[wasm_bindgen]
 pub struct BarChart {
-	data: Vec<BarChartDataPoint>,
-	start_timestamp: f64,
+	data: PreAllocatedCollection<BarChartDataPoint>,
 	width: u32,
 	height: u32,
 	background_color: Color,
 
-	bars: Vec<BarData>,
+	bars: PreAllocatedCollection<BarData>,
+	exiting_bars: Vec<ExitingBar>,
 	scale_lines: PreAllocatedCollection<ScaleLineObject>,
+	x_axis_ticks: PreAllocatedCollection<ScaleLineObject>,
 
 	bar_color: Color,
+	series_colors: Vec<Color>,
+	series_names: Vec<String>,
 	bar_hover_color: Color,
 	bar_selected_color: Color,
 
@@ -108,7 +165,9 @@ pub struct BarChart {
 	left: u32,
 	right: u32,
 
+	mode: BarMode,
 	gap: u32,
+	series_gap: u32,
 	bar_corner_radius: u32,
 	min_width: u32,
 	min_height: u32,
@@ -119,15 +178,39 @@ pub struct BarChart {
 
 	value_axis_color: Color,
 
+	x_axis_tick_length: u32,
+	x_axis_tick_thickness: u32,
+	x_axis_min_tick_distance: u32,
+
 	hover_scale: f32,
+	max_bars: usize,
+	window: usize,
 
-	max_val: f32,
+	value_axis_domain: ValueAxisDomain,
+	scale: Scale,
+	series_count: usize,
 
 	is_animating: bool,
-	selected_bar_index: Option<usize>,
-	hovered_bar_index: Option<usize>,
+	// The animation options with the animation policy applied
+	animations: AnimationOptions,
+	animation_options: AnimationOptions,
+	// In the order they were selected
+	selected_bars: Vec<BarId>,
+	selected_bar_indices: PreAllocatedCollection<f32>,
+	multi_select: bool,
+	brush: Brush,
+	hovered_bar: Option<BarId>,
+	focused_bar: Option<BarId>,
+	focus_ring: FocusRing,
+	bars_index: SpatialIndex,
+	pointer: Option<(u32, u32)>,
+	tooltip_options: TooltipOptions,
+	tooltip: Option<Tooltip>,
+	gestures: GestureRecognizer,
 
 	updated_data: bool,
+	// The vertex buffers are only filled in by update, there is nothing to rasterize before it
+	has_geometry: bool,
 
 	vertex_positions_general: PreAllocatedCollection<f32>,
 	vertex_colors_general: PreAllocatedCollection<f32>,
@@ -137,65 +220,214 @@ pub struct BarChart {
 	vertex_relative_bar_positions: PreAllocatedCollection<f32>,
 }
 
+fn new_bar(
+	id: BarId,
+	selected_state: SelectedState,
+	start_scale_t: f32,
+	height_anim_timestamp: f64,
+	timestamp: f64,
+) -> BarData {
+	BarData {
+		id,
+		x: 0,
+		y: 0,
+		width: 0,
+		height: 0,
+		negative: false,
+		scale: 1.0,
+		color_t: 0.,
+		color: Color {
+			r: 255,
+			g: 255,
+			b: 255,
+			a: 255,
+		},
+		pointer_state: PointerState::None,
+		selected_state,
+		start_scale_t,
+		height_anim_timestamp,
+		hover_anim: BarHoverAnimationData {
+			timestamp,
+			scale: SpringStateData {
+				from: 1.0,
+				to: 1.0,
+				velocity: 0.,
+			},
+		},
+		clicking_state: ClickingState::None,
+		clicking_bar_anim: ClickingBarAnimData {
+			timestamp,
+			color_t: AnimationStateData { from: 0., to: 0. },
+		},
+	}
+}
+
+fn calculate_scale(data: &[BarChartDataPoint], mode: BarMode, domain: &ValueAxisDomain) -> Scale {
+	let mut min_val: f32 = 0.0;
+	let mut max_val: f32 = 0.0;
+	let mut min_positive: Option<f32> = None;
+	for data_point in data {
+		match mode {
+			BarMode::Grouped => {
+				for value in &data_point.values {
+					min_val = value.min(min_val);
+					max_val = value.max(max_val);
+				}
+			}
+			// Positive values stack up from the baseline and negative values stack down from it
+			BarMode::Stacked => {
+				let positive: f32 = data_point.values.iter().filter(|value| **value > 0.).sum();
+				let negative: f32 = data_point.values.iter().filter(|value| **value < 0.).sum();
+				min_val = negative.min(min_val);
+				max_val = positive.max(max_val);
+			}
+		}
+
+		for value in data_point.values.iter().filter(|value| **value > 0.) {
+			min_positive = Some(min_positive.map_or(*value, |min| value.min(min)));
+		}
+	}
+
+	Scale::new(domain, min_val, max_val, min_positive)
+}
+
+// Bars of the old categories that no new category continues from
+fn exiting_bars(
+	old_bars: &[BarData],
+	old_series_count: usize,
+	matches: &[Option<usize>],
+	timestamp: f64,
+) -> Vec<ExitingBar> {
+	let kept = keys::kept(old_bars.len() / old_series_count.max(1), matches);
+
+	old_bars
+		.iter()
+		.filter(|bar| !kept[bar.id.category] && bar.height > 0)
+		.map(|bar| ExitingBar {
+			bar: bar.clone(),
+			anchor_y: if bar.negative {
+				bar.y
+			} else {
+				bar.y + bar.height
+			},
+			exit_anim: BarExitAnimData {
+				timestamp,
+				height: AnimationStateData {
+					from: bar.height as f32,
+					to: 0.,
+				},
+			},
+		})
+		.collect()
+}
+
+// Bars in every category, the most values any data point has
+fn series_count(data: &[BarChartDataPoint]) -> usize {
+	data
+		.iter()
+		.map(|data_point| data_point.values.len())
+		.max()
+		.unwrap_or(1)
+		.max(1)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn handle_data(
 	mut data: Vec<BarChartDataPoint>,
+	matches: &[Option<usize>],
 	old_bars: &[BarData],
+	old_series_count: usize,
+	mode: BarMode,
+	domain: &ValueAxisDomain,
 	graph_height: u32,
+	max_bars: usize,
+	enter_animation: &AnimationTiming,
 	timestamp: f64,
-) -> (Vec<BarChartDataPoint>, Vec<BarData>, f32) {
-	let mut bars: Vec<BarData> = Vec::with_capacity(data.len());
-	let mut max_val = 0.0;
+) -> (
+	PreAllocatedCollection<BarChartDataPoint>,
+	PreAllocatedCollection<BarData>,
+	Scale,
+	usize,
+) {
+	let mut series_count = 1;
 	for data_point in &data {
-		max_val = data_point.value.max(max_val);
+		series_count = max(data_point.values.len(), series_count);
 	}
 
-	for (index, data_point) in &mut data.iter_mut().enumerate() {
-		let mut start_scale_t = 0.;
-		let mut selected_state = SelectedState::None { timestamp };
-		if index < old_bars.len() {
-			let old_bar = &old_bars[index];
-			start_scale_t = old_bar.height as f32 / graph_height as f32;
-			selected_state = old_bar.selected_state;
-		}
+	// Every category gets a bar for every series so bars can be indexed by category and series
+	for data_point in &mut data {
+		data_point.values.resize(series_count, 0.);
+	}
 
-		bars.push(BarData {
-			x: 0,
-			y: 0,
-			width: 0,
-			height: 0,
-			scale: 1.0,
-			color_t: 0.,
-			color: Color {
-				r: 255,
-				g: 255,
-				b: 255,
-				a: 255,
-			},
-			pointer_state: PointerState::None,
-			selected_state,
-			start_scale_t,
-			hover_anim: BarHoverAnimationData {
-				timestamp,
-				scale: AnimationStateData { from: 1.0, to: 1.0 },
-			},
-			clicking_state: ClickingState::None,
-			clicking_bar_anim: ClickingBarAnimData {
-				timestamp,
-				color_t: AnimationStateData { from: 0., to: 0. },
-			},
-		});
+	let scale = calculate_scale(&data, mode, domain);
+
+	let mut bars: Vec<BarData> = Vec::with_capacity(data.len() * series_count);
+	for category in 0..data.len() {
+		for series in 0..series_count {
+			let id = BarId { category, series };
+
+			let mut start_scale_t = 0.;
+			let mut selected_state = SelectedState::None { timestamp };
+			if series < old_series_count
+				&& let Some(old_category) = matches.get(category).copied().flatten()
+				&& let Some(old_bar) = old_bars.get(old_category * old_series_count + series)
+			{
+				start_scale_t = old_bar.height as f32 / graph_height as f32;
+				selected_state = old_bar.selected_state;
+			}
 
-		data_point.value /= max_val;
+			bars.push(new_bar(
+				id,
+				selected_state,
+				start_scale_t,
+				// The categories grow in one after the other over the same total time however many there are
+				timestamp + enter_animation.stagger_delay(category, data.len()),
+				timestamp,
+			));
+		}
 	}
 
-	(data, bars, max_val)
+	// Room for streamed bars up to max_bars, split into whole categories
+	let bars_capacity = max_bars.max(bars.len());
+	let empty_bar = new_bar(
+		BarId {
+			category: 0,
+			series: 0,
+		},
+		SelectedState::None { timestamp },
+		0.,
+		timestamp,
+		timestamp,
+	);
+	let empty_data_point = BarChartDataPoint {
+		values: Vec::new(),
+		key: None,
+	};
+
+	(
+		PreAllocatedCollection::from_vec(data, empty_data_point, bars_capacity / series_count),
+		PreAllocatedCollection::from_vec(bars, empty_bar, bars_capacity),
+		scale,
+		series_count,
+	)
+}
+
+// The current bars followed by the ones shrinking away
+fn drawn_bars<'a>(
+	bars: &'a PreAllocatedCollection<BarData>,
+	exiting_bars: &'a [ExitingBar],
+) -> impl Iterator<Item = &'a BarData> {
+	bars
+		.as_slice()
+		.iter()
+		.chain(exiting_bars.iter().map(|exiting| &exiting.bar))
 }
 
 #[wasm_bindgen]
 impl BarChart {
 	#[wasm_bindgen(constructor)]
 	pub fn new(
-		data: Vec<BarChartDataPoint>,
+		mut data: Vec<BarChartDataPoint>,
 		start_timestamp: f64,
 		width: u32,
 		height: u32,
@@ -205,32 +437,53 @@ impl BarChart {
 	) -> BarChart {
 		let max_scale_lines = 100;
 
-		let scale_lines = PreAllocatedCollection::new(
-			ScaleLineObject {
-				x: 0,
-				y: 0,
-				width: 0,
-				height: 0,
-				intensity: 0,
-				value: 0.0,
-			},
-			0,
-			max_scale_lines,
-		);
+		let max_x_axis_ticks = 100;
+
+		let empty_line = ScaleLineObject {
+			x: 0,
+			y: 0,
+			width: 0,
+			height: 0,
+			intensity: 0,
+			value: 0.0,
+		};
+		let scale_lines = PreAllocatedCollection::new(empty_line, 0, max_scale_lines);
+		let x_axis_ticks = PreAllocatedCollection::new(empty_line, 0, max_x_axis_ticks);
+
+		// The vertex buffers only have room for max_bars, release builds don't check it when writing
+		let max_bars = options.bar_options.max_bars;
+		let series_count = series_count(&data);
+		if data.len() * series_count > max_bars {
+			log_warn!(
+				"new: more bars than max_bars, dropping the rest",
+				data.len(),
+				series_count,
+				max_bars
+			);
+			data.truncate(max_bars / series_count);
+		}
+		let animations = options
+			.animation_options
+			.with_policy(options.animation_policy);
 
-		let (data, bars, max_val) = handle_data(
+		let (data, bars, scale, series_count) = handle_data(
 			data,
 			&[],
+			&[],
+			0,
+			layout.bar_layout.mode,
+			layout.value_axis_layout.domain(),
 			height - layout.positioning.bottom - layout.positioning.top,
+			max_bars,
+			animations.enter(),
 			start_timestamp,
 		);
 
-		let max_bars = options.bar_options.max_bars;
-
+		let max_general_quads = max_scale_lines + max_x_axis_ticks + FOCUS_RING_QUADS + BRUSH_QUADS;
 		let vertex_positions_general =
-			PreAllocatedCollection::new(0., 0, max_scale_lines * VERTICES_PER_QUAD * 2);
+			PreAllocatedCollection::new(0., 0, max_general_quads * VERTICES_PER_QUAD * 2);
 		let vertex_colors_general =
-			PreAllocatedCollection::new(0., 0, max_scale_lines * VERTICES_PER_QUAD * 4);
+			PreAllocatedCollection::new(0., 0, max_general_quads * VERTICES_PER_QUAD * 4);
 
 		let vertex_positions_bars =
 			PreAllocatedCollection::new(0., 0, max_bars * VERTICES_PER_QUAD * 2);
@@ -240,33 +493,58 @@ impl BarChart {
 
 		BarChart {
 			data,
-			start_timestamp,
 			width,
 			height,
 			background_color: options.background_color,
 			bars,
+			exiting_bars: Vec::new(),
 			scale_lines,
+			x_axis_ticks,
 			bottom: layout.positioning.bottom,
 			top: layout.positioning.top,
 			left: layout.positioning.left,
 			right: layout.positioning.right,
+			mode: layout.bar_layout.mode,
 			gap: layout.bar_layout.gap,
+			series_gap: layout.bar_layout.series_gap,
 			bar_corner_radius: layout.bar_layout.bar_corner_radius,
 			min_width: layout.bar_layout.min_width,
 			min_height: layout.bar_layout.min_height,
 			value_axis_width: layout.value_axis_layout.value_axis_width,
 			value_axis_smallest_scale: layout.value_axis_layout.value_axis_smallest_scale,
 			value_axis_min_pixel_distance: layout.value_axis_layout.value_axis_min_pixel_distance,
+			x_axis_tick_length: layout.x_axis_layout.x_axis_tick_length,
+			x_axis_tick_thickness: layout.x_axis_layout.x_axis_tick_thickness,
+			x_axis_min_tick_distance: layout.x_axis_layout.x_axis_min_tick_distance,
 			hover_scale: options.bar_options.hover_scale,
-			max_val,
+			max_bars,
+			window: options.bar_options.window,
+			value_axis_domain: layout.value_axis_layout.domain().clone(),
+			scale,
+			series_count,
 			is_animating: true,
-			selected_bar_index: None,
-			hovered_bar_index: None,
+			animations,
+			animation_options: options.animation_options,
+			selected_bars: Vec::new(),
+			selected_bar_indices: PreAllocatedCollection::new(0., 0, max_bars),
+			multi_select: options.selection_options.multi_select(),
+			brush: Brush::new(&options.selection_options),
+			hovered_bar: None,
+			focused_bar: None,
+			focus_ring: FocusRing::new(&options.focus_ring_options, start_timestamp),
+			pointer: None,
+			tooltip_options: options.tooltip_options,
+			tooltip: None,
+			gestures: GestureRecognizer::new(&options.gesture_options),
+			bars_index: SpatialIndex::new(),
 			bar_color: options.bar_options.color,
+			series_colors: options.bar_options.series_colors,
+			series_names: options.bar_options.series_names,
 			bar_hover_color: options.bar_options.hover_color,
 			bar_selected_color: options.bar_options.selected_color,
 			value_axis_color: options.value_axis_color,
 			updated_data: false,
+			has_geometry: false,
 
 			vertex_positions_general,
 			vertex_colors_general,
@@ -288,6 +566,7 @@ impl BarChart {
 	pub fn resize(&mut self, width: u32, height: u32) {
 		self.width = width;
 		self.height = height;
+		self.bars_index.invalidate();
 	}
 
 	pub fn update_data(&mut self, data: Vec<BarChartDataPoint>, timestamp: f64) {
@@ -295,23 +574,158 @@ impl BarChart {
 			"Updating data from {:#?} to {:#?}",
 			self.data, data
 		));
-		let (data, bars, max_val) = handle_data(
+		// The vertex buffers only have room for max_bars, release builds don't check it when writing
+		let series_count = series_count(&data);
+		if data.len() * series_count > self.max_bars {
+			log_warn!(
+				"update_data: more bars than max_bars, ignoring the data",
+				data.len(),
+				series_count,
+				self.max_bars
+			);
+			return;
+		}
+		let matches = keys::match_keys(
+			self
+				.data
+				.into_iter()
+				.map(|data_point| data_point.key.as_deref()),
+			data.iter().map(|data_point| data_point.key.as_deref()),
+		);
+		let exiting_bars = exiting_bars(self.bars.as_slice(), self.series_count, &matches, timestamp);
+		let (data, bars, scale, series_count) = handle_data(
 			data,
-			&self.bars,
+			&matches,
+			self.bars.as_slice(),
+			self.series_count,
+			self.mode,
+			&self.value_axis_domain,
 			self.height - self.bottom - self.top,
+			self.max_bars,
+			self.animations.enter(),
 			timestamp,
 		);
 		self.data = data;
 		self.bars = bars;
-		self.max_val = max_val;
-		self.start_timestamp = timestamp;
+		self.scale = scale;
+		self.series_count = series_count;
+		self.exiting_bars.extend(exiting_bars);
+		self.limit_exiting_bars();
+		self.selected_bars = self
+			.bars
+			.as_slice()
+			.iter()
+			.filter(|bar| matches!(bar.selected_state, SelectedState::Selected { timestamp: _ }))
+			.map(|bar| bar.id)
+			.collect();
+		// Focus follows its category when it moves
+		self.focused_bar = self.focused_bar.and_then(|id| {
+			let category = matches.iter().position(|old| *old == Some(id.category))?;
+			(id.series < self.series_count).then_some(BarId {
+				category,
+				series: id.series,
+			})
+		});
+		self.bars_index.invalidate();
+		self.updated_data = true;
+	}
+
+	// Exiting bars are drawn after the current ones, so only as many as there is room for are kept
+	fn limit_exiting_bars(&mut self) {
+		let room = self.max_bars.saturating_sub(self.bars.len());
+		if self.exiting_bars.len() > room {
+			let overflow = self.exiting_bars.len() - room;
+			self.exiting_bars.drain(..overflow);
+		}
+	}
+
+	// Adds a category to the end without reallocating. With a sliding window the oldest categories are
+	// dropped to make room, the remaining bars keep their animation state and the new ones grow in.
+	// Returns false when the bar doesn't fit
+	pub fn push_bar(&mut self, mut data_point: BarChartDataPoint, timestamp: f64) -> bool {
+		if data_point.values.len() > self.series_count {
+			log_warn!(
+				"push_bar: more values than series, ignoring the rest",
+				data_point.values.len(),
+				self.series_count
+			);
+		}
+		data_point.values.resize(self.series_count, 0.);
+
+		if self.window > 0 {
+			let window = self.window.min(self.data.capacity());
+			self.shift_categories((self.data.len() + 1).saturating_sub(window));
+		}
+
+		if self.data.is_full() {
+			log_warn!(
+				"push_bar: max_bars reached, ignoring the bar",
+				self.max_bars
+			);
+			return false;
+		}
+
+		let category = self.data.len();
+		self.data.push(data_point);
+		for series in 0..self.series_count {
+			self.bars.push(new_bar(
+				BarId { category, series },
+				SelectedState::None { timestamp },
+				0.,
+				timestamp,
+				timestamp,
+			));
+		}
+
+		self.handle_streamed_data();
+		true
+	}
+
+	// Drops the oldest categories, the remaining bars keep their animation state
+	pub fn shift_bars(&mut self, count: usize) {
+		self.shift_categories(count);
+		self.handle_streamed_data();
+	}
+
+	fn shift_categories(&mut self, count: usize) {
+		let count = count.min(self.data.len());
+		if count == 0 {
+			return;
+		}
+
+		self.data.shift(count);
+		self.bars.shift(count * self.series_count);
+		for bar in &mut self.bars {
+			bar.id.category -= count;
+		}
+
+		let shift = |id: BarId| {
+			id.category.checked_sub(count).map(|category| BarId {
+				category,
+				series: id.series,
+			})
+		};
+		self.selected_bars = self
+			.selected_bars
+			.iter()
+			.copied()
+			.filter_map(shift)
+			.collect();
+		self.focused_bar = self.focused_bar.and_then(shift);
+	}
+
+	fn handle_streamed_data(&mut self) {
+		self.scale = calculate_scale(self.data.as_slice(), self.mode, &self.value_axis_domain);
+		self.limit_exiting_bars();
+		self.bars_index.invalidate();
 		self.updated_data = true;
 	}
 
 	fn get_scale_line_vertex_positions(&mut self) {
 		let positions = &mut self.vertex_positions_general;
 		trace!(positions.len());
-		for (i, scale_line) in self.scale_lines.into_iter().enumerate() {
+		let lines = self.scale_lines.into_iter().chain(&self.x_axis_ticks);
+		for (i, scale_line) in lines.enumerate() {
 			let vert_index = i * VERTICES_PER_QUAD * 2;
 
 			let left = scale_line.x;
@@ -342,7 +756,8 @@ impl BarChart {
 
 	fn get_scale_line_vertex_colors(&mut self) {
 		let colors = &mut self.vertex_colors_general;
-		for (i, scale_line) in self.scale_lines.into_iter().enumerate() {
+		let lines = self.scale_lines.into_iter().chain(&self.x_axis_ticks);
+		for (i, scale_line) in lines.enumerate() {
 			let vert_index = i * VERTICES_PER_QUAD * 4;
 
 			let color = self
@@ -361,9 +776,9 @@ impl BarChart {
 
 	fn get_bar_vertex_positions(&mut self) -> WasmFloat32Array {
 		let positions = &mut self.vertex_positions_bars;
-		positions.set_size(self.bars.len() * VERTICES_PER_QUAD * 2);
+		positions.set_size((self.bars.len() + self.exiting_bars.len()) * VERTICES_PER_QUAD * 2);
 
-		for (i, bar) in self.bars.iter().enumerate() {
+		for (i, bar) in drawn_bars(&self.bars, &self.exiting_bars).enumerate() {
 			let vert_index = i * VERTICES_PER_QUAD * 2;
 
 			let width = (bar.width as f32 * bar.scale) as u32;
@@ -396,14 +811,17 @@ impl BarChart {
 
 	fn get_relative_bar_vertex_positions(&mut self) -> WasmFloat32Array {
 		let positions = &mut self.vertex_relative_bar_positions;
-		positions.set_size(self.bars.len() * VERTICES_PER_QUAD * 4);
+		positions.set_size((self.bars.len() + self.exiting_bars.len()) * VERTICES_PER_QUAD * 4);
 
-		for (i, bar) in self.bars.iter().enumerate() {
+		for (i, bar) in drawn_bars(&self.bars, &self.exiting_bars).enumerate() {
 			let vert_index = i * VERTICES_PER_QUAD * 4;
 
 			let bar_width = bar.width as f32;
 			let bar_height = bar.height as f32;
 
+			// The relative height is 1 at the end away from the baseline, which is the bottom for negative bars
+			let (bottom, top) = if bar.negative { (1., 0.) } else { (0., 1.) };
+
 			/*
 			0: relative width of nth vertex
 			1: relative height of nth vertex
@@ -412,32 +830,32 @@ impl BarChart {
 			4: ...
 			*/
 			positions[vert_index] = 0.;
-			positions[vert_index + 1] = 0.;
+			positions[vert_index + 1] = bottom;
 			positions[vert_index + 2] = bar_width;
 			positions[vert_index + 3] = bar_height;
 
 			positions[vert_index + 4] = 0.;
-			positions[vert_index + 5] = 1.;
+			positions[vert_index + 5] = top;
 			positions[vert_index + 6] = bar_width;
 			positions[vert_index + 7] = bar_height;
 
 			positions[vert_index + 8] = 1.;
-			positions[vert_index + 9] = 1.;
+			positions[vert_index + 9] = top;
 			positions[vert_index + 10] = bar_width;
 			positions[vert_index + 11] = bar_height;
 
 			positions[vert_index + 12] = 1.;
-			positions[vert_index + 13] = 0.;
+			positions[vert_index + 13] = bottom;
 			positions[vert_index + 14] = bar_width;
 			positions[vert_index + 15] = bar_height;
 
 			positions[vert_index + 16] = 0.;
-			positions[vert_index + 17] = 0.;
+			positions[vert_index + 17] = bottom;
 			positions[vert_index + 18] = bar_width;
 			positions[vert_index + 19] = bar_height;
 
 			positions[vert_index + 20] = 1.;
-			positions[vert_index + 21] = 1.;
+			positions[vert_index + 21] = top;
 			positions[vert_index + 22] = bar_width;
 			positions[vert_index + 23] = bar_height;
 		}
@@ -447,9 +865,9 @@ impl BarChart {
 
 	fn get_bar_vertex_colors(&mut self) -> WasmFloat32Array {
 		let colors = &mut self.vertex_colors_bars;
-		colors.set_size(self.bars.len() * VERTICES_PER_QUAD * 4);
+		colors.set_size((self.bars.len() + self.exiting_bars.len()) * VERTICES_PER_QUAD * 4);
 
-		for (i, bar) in self.bars.iter().enumerate() {
+		for (i, bar) in drawn_bars(&self.bars, &self.exiting_bars).enumerate() {
 			let vert_index = i * VERTICES_PER_QUAD * 4;
 
 			let color = bar.color;
@@ -469,11 +887,23 @@ impl BarChart {
 	fn get_general_vertex_positions(&mut self) -> WasmFloat32Array {
 		trace!("get_general_vertex_positions");
 
-		self
-			.vertex_positions_general
-			.set_size(self.scale_lines.len() * VERTICES_PER_QUAD * 2);
+		let scale_lines_len = self.scale_lines.len() + self.x_axis_ticks.len();
+		let focus_ring_len = self.focus_ring.quads_len();
+		self.vertex_positions_general.set_size(
+			(scale_lines_len + focus_ring_len + self.brush.quads_len()) * VERTICES_PER_QUAD * 2,
+		);
 
 		self.get_scale_line_vertex_positions();
+		self.focus_ring.write_vertex_positions(
+			&mut self.vertex_positions_general,
+			scale_lines_len,
+			(self.width, self.height),
+		);
+		self.brush.write_vertex_positions(
+			&mut self.vertex_positions_general,
+			scale_lines_len + focus_ring_len,
+			(self.width, self.height),
+		);
 
 		(&self.vertex_positions_general).into()
 	}
@@ -481,11 +911,20 @@ impl BarChart {
 	fn get_general_vertex_colors(&mut self) -> WasmFloat32Array {
 		trace!("get_general_vertex_colors");
 
-		self
-			.vertex_colors_general
-			.set_size(self.scale_lines.len() * VERTICES_PER_QUAD * 4);
+		let scale_lines_len = self.scale_lines.len() + self.x_axis_ticks.len();
+		let focus_ring_len = self.focus_ring.quads_len();
+		self.vertex_colors_general.set_size(
+			(scale_lines_len + focus_ring_len + self.brush.quads_len()) * VERTICES_PER_QUAD * 4,
+		);
 
 		self.get_scale_line_vertex_colors();
+		self
+			.focus_ring
+			.write_vertex_colors(&mut self.vertex_colors_general, scale_lines_len);
+		self.brush.write_vertex_colors(
+			&mut self.vertex_colors_general,
+			scale_lines_len + focus_ring_len,
+		);
 
 		(&self.vertex_colors_general).into()
 	}
@@ -510,6 +949,32 @@ impl BarChart {
 		self.bars[index].height
 	}
 
+	pub fn get_bar_category_at(&self, index: usize) -> usize {
+		self.bars[index].id.category
+	}
+
+	pub fn get_bar_series_at(&self, index: usize) -> usize {
+		self.bars[index].id.series
+	}
+
+	pub fn get_series_count(&self) -> usize {
+		self.series_count
+	}
+
+	pub fn get_categories_len(&self) -> usize {
+		self.data.len()
+	}
+
+	pub fn get_category_x_at(&self, index: usize) -> u32 {
+		self.bars[index * self.series_count].x
+	}
+
+	pub fn get_category_width_at(&self, index: usize) -> u32 {
+		let first_bar = &self.bars[index * self.series_count];
+		let last_bar = &self.bars[(index + 1) * self.series_count - 1];
+		last_bar.x + last_bar.width - first_bar.x
+	}
+
 	pub fn get_scale_lines_count(&self) -> usize {
 		self.scale_lines.len()
 	}
@@ -526,20 +991,274 @@ impl BarChart {
 		self.scale_lines[index].value
 	}
 
+	pub fn get_x_axis_ticks_count(&self) -> usize {
+		self.x_axis_ticks.len()
+	}
+
+	pub fn get_x_axis_tick_x_at(&self, index: usize) -> u32 {
+		let tick = &self.x_axis_ticks[index];
+		tick.x + tick.width / 2
+	}
+
+	pub fn get_x_axis_tick_y_at(&self, index: usize) -> u32 {
+		self.x_axis_ticks[index].y
+	}
+
+	pub fn get_x_axis_tick_value_at(&self, index: usize) -> f32 {
+		self.x_axis_ticks[index].value
+	}
+
+	// Applies to animations already running too, so they jump to where they end up
+	pub fn set_animation_policy(&mut self, policy: AnimationPolicy) {
+		self.animations = self.animation_options.with_policy(policy);
+		self.is_animating = true;
+	}
+
 	pub fn get_is_animating(&self) -> bool {
 		self.is_animating
 	}
 
-	pub fn get_selected_bar_index(&self) -> Option<usize> {
-		self.selected_bar_index
+	// Scale lines, x axis ticks, the focus ring and the brush all share the general buffer
+	pub fn get_max_general_vertices(&self) -> usize {
+		self.vertex_colors_general.capacity() / 4
+	}
+
+	// The most recently selected bar
+	pub fn get_selected_bar_category(&self) -> Option<usize> {
+		self.selected_bars.last().map(|id| id.category)
+	}
+
+	pub fn get_selected_bar_series(&self) -> Option<usize> {
+		self.selected_bars.last().map(|id| id.series)
+	}
+
+	// Indices of every selected bar, in the order they were selected
+	pub fn get_selected_bar_indices(&mut self) -> WasmFloat32Array {
+		let indices = &mut self.selected_bar_indices;
+		indices.set_size(self.selected_bars.len());
+		for (i, id) in self.selected_bars.iter().enumerate() {
+			indices[i] = (id.category * self.series_count + id.series) as f32;
+		}
+
+		indices.into()
+	}
+
+	pub fn get_hovered_bar_category(&self) -> Option<usize> {
+		self.hovered_bar.map(|id| id.category)
+	}
+
+	pub fn get_hovered_bar_series(&self) -> Option<usize> {
+		self.hovered_bar.map(|id| id.series)
+	}
+
+	pub fn get_focused_bar_category(&self) -> Option<usize> {
+		self.focused_bar.map(|id| id.category)
+	}
+
+	pub fn get_focused_bar_series(&self) -> Option<usize> {
+		self.focused_bar.map(|id| id.series)
+	}
+
+	// Works out the tooltip for where the pointer is, placed for a tooltip of the given size.
+	// Returns whether there is one
+	pub fn calculate_tooltip(&mut self, tooltip_width: u32, tooltip_height: u32) -> bool {
+		let bars: Vec<BarId> = match self.tooltip_options.mode() {
+			TooltipMode::Hovered => self.hovered_bar.into_iter().collect(),
+			TooltipMode::NearestX => self
+				.nearest_category()
+				.map(|category| {
+					(0..self.series_count)
+						.map(|series| BarId { category, series })
+						.collect()
+				})
+				.unwrap_or_default(),
+		};
+
+		let drawn: Vec<&BarData> = bars
+			.iter()
+			.filter_map(|id| {
+				self
+					.bars
+					.as_slice()
+					.get(id.category * self.series_count + id.series)
+			})
+			.collect();
+		let (Some(first), Some(last)) = (drawn.first(), drawn.last()) else {
+			self.tooltip = None;
+			return false;
+		};
+
+		let anchor = (
+			(first.x + last.x + last.width) / 2,
+			drawn.iter().map(|bar| bar.y).min().unwrap_or(first.y),
+		);
+		let entries = drawn
+			.iter()
+			.map(|bar| TooltipEntry {
+				series: bar.id.series,
+				index: bar.id.category,
+				name: self
+					.series_names
+					.get(bar.id.series)
+					.cloned()
+					.unwrap_or_default(),
+				value: self.data[bar.id.category]
+					.values
+					.get(bar.id.series)
+					.copied()
+					.unwrap_or(0.),
+			})
+			.collect();
+
+		self.tooltip = Some(Tooltip::new(
+			anchor,
+			(tooltip_width, tooltip_height),
+			self.tooltip_options.offset(),
+			(
+				self.left,
+				self.top,
+				self.width.saturating_sub(self.right),
+				self.height.saturating_sub(self.bottom),
+			),
+			entries,
+		));
+		true
+	}
+
+	pub fn get_tooltip_anchor_x(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.anchor_x)
+	}
+
+	pub fn get_tooltip_anchor_y(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.anchor_y)
 	}
 
-	pub fn get_hovered_bar_index(&self) -> Option<usize> {
-		self.hovered_bar_index
+	pub fn get_tooltip_x(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.x)
+	}
+
+	pub fn get_tooltip_y(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.y)
+	}
+
+	pub fn get_tooltip_width(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.width)
+	}
+
+	pub fn get_tooltip_height(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.height)
+	}
+
+	pub fn get_tooltip_entries_len(&self) -> usize {
+		self
+			.tooltip
+			.as_ref()
+			.map_or(0, |tooltip| tooltip.entries.len())
+	}
+
+	pub fn get_tooltip_entry_category_at(&self, index: usize) -> Option<usize> {
+		self.tooltip_entry_at(index).map(|entry| entry.index)
+	}
+
+	pub fn get_tooltip_entry_series_at(&self, index: usize) -> Option<usize> {
+		self.tooltip_entry_at(index).map(|entry| entry.series)
+	}
+
+	pub fn get_tooltip_entry_name_at(&self, index: usize) -> Option<String> {
+		self.tooltip_entry_at(index).map(|entry| entry.name.clone())
+	}
+
+	pub fn get_tooltip_entry_value_at(&self, index: usize) -> Option<f32> {
+		self.tooltip_entry_at(index).map(|entry| entry.value)
+	}
+
+	fn tooltip_entry_at(&self, index: usize) -> Option<&TooltipEntry> {
+		self
+			.tooltip
+			.as_ref()
+			.and_then(|tooltip| tooltip.entries.get(index))
+	}
+
+	// The category whose bars are horizontally closest to the pointer
+	fn nearest_category(&self) -> Option<usize> {
+		let (pointer_x, _) = self.pointer?;
+		if self.series_count == 0 {
+			return None;
+		}
+
+		(0..self.bars.len() / self.series_count).min_by_key(|category| {
+			let x = self.get_category_x_at(*category);
+			let width = self.get_category_width_at(*category);
+			if pointer_x < x {
+				x - pointer_x
+			} else {
+				pointer_x.saturating_sub(x + width)
+			}
+		})
+	}
+
+	// Selects the bar at index, either on its own or added to the current selection
+	pub fn select_bar(&mut self, index: usize, additive: bool, timestamp: f64) {
+		if index >= self.bars.len() {
+			log_warn!("select_bar: bar index out of bounds", index);
+			return;
+		}
+
+		if !additive || !self.multi_select {
+			for i in (0..self.bars.len()).filter(|i| *i != index) {
+				self.set_bar_selected_at(i, false, timestamp);
+			}
+		}
+		self.set_bar_selected_at(index, true, timestamp);
+		self.is_animating = true;
+	}
+
+	pub fn clear_selection(&mut self, timestamp: f64) {
+		if !self.selected_bars.is_empty() {
+			self.deselect_bars(timestamp);
+			self.is_animating = true;
+		}
+	}
+
+	// Moves focus to the next bar, wrapping around to the first
+	pub fn focus_next(&mut self) {
+		let index = match self.focused_bar_index() {
+			Some(index) => (index + 1) % self.bars.len(),
+			None => 0,
+		};
+		self.focus_bar_at(index);
+	}
+
+	// Moves focus to the previous bar, wrapping around to the last
+	pub fn focus_previous(&mut self) {
+		let index = match self.focused_bar_index() {
+			Some(index) => index.checked_sub(1).unwrap_or(self.bars.len() - 1),
+			None => self.bars.len().saturating_sub(1),
+		};
+		self.focus_bar_at(index);
+	}
+
+	pub fn clear_focus(&mut self) {
+		if self.focused_bar.is_some() {
+			self.focused_bar = None;
+			self.is_animating = true;
+		}
+	}
+
+	fn focused_bar_index(&self) -> Option<usize> {
+		let id = self.focused_bar?;
+		self.bars.as_slice().iter().position(|bar| bar.id == id)
+	}
+
+	fn focus_bar_at(&mut self, index: usize) {
+		if index < self.bars.len() {
+			self.focused_bar = Some(self.bars[index].id);
+			self.is_animating = true;
+		}
 	}
 
 	pub fn get_corner_radius(&self) -> u32 {
-		let first_bar = self.bars.first();
+		let first_bar = self.bars.as_slice().first();
 		if let Some(first_bar) = first_bar {
 			min(self.bar_corner_radius, first_bar.width / 2)
 		} else {
@@ -547,23 +1266,49 @@ impl BarChart {
 		}
 	}
 
-	fn toggle_bar_selection_at(&mut self, index: usize, timestamp: f64) {
-		for i in 0..self.bars.len() {
-			let selected = i == index;
+	fn set_bar_selected_at(&mut self, index: usize, selected: bool, timestamp: f64) {
+		let bar = &mut self.bars[index];
+		match (bar.selected_state, selected) {
+			(SelectedState::None { timestamp: _ }, true) => {
+				trace!("Select bar", index);
+				bar.selected_state = SelectedState::Selected { timestamp };
+				self.selected_bars.push(bar.id);
+			}
+			(SelectedState::Selected { timestamp: _ }, false) => {
+				trace!("Deselect bar", index);
+				bar.selected_state = SelectedState::None { timestamp };
+				let id = bar.id;
+				self.selected_bars.retain(|selected| *selected != id);
+			}
+			_ => {}
+		}
+	}
 
-			if selected {
-				if let SelectedState::Selected { timestamp: _ } = self.bars[i].selected_state {
-					trace!("Deselect bar", i);
-					self.bars[i].selected_state = SelectedState::None { timestamp };
-					self.selected_bar_index = None;
-				} else {
-					trace!("Select bar", i);
-					self.bars[i].selected_state = SelectedState::Selected { timestamp };
-					self.selected_bar_index = Some(index);
-				}
-			} else if let SelectedState::None { timestamp: _ } = self.bars[i].selected_state {
-			} else {
-				self.bars[i].selected_state = SelectedState::None { timestamp };
+	// A click selects only the bar, or deselects it when it is the only one selected. Additive clicks
+	// toggle the bar and leave the rest of the selection alone
+	fn toggle_bar_selection_at(&mut self, index: usize, additive: bool, timestamp: f64) {
+		let id = self.bars[index].id;
+		let selected = self.selected_bars.contains(&id);
+		if additive {
+			self.set_bar_selected_at(index, !selected, timestamp);
+			return;
+		}
+
+		let only_selected = selected && self.selected_bars.len() == 1;
+		for i in (0..self.bars.len()).filter(|i| *i != index) {
+			self.set_bar_selected_at(i, false, timestamp);
+		}
+		self.set_bar_selected_at(index, !only_selected, timestamp);
+	}
+
+	// Selects every bar the brushed rectangle touches
+	fn brush_select(&mut self, rect: (u32, u32, u32, u32), additive: bool, timestamp: f64) {
+		for i in 0..self.bars.len() {
+			let bar = &self.bars[i];
+			if selection::intersects(rect, (bar.x, bar.y, bar.width, bar.height)) {
+				self.set_bar_selected_at(i, true, timestamp);
+			} else if !additive {
+				self.set_bar_selected_at(i, false, timestamp);
 			}
 		}
 	}
@@ -580,7 +1325,41 @@ impl BarChart {
 				self.bars[i].selected_state = SelectedState::None { timestamp };
 			}
 		}
-		self.selected_bar_index = None;
+		self.selected_bars.clear();
+	}
+
+	// Left edge of the first category, the distance between categories and the width of one category
+	fn category_slots(&self) -> (u32, f32, f32) {
+		let categories_count = self.data.len();
+
+		let mut left = self.left + self.value_axis_width;
+		let mut base_width = (self.width as i32 - left as i32 - self.right as i32 + self.gap as i32)
+			as f32
+			/ (categories_count as f32);
+
+		let unclamped_width = base_width - self.gap as f32;
+
+		if unclamped_width < 0.0 {
+			left = (left as i32 + unclamped_width as i32).to_u32();
+			base_width = ((self.width as i32 - left as i32 - self.right as i32 + self.gap as i32) as f32
+				+ unclamped_width)
+				/ (categories_count as f32);
+		}
+
+		(left, base_width, unclamped_width)
+	}
+
+	// The last bar whose current rectangle contains (x, y)
+	fn bar_at(&self, x: u32, y: u32) -> Option<BarId> {
+		self
+			.bars_index
+			.query(x as f32, y as f32, 0.)
+			.filter(|bar_index| {
+				let bar = &self.bars[*bar_index];
+				x >= bar.x && x <= bar.x + bar.width && y >= bar.y && y <= bar.y + bar.height
+			})
+			.max()
+			.map(|bar_index| self.bars[bar_index].id)
 	}
 
 	fn calculate_bars(
@@ -589,91 +1368,158 @@ impl BarChart {
 		pointer_x: Option<u32>,
 		pointer_y: Option<u32>,
 		clicking_state: ClickingState,
+		additive: bool,
 	) {
 		trace!("calculate_bars");
-		let bars_count = self.data.len();
+		let additive = additive && self.multi_select;
+		let bars_count = self.bars.len();
 
-		let mut left = self.left + self.value_axis_width;
-		let mut base_width = (self.width as i32 - left as i32 - self.right as i32 + self.gap as i32)
-			as f32
-			/ (bars_count as f32);
+		let (left, base_width, unclamped_width) = self.category_slots();
 
 		let bottom = self.bottom;
 
 		let height = self.height as i32 - self.top as i32 - bottom as i32;
-		let unclamped_width = base_width - self.gap as f32;
 
-		if unclamped_width < 0.0 {
-			left = (left as i32 + unclamped_width as i32).to_u32();
-			base_width = ((self.width as i32 - left as i32 - self.right as i32 + self.gap as i32) as f32
-				+ unclamped_width)
-				/ (bars_count as f32);
-		}
+		let series_count = self.series_count;
+		let series_width = match self.mode {
+			BarMode::Grouped => {
+				(unclamped_width - (self.series_gap * (series_count as u32 - 1)) as f32)
+					/ series_count as f32
+			}
+			BarMode::Stacked => unclamped_width,
+		};
 
 		let mut all_animations_done = true;
 		let mut any_bar_was_clicked = false;
-		self.hovered_bar_index = None;
 
-		let height_animation_delay = 800.
-			/ if bars_count == 0 {
-				1.
-			} else {
-				bars_count as f64
-			};
+		let baseline = self.scale.baseline();
+		let zero_y = self.value_to_y(baseline);
+
+		let mut positive_stack_height = 0;
+		let mut negative_stack_height = 0;
+		let mut positive_stack_value = baseline;
+		let mut negative_stack_value = baseline;
 
 		for bar_index in 0..bars_count {
 			let bar = &mut self.bars[bar_index];
+			let BarId { category, series } = bar.id;
+
+			if series == 0 {
+				positive_stack_height = 0;
+				negative_stack_height = 0;
+				positive_stack_value = baseline;
+				negative_stack_value = baseline;
+			}
 
 			let anim_data = BarHeightAnimData {
-				timestamp: self.start_timestamp,
+				timestamp: bar.height_anim_timestamp,
 				scale_t: AnimationStateData { from: 0.0, to: 1.0 },
 			};
-			let animation = Animation::new(
-				&anim_data,
-				timestamp,
-				500.0,
-				height_animation_delay * bar_index as f64,
-			);
+			let animation = Animation::new(&anim_data, timestamp, self.animations.enter());
 
 			if !animation.is_completed() {
 				all_animations_done = false;
 			}
 
-			let x_pos = (bar_index as f32 * base_width + left as f32).to_u32();
-			let width = unclamped_width.max(self.min_width as f32).to_u32();
+			let category_x = category as f32 * base_width + left as f32;
+			let x_pos = match self.mode {
+				BarMode::Grouped => {
+					(category_x + series as f32 * (series_width + self.series_gap as f32)).to_u32()
+				}
+				BarMode::Stacked => category_x.to_u32(),
+			};
+			let width = series_width.max(self.min_width as f32).to_u32();
+
+			let value = self.data[category].values[series];
+			let negative = value < 0.;
+
+			// Measured between the start and end values so that stacks are sized correctly on nonlinear scales
+			let start_value = match (self.mode, negative) {
+				(BarMode::Grouped, _) => baseline,
+				(BarMode::Stacked, false) => positive_stack_value,
+				(BarMode::Stacked, true) => negative_stack_value,
+			};
+			let end_value = start_value + value;
+			let value_t = (self.scale.normalize(end_value) - self.scale.normalize(start_value)).abs();
 
 			let scale_t = animation.get_current().scale_t;
-			let full_height = max(
-				(height as f32 * self.data[bar_index].value).to_u32(),
-				self.min_height,
-			) as f32;
-			let height = lerp(bar.start_scale_t * height as f32, full_height, scale_t).to_u32();
-			let y_pos = (self.height as i32 - bottom as i32 - height as i32).to_u32();
+			let value_height = (height as f32 * value_t).to_u32();
+			// Zero values, like the ones series are padded with, get no stub
+			let full_height = if value == 0. {
+				value_height
+			} else {
+				max(value_height, self.min_height)
+			};
+			let start_height = bar.start_scale_t * height as f32;
+			// The minimum height is only drawn, stacks still add up to the scale
+			let stack_height = lerp(start_height, value_height as f32, scale_t).to_u32();
+			let height = lerp(start_height, full_height as f32, scale_t).to_u32();
+			let y_pos = if negative {
+				(zero_y + negative_stack_height as f32).to_u32()
+			} else {
+				(zero_y - height as f32 - positive_stack_height as f32).to_u32()
+			};
+
+			if let BarMode::Stacked = self.mode {
+				if negative {
+					negative_stack_height += stack_height;
+					negative_stack_value = end_value;
+				} else {
+					positive_stack_height += stack_height;
+					positive_stack_value = end_value;
+				}
+			}
 
 			bar.x = x_pos;
 			bar.y = y_pos;
 			bar.width = width;
 			bar.height = height;
+			bar.negative = negative;
+		}
 
-			if let Some(pointer_x) = pointer_x
-				&& let Some(pointer_y) = pointer_y
-				&& pointer_x >= x_pos
-				&& pointer_x <= x_pos + width
-				&& pointer_y >= y_pos
-				&& pointer_y <= y_pos + height
-			{
-				self.hovered_bar_index = Some(bar_index);
+		// Bars only move horizontally when the data or the size changes, so the index covers their whole column
+		if self.bars_index.is_dirty() {
+			let top = self.top as f32;
+			let bottom = self.height.saturating_sub(self.bottom) as f32;
+			self.bars_index.rebuild(
+				(self.width, self.height),
+				self
+					.bars
+					.as_slice()
+					.iter()
+					.enumerate()
+					.map(|(bar_index, bar)| {
+						let (x, width) = (bar.x as f32, bar.width as f32);
+						(bar_index, (x, top, x + width, bottom))
+					}),
+			);
+		}
 
+		self.hovered_bar = match (pointer_x, pointer_y) {
+			(Some(pointer_x), Some(pointer_y)) => self.bar_at(pointer_x, pointer_y),
+			_ => None,
+		};
+
+		let brushed = self.brush.update(pointer_x, pointer_y, clicking_state);
+		if self.brush.is_brushing() {
+			all_animations_done = false;
+		}
+
+		for bar_index in 0..bars_count {
+			let bar = &mut self.bars[bar_index];
+			let series = bar.id.series;
+
+			if self.hovered_bar == Some(bar.id) {
 				if let PointerState::Hover = bar.pointer_state {
 				} else {
 					bar.pointer_state = PointerState::Hover;
-					bar.hover_anim = BarHoverAnimationData {
+					bar.hover_anim = bar.hover_anim.retarget(
+						self.animations.hover(),
 						timestamp,
-						scale: AnimationStateData {
-							from: bar.scale,
-							to: self.hover_scale,
+						CurrentBarHoverAnimData {
+							scale: self.hover_scale,
 						},
-					};
+					);
 				}
 
 				match clicking_state {
@@ -700,7 +1546,10 @@ impl BarChart {
 						};
 						bar.clicking_state = ClickingState::JustReleased;
 
-						self.toggle_bar_selection_at(bar_index, timestamp);
+						// Letting go of a brush selects what it covers rather than the bar under the pointer
+						if brushed.is_none() {
+							self.toggle_bar_selection_at(bar_index, additive, timestamp);
+						}
 						any_bar_was_clicked = true;
 					}
 					_ => {}
@@ -709,13 +1558,11 @@ impl BarChart {
 			} else {
 				bar.pointer_state = PointerState::None;
 
-				bar.hover_anim = BarHoverAnimationData {
+				bar.hover_anim = bar.hover_anim.retarget(
+					self.animations.hover(),
 					timestamp,
-					scale: AnimationStateData {
-						from: bar.scale,
-						to: 1.0,
-					},
-				};
+					CurrentBarHoverAnimData { scale: 1.0 },
+				);
 				bar.clicking_bar_anim = ClickingBarAnimData {
 					timestamp,
 					color_t: AnimationStateData {
@@ -728,7 +1575,7 @@ impl BarChart {
 
 			let bar = &mut self.bars[bar_index];
 
-			let animation = Animation::new(&bar.hover_anim, timestamp, 200.0, 0.0);
+			let animation = Animation::new(&bar.hover_anim, timestamp, self.animations.hover());
 
 			if !animation.is_completed() {
 				all_animations_done = false;
@@ -743,17 +1590,17 @@ impl BarChart {
 				},
 				timestamp: bar.selected_state.get_timestamp(),
 			};
-			let animation = Animation::new(&anim_data, timestamp, 200.0, 0.0);
+			let animation = Animation::new(&anim_data, timestamp, self.animations.select());
+
+			let series_color = *self.series_colors.get(series).unwrap_or(&self.bar_color);
 
-			bar.color = self
-				.bar_color
-				.lerp(&self.bar_selected_color, animation.get_current().color_t);
+			bar.color = series_color.lerp(&self.bar_selected_color, animation.get_current().color_t);
 
 			if !animation.is_completed() {
 				all_animations_done = false;
 			}
 
-			let animation = Animation::new(&bar.clicking_bar_anim, timestamp, 200.0, 0.0);
+			let animation = Animation::new(&bar.clicking_bar_anim, timestamp, self.animations.click());
 
 			if !animation.is_completed() {
 				all_animations_done = false;
@@ -764,86 +1611,102 @@ impl BarChart {
 			bar.color = bar.color.lerp(&self.bar_hover_color, bar.color_t);
 		}
 
-		if matches!(clicking_state, ClickingState::JustReleased) && !any_bar_was_clicked {
+		if let Some(rect) = brushed {
+			self.brush_select(rect, additive, timestamp);
+			all_animations_done = false;
+		} else if matches!(clicking_state, ClickingState::JustReleased)
+			&& !any_bar_was_clicked
+			&& !additive
+		{
 			self.deselect_bars(timestamp);
 			all_animations_done = false;
 		}
 
-		self.is_animating = !all_animations_done;
-	}
-
-	fn calculate_scale_lines(&mut self) {
-		trace!("calculate_scale_lines");
-		let thickness = 2;
-		let x_offset = self.value_axis_width;
-
-		let smallest_scale = self.value_axis_smallest_scale;
-		let min_pixel_dist = self.value_axis_min_pixel_distance as f32;
-
-		let height = (self.height as i32 - self.top as i32 - self.bottom as i32) as f32;
-		let mut pixel_distance = (smallest_scale / self.max_val) * height;
-
-		let mut mult: i64 = 1;
+		// Removed bars shrink towards the baseline and are dropped once they are gone
+		self.exiting_bars.retain_mut(|exiting| {
+			let animation = Animation::new(&exiting.exit_anim, timestamp, self.animations.exit());
+			let height = animation.get_current().height.to_u32();
+			exiting.bar.height = height;
+			exiting.bar.y = if exiting.bar.negative {
+				exiting.anchor_y
+			} else {
+				exiting.anchor_y.saturating_sub(height)
+			};
 
-		if pixel_distance < min_pixel_dist {
-			mult = (min_pixel_dist / pixel_distance).ceil_nearest_power_2() as i64;
-			pixel_distance *= mult as f32;
+			!animation.is_completed()
+		});
+		if !self.exiting_bars.is_empty() {
+			all_animations_done = false;
 		}
 
-		if pixel_distance < 1. {
-			log_warn!("pixel_distance < 1");
-			return;
+		let focus_rect = self.focused_bar_index().map(|index| {
+			let bar = &self.bars[index];
+			let width = (bar.width as f32 * bar.scale) as u32;
+			let left = (bar.x as f32 - (width as f32 - bar.width as f32) / 2.).to_u32();
+			(left, bar.y, width, bar.height)
+		});
+		if !self
+			.focus_ring
+			.update(focus_rect, self.animations.hover(), timestamp)
+		{
+			all_animations_done = false;
 		}
 
-		let line_count = (height / pixel_distance).to_u32() + 1;
-		let mut success_line_count = line_count;
-		for i in 1..line_count {
-			let total_pixel_dist = pixel_distance * i as f32;
-
-			if (self.height as f32 - self.bottom as f32 - total_pixel_dist - self.top as f32)
-				< min_pixel_dist * (2.0 / 3.0)
-			{
-				success_line_count -= 1;
-				continue;
-			}
+		self.is_animating = !all_animations_done;
+	}
 
-			let ratio = total_pixel_dist / height;
+	fn value_to_y(&self, value: f32) -> f32 {
+		let height = (self.height as i32 - self.top as i32 - self.bottom as i32) as f32;
+		self.height as f32 - self.bottom as f32 - self.scale.normalize(value) * height
+	}
 
-			let value = ratio * self.max_val;
+	fn calculate_scale_lines(&mut self) {
+		trace!("calculate_scale_lines");
+		scale::calculate_scale_lines(
+			&mut self.scale_lines,
+			&self.scale,
+			(self.value_axis_width, self.width),
+			(self.top as f32, self.height as f32 - self.bottom as f32),
+			self.value_axis_smallest_scale,
+			self.value_axis_min_pixel_distance as f32,
+		);
+	}
 
-			let modu = (value.round() % (smallest_scale * mult as f32 * 2.0)).to_u32();
-			let y =
-				(self.height as i32 - self.bottom as i32 - thickness as i32 - total_pixel_dist as i32)
-					.to_u32();
+	fn calculate_x_axis(&mut self) {
+		trace!("calculate_x_axis");
+		let categories_count = self.data.len();
 
-			let scale_line = &mut self.scale_lines[i as usize];
-			scale_line.x = x_offset;
-			scale_line.y = y;
-			scale_line.width = (self.width as i32 - x_offset as i32).to_u32();
-			scale_line.height = thickness;
-			scale_line.intensity = if modu == 0 { 100 } else { 50 };
-			scale_line.value = value;
+		if categories_count == 0 || self.x_axis_tick_length == 0 {
+			self.x_axis_ticks.set_size(0);
+			return;
 		}
 
-		let scale_line = &mut self.scale_lines[0];
-		scale_line.x = x_offset;
-		scale_line.y = self.top;
-		scale_line.width = (self.width as i32 - x_offset as i32).to_u32();
-		scale_line.height = thickness;
-		scale_line.intensity = 255;
-		scale_line.value = self.max_val;
-
-		let scale_line = &mut self.scale_lines[success_line_count as usize];
-		scale_line.x = x_offset;
-		scale_line.y = (self.height as i32 - self.bottom as i32 - thickness as i32).to_u32();
-		scale_line.width = (self.width as i32 - x_offset as i32).to_u32();
-		scale_line.height = thickness;
-		scale_line.intensity = 200;
-		scale_line.value = 0.0;
+		let (left, base_width, unclamped_width) = self.category_slots();
+
+		// Skip categories when there isn't room for a tick in every slot
+		let stride = (self.x_axis_min_tick_distance as f32 / base_width.max(1.))
+			.ceil()
+			.max(1.) as usize;
+		let y = (self.height as i32 - self.bottom as i32).to_u32();
+		let thickness = self.x_axis_tick_thickness;
+
+		let capacity = self.x_axis_ticks.get_data().len();
+		let mut count = 0;
+		for category in (0..categories_count).step_by(stride).take(capacity) {
+			let x = left as f32 + category as f32 * base_width + unclamped_width.max(0.) / 2.;
+
+			let tick = &mut self.x_axis_ticks[count];
+			tick.x = (x - thickness as f32 / 2.).to_u32();
+			tick.y = y;
+			tick.width = thickness;
+			tick.height = self.x_axis_tick_length;
+			tick.intensity = 200;
+			tick.value = category as f32;
+
+			count += 1;
+		}
 
-		self
-			.scale_lines
-			.set_size(usize::try_from(success_line_count).unwrap_or(usize::MAX - 1) + 1);
+		self.x_axis_ticks.set_size(count);
 	}
 
 	pub fn update(
@@ -852,11 +1715,30 @@ impl BarChart {
 		pointer_x: Option<u32>,
 		pointer_y: Option<u32>,
 		clicking_state: ClickingState,
+		additive: bool,
+		touches: &[f32],
 	) -> WasmBarChartData {
 		trace!("update");
 
+		// Bars have nothing to pan or zoom, only taps and long-presses stand in for the pointer
+		let gestures = self.gestures.update(touches, timestamp);
+		let (pointer_x, pointer_y, clicking_state, additive) =
+			if self.gestures.is_touching() || !gestures.is_empty() {
+				match self.gestures.pointer(&gestures) {
+					Some((x, y, clicking_state, additive)) => (Some(x), Some(y), clicking_state, additive),
+					None => (None, None, ClickingState::None, false),
+				}
+			} else {
+				(pointer_x, pointer_y, clicking_state, additive)
+			};
+
+		self.pointer = pointer_x.zip(pointer_y);
 		self.calculate_scale_lines();
-		self.calculate_bars(timestamp, pointer_x, pointer_y, clicking_state);
+		self.calculate_x_axis();
+		self.calculate_bars(timestamp, pointer_x, pointer_y, clicking_state, additive);
+		if self.gestures.is_pending() {
+			self.is_animating = true;
+		}
 
 		let vertex_array_general = self.get_general_vertex_positions();
 		let colors_array_general = self.get_general_vertex_colors();
@@ -868,6 +1750,7 @@ impl BarChart {
 			self.is_animating = true;
 			self.updated_data = false;
 		}
+		self.has_geometry = true;
 
 		WasmBarChartData {
 			vertex_array_general,
@@ -877,4 +1760,161 @@ impl BarChart {
 			relative_bar_positions,
 		}
 	}
+
+	// The last update drawn without WebGL, as RGBA pixels row by row from the top. None until update has
+	// been called, as that is what lays out what is drawn
+	pub fn rasterize(&self) -> Option<Vec<u8>> {
+		self.raster().map(Raster::into_pixels)
+	}
+
+	pub fn rasterize_png(&self) -> Option<Vec<u8>> {
+		self.raster().map(|raster| raster.encode_png())
+	}
+
+	fn raster(&self) -> Option<Raster> {
+		if !self.has_geometry {
+			return None;
+		}
+
+		let mut raster = Raster::new(self.width, self.height, self.background_color);
+		raster.fill_triangles(
+			self.vertex_positions_general.as_slice(),
+			self.vertex_colors_general.as_slice(),
+		);
+		raster.fill_bars(
+			self.vertex_positions_bars.as_slice(),
+			self.vertex_colors_bars.as_slice(),
+			self.vertex_relative_bar_positions.as_slice(),
+			self.get_corner_radius() as f32,
+		);
+		Some(raster)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::animation::easing::{Easing, EasingType};
+	use crate::graph_types::shared::scale::ScaleType;
+
+	fn chart(values: &[f32], width: u32, height: u32) -> BarChart {
+		let instant = AnimationTiming::new(
+			0.,
+			0.,
+			0.,
+			Easing::new(EasingType::Linear, 0., 0., 0., 0., 0., 0.),
+		);
+		let white = Color::new(255, 255, 255, 255);
+
+		BarChart::new(
+			values
+				.iter()
+				.map(|value| BarChartDataPoint::new(vec![*value], None))
+				.collect(),
+			0.,
+			width,
+			height,
+			BarChartLayout::new(
+				Positioning::new(0, 0, 0, 0),
+				BarLayout::new(BarMode::Grouped, 2, 0, 0, 1, 0),
+				ValueAxisLayout::new(
+					0,
+					1.,
+					20,
+					ValueAxisDomain::new(ScaleType::Linear, 10, None, None, false),
+				),
+				XAxisLayout::new(0, 0, 50),
+			),
+			BarChartOptions::new(
+				Color::new(0, 0, 0, 255),
+				BarOptions::new(
+					Color::new(255, 0, 0, 255),
+					vec![],
+					vec![],
+					white,
+					white,
+					1.,
+					10,
+					0,
+				),
+				Color::new(0, 0, 255, 255),
+				FocusRingOptions::new(white, 0, 0),
+				SelectionOptions::new(false, false, white),
+				TooltipOptions::new(TooltipMode::Hovered, 0),
+				GestureOptions::new(10, 300., 500., 10),
+				AnimationOptions::new(
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant,
+				),
+				AnimationPolicy::None,
+			),
+		)
+	}
+
+	#[test]
+	fn nothing_to_rasterize_before_the_first_update() {
+		let mut chart = chart(&[1., 2., 4.], 12, 8);
+		assert!(chart.rasterize().is_none());
+		assert!(chart.rasterize_png().is_none());
+
+		chart.update(0., None, None, ClickingState::None, false, &[]);
+		assert_eq!(chart.rasterize().unwrap().len(), 12 * 8 * 4);
+		assert!(chart.rasterize_png().unwrap().starts_with(b"\x89PNG"));
+	}
+
+	#[test]
+	fn new_drops_the_bars_past_max_bars() {
+		// The chart only has room for ten bars
+		let mut chart = chart(&[1.; 12], 12, 8);
+		assert_eq!(chart.data.len(), 10);
+		assert_eq!(chart.bars.len(), 10);
+
+		chart.update(0., None, None, ClickingState::None, false, &[]);
+		assert!(chart.rasterize().is_some());
+	}
+
+	#[test]
+	fn golden_small_chart() {
+		let mut chart = chart(&[1., 2., 4.], 12, 8);
+		chart.update(0., None, None, ClickingState::None, false, &[]);
+		let pixels = chart.rasterize().unwrap();
+
+		// Bars are red and darken towards the baseline, scale lines are blue
+		let rows: Vec<String> = pixels
+			.chunks_exact(12 * 4)
+			.map(|row| {
+				row
+					.chunks_exact(4)
+					.map(|pixel| match pixel {
+						[0, 0, 0, 255] => '.',
+						[_, 0, 0, 255] => '#',
+						[0, 0, _, 255] => '-',
+						_ => '?',
+					})
+					.collect()
+			})
+			.collect();
+		assert_eq!(
+			rows,
+			[
+				"---------##-",
+				"---------##-",
+				".........##.",
+				".........##.",
+				"....##...##.",
+				"....##...##.",
+				"##--##---##-",
+				"##--##---##-",
+			]
+		);
+		assert_eq!(pixels[9 * 4..9 * 4 + 4], [247, 0, 0, 255]);
+		assert_eq!(
+			pixels[(7 * 12 + 9) * 4..(7 * 12 + 9) * 4 + 4],
+			[135, 0, 0, 255]
+		);
+	}
 }
@@ -1,16 +1,30 @@
-#![allow(warnings)] // TODO: remove this
-
 use proc_macros::wasm_struct;
+use std::f32::consts::PI;
+use std::f32::consts::TAU;
+use std::ops::Range;
 use wasm_bindgen::prelude::wasm_bindgen;
 
 use crate::animation::Animation;
 use crate::animation::AnimationStateData;
 use crate::animation::*;
 use crate::graph_types::shared::consts::VERTICES_PER_QUAD;
-use crate::graph_types::shared::types::ValueAxisLayout;
-use crate::graph_types::shared::types::{
-	PointerState, Positioning, ScaleLineObject, SelectedState,
+use crate::graph_types::shared::crosshair::{
+	CROSSHAIR_QUADS, Crosshair, CrosshairOptions, CrosshairTarget,
 };
+use crate::graph_types::shared::downsampling::{Downsampling, downsample};
+use crate::graph_types::shared::focus_ring::{FOCUS_RING_QUADS, FocusRing, FocusRingOptions};
+use crate::graph_types::shared::gestures::{Gesture, GestureOptions, GestureRecognizer};
+use crate::graph_types::shared::interpolation::{Interpolation, interpolate};
+use crate::graph_types::shared::keys;
+use crate::graph_types::shared::points::{PointInteraction, PointState};
+use crate::graph_types::shared::raster::Raster;
+use crate::graph_types::shared::scale::{self, Scale};
+use crate::graph_types::shared::selection::{self, BRUSH_QUADS, Brush, SelectionOptions};
+use crate::graph_types::shared::spatial_index::SpatialIndex;
+use crate::graph_types::shared::ticks::{nice_step, ticks_in};
+use crate::graph_types::shared::tooltip::{Tooltip, TooltipEntry, TooltipMode, TooltipOptions};
+use crate::graph_types::shared::types::{Positioning, ScaleLineObject, SelectedState};
+use crate::graph_types::shared::types::{ValueAxisDomain, ValueAxisLayout, XAxisLayout};
 use crate::graph_types::utils::Color;
 use crate::utils::NumUtils;
 use crate::utils::PreAllocatedCollection;
@@ -18,14 +32,22 @@ use crate::utils::lerp;
 use crate::{DefineAnimation, log_warn, trace};
 use crate::{graph_types::shared::types::ClickingState, utils::WasmFloat32Array};
 
-DefineAnimation!(PointHoverAnimationData, CurrentPointHoverAnimData, scale);
-DefineAnimation!(ClickingPointAnimData, CurrentClickingPointAnimData, color_t);
-DefineAnimation!(SelectPointAnimData, CurrentSelectPointAnimData, color_t);
+DefineAnimation!(PointExitAnimData, CurrentPointExitAnimData, scale);
+DefineAnimation!(AreaFillAnimData, CurrentAreaFillAnimData, fill_t);
+DefineAnimation!(ViewportAnimData, CurrentViewportAnimData, t);
+
+const ROUND_JOIN_SEGMENTS: usize = 8;
+const MITER_LIMIT: f32 = 4.;
 
 #[wasm_bindgen]
 pub struct WasmLineChartData {
 	pub vertex_array_general: WasmFloat32Array,
 	pub colors_array_general: WasmFloat32Array,
+	pub vertex_array_area: WasmFloat32Array,
+	pub colors_array_area: WasmFloat32Array,
+	pub vertex_array_line: WasmFloat32Array,
+	pub colors_array_line: WasmFloat32Array,
+	pub relative_line_positions: WasmFloat32Array,
 	pub vertex_array_points: WasmFloat32Array,
 	pub colors_array_points: WasmFloat32Array,
 }
@@ -34,22 +56,68 @@ pub struct WasmLineChartData {
 pub struct LineChartDataPoint {
 	x: f32,
 	y: f32,
+	// Identifies the point across updates so it animates from where it was, even when it moves
+	key: Option<String>,
 }
 
-#[derive(Debug)]
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum PointStyle {
+	Square,
+	Diamond,
+	Hidden,
+}
+
+#[wasm_struct]
+pub struct LineChartSeries {
+	name: String,
+	color: Color,
+	point_style: PointStyle,
+	visible: bool,
+	data: Vec<LineChartDataPoint>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct PointId {
+	series: usize,
+	index: usize,
+}
+
+#[derive(Debug, Clone)]
 struct PointData {
 	x: u32,
 	y: u32,
+
+	state: PointState,
+}
+
+// A removed point shrinking away from where it was last drawn
+#[derive(Debug)]
+struct ExitingPoint {
+	x: u32,
+	y: u32,
 	scale: f32,
-	color_t: f32,
 	color: Color,
-	pointer_state: PointerState,
-	selected_state: SelectedState,
+	point_style: PointStyle,
+	exit_anim: PointExitAnimData,
+}
 
-	start_scale_t: f32,
-	hover_anim: PointHoverAnimationData,
-	clicking_state: ClickingState,
-	clicking_point_anim: ClickingPointAnimData,
+#[derive(Debug)]
+struct SeriesData {
+	name: String,
+	color: Color,
+	point_style: PointStyle,
+	visible: bool,
+	data: PreAllocatedCollection<LineChartDataPoint>,
+	points: PreAllocatedCollection<PointData>,
+	// Indices of the points that are drawn and can be hovered, every point when there is no downsampling
+	samples: Vec<usize>,
+	// The closest points outside the viewport on either side, so the line runs on to the edge of the plot
+	before_view: Option<usize>,
+	after_view: Option<usize>,
+
+	legend_x: u32,
+	legend_y: u32,
 }
 
 #[wasm_struct]
@@ -57,44 +125,104 @@ pub struct PointLayout {
 	radius: u32,
 }
 
+#[wasm_struct]
+pub struct LegendLayout {
+	swatch_size: u32,
+	item_width: u32,
+	gap: u32,
+}
+
 #[wasm_struct]
 pub struct LineChartLayout {
 	positioning: Positioning,
 
 	point_layout: PointLayout,
 	value_axis_layout: ValueAxisLayout,
+	x_axis_layout: XAxisLayout,
+	legend_layout: LegendLayout,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum LineJoin {
+	Miter,
+	Round,
+}
+
+#[wasm_struct]
+pub struct LineOptions {
+	width: f32,
+	join: LineJoin,
+	interpolation: Interpolation,
+	curve_segments: u32,
+	downsampling: Downsampling,
+}
+
+#[wasm_struct]
+pub struct AreaOptions {
+	enabled: bool,
+	opacity: f32,
+	gradient: bool,
 }
 
 #[wasm_struct]
 pub struct PointOptions {
-	color: Color,
 	hover_color: Color,
 	selected_color: Color,
 	hover_scale: f32,
 	max_points: usize,
+	// The line and area buffers have room for the curves between the points and past the edges of
+	// this many series
+	max_series: usize,
+	// Points kept per series when streaming, the oldest are dropped past it. 0 disables the window
+	window: usize,
 }
 
 #[wasm_struct]
 pub struct LineChartOptions {
 	background_color: Color,
+	line_options: LineOptions,
+	area_options: AreaOptions,
 	point_options: PointOptions,
 	value_axis_color: Color,
+	focus_ring_options: FocusRingOptions,
+	crosshair_options: CrosshairOptions,
+	selection_options: SelectionOptions,
+	tooltip_options: TooltipOptions,
+	gesture_options: GestureOptions,
+	animation_options: AnimationOptions,
+	animation_policy: AnimationPolicy,
 }
 
 #[wasm_bindgen]
 pub struct LineChart {
-	data: Vec<LineChartDataPoint>,
+	series: Vec<SeriesData>,
 	start_timestamp: f64,
 	width: u32,
 	height: u32,
 	background_color: Color,
 
-	points: Vec<PointData>,
 	scale_lines: PreAllocatedCollection<ScaleLineObject>,
+	x_axis_ticks: PreAllocatedCollection<ScaleLineObject>,
+
+	line_width: f32,
+	line_join: LineJoin,
+	interpolation: Interpolation,
+	curve_segments: usize,
+	downsampling: Downsampling,
+	max_points: usize,
+	max_series: usize,
+	window: usize,
+
+	point_positions: PreAllocatedCollection<(f32, f32)>,
+	curve: PreAllocatedCollection<(f32, f32)>,
+
+	area_enabled: bool,
+	area_opacity: f32,
+	area_gradient: bool,
+	area_fill_t: f32,
 
-	point_color: Color,
-	point_hover_color: Color,
-	point_selected_color: Color,
+	point_interaction: PointInteraction,
 
 	bottom: u32,
 	top: u32,
@@ -109,88 +237,608 @@ pub struct LineChart {
 
 	value_axis_color: Color,
 
-	hover_scale: f32,
+	x_axis_tick_length: u32,
+	x_axis_tick_thickness: u32,
+	x_axis_min_tick_distance: u32,
+
+	legend_swatch_size: u32,
+	legend_item_width: u32,
+	legend_gap: u32,
 
 	min_x: f32,
 	max_x: f32,
-	max_y: f32,
+	// The X range zoomed or panned to, everything when it is None
+	x_domain: Option<(f32, f32)>,
+	// The X range currently shown, animated towards x_domain
+	view: (f32, f32),
+	viewport_from: (f32, f32),
+	viewport_anim: ViewportAnimData,
+	// Whether the samples and the index have caught up with where the viewport animation ended
+	viewport_settled: bool,
+	value_axis_domain: ValueAxisDomain,
+	// Value axis for all of the data, scale is the one for what is in view
+	domain_scale: Scale,
+	scale: Scale,
 
 	is_animating: bool,
-	selected_point_index: Option<usize>,
-	hovered_point_index: Option<usize>,
+	// The animation options with the animation policy applied
+	animations: AnimationOptions,
+	animation_options: AnimationOptions,
+	// In the order they were selected
+	selected_points: Vec<PointId>,
+	selected_point_indices: PreAllocatedCollection<f32>,
+	multi_select: bool,
+	brush: Brush,
+	hovered_point: Option<PointId>,
+	focused_point: Option<PointId>,
+	focus_ring: FocusRing,
+	crosshair: Crosshair,
+	exiting_points: Vec<ExitingPoint>,
+	// Visible points in the order they were added to the index
+	point_ids: Vec<PointId>,
+	points_index: SpatialIndex,
+	pointer: Option<(u32, u32)>,
+	tooltip_options: TooltipOptions,
+	tooltip: Option<Tooltip>,
+	gestures: GestureRecognizer,
 
 	updated_data: bool,
+	// The vertex buffers are only filled in by update, there is nothing to rasterize before it
+	has_geometry: bool,
 
 	vertex_positions_general: PreAllocatedCollection<f32>,
 	vertex_colors_general: PreAllocatedCollection<f32>,
+	vertex_positions_area: PreAllocatedCollection<f32>,
+	vertex_colors_area: PreAllocatedCollection<f32>,
+	vertex_positions_line: PreAllocatedCollection<f32>,
+	vertex_colors_line: PreAllocatedCollection<f32>,
+	vertex_relative_line_positions: PreAllocatedCollection<f32>,
 	vertex_positions_points: PreAllocatedCollection<f32>,
 	vertex_colors_points: PreAllocatedCollection<f32>,
 }
 
+// Points each series can be reduced to, one per pixel column of the graph within the shared max_points
+fn max_samples(
+	downsampling: Downsampling,
+	columns: i32,
+	max_points: usize,
+	series_count: usize,
+) -> usize {
+	downsampling
+		.max_samples(columns.max(1) as usize)
+		.min(max_points / series_count.max(1))
+}
+
+// Indices of the points with an X within view (min_x, max_x), which has to be sorted by X
+fn visible_range(series: &SeriesData, (min_x, max_x): (f32, f32)) -> Range<usize> {
+	let data = series.data.as_slice();
+	let start = data.partition_point(|data_point| data_point.x < min_x);
+	let end = data.partition_point(|data_point| data_point.x <= max_x);
+	start..end.max(start)
+}
+
+// Downsamples the points within view (min_x, max_x)
+fn sample(
+	series: &mut SeriesData,
+	downsampling: Downsampling,
+	max_samples: usize,
+	view: (f32, f32),
+) {
+	let range = visible_range(series, view);
+	series.before_view = range.start.checked_sub(1);
+	series.after_view = (range.end < series.data.len()).then_some(range.end);
+	series.samples = downsample(
+		downsampling,
+		range.len(),
+		|index| {
+			let data_point = &series.data[range.start + index];
+			(data_point.x, data_point.y)
+		},
+		max_samples,
+	)
+	.into_iter()
+	.map(|index| range.start + index)
+	.collect();
+}
+
+// Cuts the curve off where it leaves the plot between left and right
+fn clip_curve(curve: &mut PreAllocatedCollection<(f32, f32)>, left: f32, right: f32) {
+	let len = curve.len();
+	if len < 2 {
+		return;
+	}
+
+	let at_x = |(x1, y1): (f32, f32), (x2, y2): (f32, f32), x: f32| {
+		let t = if x2 != x1 { (x - x1) / (x2 - x1) } else { 0. };
+		(x, lerp(y1, y2, t.clamp(0., 1.)))
+	};
+
+	let points = curve.as_slice();
+	let start = points.iter().position(|(x, _)| *x >= left).unwrap_or(len);
+	let end = points
+		.iter()
+		.rposition(|(x, _)| *x <= right)
+		.map_or(0, |index| index + 1);
+	if start == len || end == 0 {
+		curve.set_size(0);
+		return;
+	}
+
+	let first = (start > 0).then(|| at_x(points[start - 1], points[start], left));
+	let last = (end < len).then(|| at_x(points[end - 1], points[end], right));
+
+	// Only ever shrinks, a point is dropped for every one added at an edge
+	let mut count = 0;
+	if let Some(point) = first {
+		curve[0] = point;
+		count += 1;
+	}
+	for index in start..end.max(start) {
+		curve[count] = curve[index];
+		count += 1;
+	}
+	if let Some(point) = last {
+		curve[count] = point;
+		count += 1;
+	}
+	curve.set_size(count);
+}
+
+// Room kept in every series for streamed points, the sliding window when there is one
+fn series_capacity(
+	window: usize,
+	downsampling: Downsampling,
+	max_points: usize,
+	series_count: usize,
+) -> usize {
+	let shared = max_points / series_count.max(1);
+	match (window, downsampling) {
+		(0, _) => shared,
+		// Every point is drawn, so the window can't go past the shared max_points
+		(window, Downsampling::None) => window.min(shared),
+		(window, _) => window,
+	}
+}
+
+fn new_point(color: Color, selected_state: SelectedState, scale: f32, timestamp: f64) -> PointData {
+	PointData {
+		x: 0,
+		y: 0,
+		state: PointState::new(color, selected_state, scale, timestamp),
+	}
+}
+
+// For every series, the old point each new point continues from
+fn match_points(old_series: &[SeriesData], series: &[LineChartSeries]) -> Vec<Vec<Option<usize>>> {
+	series
+		.iter()
+		.enumerate()
+		.map(|(series_index, series)| {
+			let keys = series.data.iter().map(|point| point.key.as_deref());
+			match old_series.get(series_index) {
+				Some(old_series) => keys::match_keys(
+					old_series
+						.data
+						.into_iter()
+						.map(|point| point.key.as_deref()),
+					keys,
+				),
+				None => keys.map(|_| None).collect(),
+			}
+		})
+		.collect()
+}
+
+// Drawn points of the old series that no new point continues from
+fn exiting_points(
+	old_series: &[SeriesData],
+	matches: &[Vec<Option<usize>>],
+	timestamp: f64,
+) -> Vec<ExitingPoint> {
+	let mut exiting_points = Vec::new();
+	for (series_index, series) in old_series.iter().enumerate() {
+		if !series.visible {
+			continue;
+		}
+
+		let kept = keys::kept(
+			series.points.len(),
+			matches.get(series_index).map_or(&[], |matches| matches),
+		);
+		exiting_points.extend(
+			series
+				.samples
+				.iter()
+				.filter(|index| !kept[**index])
+				.map(|index| {
+					let point = &series.points[*index];
+					ExitingPoint {
+						x: point.x,
+						y: point.y,
+						scale: point.state.scale,
+						color: point.state.color,
+						point_style: series.point_style,
+						exit_anim: PointExitAnimData {
+							timestamp,
+							scale: AnimationStateData {
+								from: point.state.scale,
+								to: 0.,
+							},
+						},
+					}
+				}),
+		);
+	}
+	exiting_points
+}
+
+#[allow(clippy::too_many_arguments)]
 fn handle_data(
-	mut data: Vec<LineChartDataPoint>,
-	old_points: &[PointData],
-	graph_height: u32,
+	series: Vec<LineChartSeries>,
+	matches: &[Vec<Option<usize>>],
+	old_series: &[SeriesData],
+	downsampling: Downsampling,
+	max_samples: usize,
+	view: (f32, f32),
+	capacity: usize,
 	timestamp: f64,
-) -> (Vec<LineChartDataPoint>, Vec<PointData>, f32, f32, f32) {
-	let mut points: Vec<PointData> = Vec::with_capacity(data.len());
+) -> Vec<SeriesData> {
+	series
+		.into_iter()
+		.enumerate()
+		.map(|(series_index, series)| {
+			let old_points = old_series
+				.get(series_index)
+				.map(|old_series| old_series.points.as_slice())
+				.unwrap_or(&[]);
+			let matches = matches.get(series_index).map_or(&[][..], |matches| matches);
+
+			let points = series
+				.data
+				.iter()
+				.enumerate()
+				.map(|(index, data_point)| {
+					match matches
+						.get(index)
+						.copied()
+						.flatten()
+						.map(|old| &old_points[old])
+					{
+						Some(old_point) => new_point(
+							series.color,
+							old_point.state.selected_state,
+							old_point.state.scale,
+							timestamp,
+						),
+						// New keyed points grow in, without a key there is no telling them apart
+						None => new_point(
+							series.color,
+							SelectedState::None { timestamp },
+							if data_point.key.is_some() { 0. } else { 1.0 },
+							timestamp,
+						),
+					}
+				})
+				.collect();
+
+			let empty_point = new_point(
+				series.color,
+				SelectedState::None { timestamp },
+				1.0,
+				timestamp,
+			);
+			let mut series = SeriesData {
+				name: series.name,
+				color: series.color,
+				point_style: series.point_style,
+				visible: series.visible,
+				data: PreAllocatedCollection::from_vec(
+					series.data,
+					LineChartDataPoint {
+						x: 0.,
+						y: 0.,
+						key: None,
+					},
+					capacity,
+				),
+				points: PreAllocatedCollection::from_vec(points, empty_point, capacity),
+				samples: Vec::new(),
+				before_view: None,
+				after_view: None,
+				legend_x: 0,
+				legend_y: 0,
+			};
+			sample(&mut series, downsampling, max_samples, view);
+			series
+		})
+		.collect()
+}
+
+// Shared bounds of every visible series, so they can all be drawn against the same axes
+fn calculate_domain(series: &[SeriesData], domain: &ValueAxisDomain) -> (f32, f32, Scale) {
 	let mut min_x = 0.;
 	let mut max_x = 0.;
 	let mut max_y = 0.;
-	for data_point in &data {
+	let mut min_positive: Option<f32> = None;
+	for data_point in series
+		.iter()
+		.filter(|series| series.visible)
+		.flat_map(|series| &series.data)
+	{
 		min_x = data_point.x.min(min_x);
 		max_x = data_point.x.max(max_x);
 		max_y = data_point.y.max(max_y);
+		if data_point.y > 0. {
+			min_positive = Some(min_positive.map_or(data_point.y, |min| data_point.y.min(min)));
+		}
 	}
 
-	trace!("handle_data", min_x, max_x, max_y);
+	trace!("calculate_domain", min_x, max_x, max_y);
 
-	for (index, data_point) in &mut data.iter_mut().enumerate() {
-		let mut start_scale_t = 0.;
-		let mut selected_state = SelectedState::None { timestamp };
-		// if index < old_points.len() {
-		// 	let old_bar = &old_points[index];
-		// 	start_scale_t = old_bar.height as f32 / graph_height as f32;
-		// 	selected_state = old_bar.selected_state;
-		// }
+	(min_x, max_x, Scale::new(domain, 0., max_y, min_positive))
+}
 
-		points.push(PointData {
-			x: 0,
-			y: 0,
-			scale: 1.0,
-			color_t: 0.,
-			color: Color {
-				r: 255,
-				g: 255,
-				b: 255,
-				a: 255,
-			},
-			pointer_state: PointerState::None,
-			selected_state,
-			start_scale_t,
-			hover_anim: PointHoverAnimationData {
-				timestamp,
-				scale: AnimationStateData { from: 1.0, to: 1.0 },
-			},
-			clicking_state: ClickingState::None,
-			clicking_point_anim: ClickingPointAnimData {
-				timestamp,
-				color_t: AnimationStateData { from: 0., to: 0. },
-			},
-		});
+// Value axis for only the samples within view, so zooming in on a small part of the data fills the plot
+fn calculate_view_scale(
+	series: &[SeriesData],
+	(min_x, max_x): (f32, f32),
+	domain: &ValueAxisDomain,
+) -> Scale {
+	let mut max_y = 0.;
+	let mut min_positive: Option<f32> = None;
+	for data_point in series
+		.iter()
+		.filter(|series| series.visible)
+		.flat_map(|series| series.samples.iter().map(|index| &series.data[*index]))
+		.filter(|data_point| data_point.x >= min_x && data_point.x <= max_x)
+	{
+		max_y = data_point.y.max(max_y);
+		if data_point.y > 0. {
+			min_positive = Some(min_positive.map_or(data_point.y, |min| data_point.y.min(min)));
+		}
+	}
+
+	Scale::new(domain, 0., max_y, min_positive)
+}
+
+fn find_selected_points(series: &[SeriesData]) -> Vec<PointId> {
+	series
+		.iter()
+		.enumerate()
+		.flat_map(|(series_index, series)| {
+			series
+				.points
+				.as_slice()
+				.iter()
+				.enumerate()
+				.filter(|(_, point)| point.state.is_selected())
+				.map(move |(index, _)| PointId {
+					series: series_index,
+					index,
+				})
+		})
+		.collect()
+}
+
+struct VertexWriter<'a> {
+	positions: &'a mut PreAllocatedCollection<f32>,
+	colors: &'a mut PreAllocatedCollection<f32>,
+	vertex: usize,
+	size: (u32, u32),
+}
+
+impl VertexWriter<'_> {
+	fn push(&mut self, (x, y): (f32, f32), color: Color) {
+		let (width, height) = self.size;
+		let vertex = self.vertex;
+
+		// Convert pixel positions to (-1 to 1) scale
+		self.positions[vertex * 2] = (x / width as f32) * 2. - 1.;
+		self.positions[vertex * 2 + 1] = -((y / height as f32) * 2. - 1.);
+
+		self.colors[vertex * 4] = color.r as f32 / 255.;
+		self.colors[vertex * 4 + 1] = color.g as f32 / 255.;
+		self.colors[vertex * 4 + 2] = color.b as f32 / 255.;
+		self.colors[vertex * 4 + 3] = color.a as f32 / 255.;
+
+		self.vertex += 1;
+	}
+
+	fn finish(self) -> usize {
+		self.positions.set_size(self.vertex * 2);
+		self.colors.set_size(self.vertex * 4);
+		self.vertex
+	}
+}
+
+fn segment_normal(points: &[(f32, f32)], index: usize) -> (f32, f32) {
+	let dx = points[index + 1].0 - points[index].0;
+	let dy = points[index + 1].1 - points[index].1;
+	let length = (dx * dx + dy * dy).sqrt();
+
+	if length < f32::EPSILON {
+		return (0., 1.);
+	}
+
+	(-dy / length, dx / length)
+}
+
+// Offset from a point to the left edge of the line, mitered between the two segments meeting there
+fn miter_offset(points: &[(f32, f32)], index: usize, half_width: f32) -> (f32, f32) {
+	let last_segment = points.len() - 2;
+
+	let (nx, ny) = segment_normal(points, index.min(last_segment));
+	if index == 0 || index > last_segment {
+		return (nx * half_width, ny * half_width);
+	}
 
-		data_point.x /= max_x - min_x;
-		data_point.y /= max_y;
+	let (prev_nx, prev_ny) = segment_normal(points, index - 1);
+	let (mx, my) = (prev_nx + nx, prev_ny + ny);
+	let length = (mx * mx + my * my).sqrt();
+
+	if length < f32::EPSILON {
+		return (nx * half_width, ny * half_width);
+	}
+
+	let (mx, my) = (mx / length, my / length);
+	let miter_length = (half_width / (mx * nx + my * ny)).min(half_width * MITER_LIMIT);
+
+	(mx * miter_length, my * miter_length)
+}
+
+// The relative position is -1 on the right edge of the line, 1 on the left and 0 at the center of a round join
+fn write_line_vertex(
+	writer: &mut VertexWriter,
+	relative_positions: &mut PreAllocatedCollection<f32>,
+	position: (f32, f32),
+	relative_position: f32,
+	color: Color,
+) {
+	relative_positions[writer.vertex] = relative_position;
+	writer.push(position, color);
+}
+
+fn write_line(
+	points: &[(f32, f32)],
+	join: LineJoin,
+	half_width: f32,
+	color: Color,
+	writer: &mut VertexWriter,
+	relative_positions: &mut PreAllocatedCollection<f32>,
+) {
+	for i in 0..points.len().saturating_sub(1) {
+		let start = points[i];
+		let end = points[i + 1];
+
+		let (start_offset, end_offset) = match join {
+			LineJoin::Miter => (
+				miter_offset(points, i, half_width),
+				miter_offset(points, i + 1, half_width),
+			),
+			LineJoin::Round => {
+				let (nx, ny) = segment_normal(points, i);
+				let offset = (nx * half_width, ny * half_width);
+				(offset, offset)
+			}
+		};
+
+		let start_left = (start.0 + start_offset.0, start.1 + start_offset.1);
+		let start_right = (start.0 - start_offset.0, start.1 - start_offset.1);
+		let end_left = (end.0 + end_offset.0, end.1 + end_offset.1);
+		let end_right = (end.0 - end_offset.0, end.1 - end_offset.1);
+
+		// Two triangles per segment
+		for (position, relative_position) in [
+			(start_left, 1.),
+			(start_right, -1.),
+			(end_left, 1.),
+			(end_left, 1.),
+			(start_right, -1.),
+			(end_right, -1.),
+		] {
+			write_line_vertex(
+				writer,
+				relative_positions,
+				position,
+				relative_position,
+				color,
+			);
+		}
+
+		if !matches!(join, LineJoin::Round) || i + 2 >= points.len() {
+			continue;
+		}
+
+		// Fill the gap on the outer side of the joint with a fan around the point
+		let (nx, ny) = segment_normal(points, i);
+		let (next_nx, next_ny) = segment_normal(points, i + 1);
+		let next = points[i + 2];
+		let turns_left = (next.0 - end.0) * nx + (next.1 - end.1) * ny > 0.;
+		let side = if turns_left { -1. } else { 1. };
+
+		let start_angle = (ny * side).atan2(nx * side);
+		let mut sweep = (next_ny * side).atan2(next_nx * side) - start_angle;
+		if sweep > PI {
+			sweep -= TAU;
+		} else if sweep < -PI {
+			sweep += TAU;
+		}
+
+		if sweep.abs() < f32::EPSILON {
+			continue;
+		}
+
+		for segment in 0..ROUND_JOIN_SEGMENTS {
+			let a0 = start_angle + sweep * (segment as f32 / ROUND_JOIN_SEGMENTS as f32);
+			let a1 = start_angle + sweep * ((segment + 1) as f32 / ROUND_JOIN_SEGMENTS as f32);
+
+			for (position, relative_position) in [
+				(end, 0.),
+				(
+					(end.0 + a0.cos() * half_width, end.1 + a0.sin() * half_width),
+					side,
+				),
+				(
+					(end.0 + a1.cos() * half_width, end.1 + a1.sin() * half_width),
+					side,
+				),
+			] {
+				write_line_vertex(
+					writer,
+					relative_positions,
+					position,
+					relative_position,
+					color,
+				);
+			}
+		}
 	}
+}
 
-	(data, points, min_x, max_x, max_y)
+// One quad per curve segment reaching down to the baseline
+fn write_area(
+	points: &[(f32, f32)],
+	baseline: f32,
+	fill_t: f32,
+	color: Color,
+	gradient_height: Option<f32>,
+	writer: &mut VertexWriter,
+) {
+	// Fades from the area color at the top of the graph to transparent at the baseline
+	let color_at = |y: f32| match gradient_height {
+		Some(height) => Color {
+			a: (color.a as f32 * ((baseline - y) / height).clamp(0., 1.)).to_u8(),
+			..color
+		},
+		None => color,
+	};
+
+	for i in 0..points.len().saturating_sub(1) {
+		let (start_x, start_y) = points[i];
+		let (end_x, end_y) = points[i + 1];
+
+		// Grows up from the baseline while the chart animates in
+		let start_y = lerp(baseline, start_y, fill_t);
+		let end_y = lerp(baseline, end_y, fill_t);
+
+		for position in [
+			(start_x, baseline),
+			(start_x, start_y),
+			(end_x, end_y),
+			(end_x, baseline),
+			(start_x, baseline),
+			(end_x, end_y),
+		] {
+			writer.push(position, color_at(position.1));
+		}
+	}
 }
 
 #[wasm_bindgen]
 impl LineChart {
 	#[wasm_bindgen(constructor)]
 	pub fn new(
-		data: Vec<LineChartDataPoint>,
+		mut series: Vec<LineChartSeries>,
 		start_timestamp: f64,
 		width: u32,
 		height: u32,
@@ -200,32 +848,79 @@ impl LineChart {
 	) -> Self {
 		let max_scale_lines = 100;
 
-		let scale_lines = PreAllocatedCollection::new(
-			ScaleLineObject {
-				x: 0,
-				y: 0,
-				width: 0,
-				height: 0,
-				intensity: 0,
-				value: 0.0,
-			},
-			0,
-			max_scale_lines,
-		);
+		let max_x_axis_ticks = 100;
 
-		let (data, points, min_x, max_x, max_y) = handle_data(
-			data,
+		let empty_line = ScaleLineObject {
+			x: 0,
+			y: 0,
+			width: 0,
+			height: 0,
+			intensity: 0,
+			value: 0.0,
+		};
+		let scale_lines = PreAllocatedCollection::new(empty_line, 0, max_scale_lines);
+		let x_axis_ticks = PreAllocatedCollection::new(empty_line, 0, max_x_axis_ticks);
+
+		// max_points is shared between every series
+		let max_points = options.point_options.max_points;
+		let max_series = options.point_options.max_series;
+		if series.len() > max_series {
+			log_warn!(
+				"new: more series than max_series, dropping the rest",
+				series.len(),
+				max_series
+			);
+			series.truncate(max_series);
+		}
+		let downsampling = options.line_options.downsampling;
+		let plot_width = width as i32
+			- layout.positioning.left as i32
+			- layout.value_axis_layout.value_axis_width as i32
+			- layout.positioning.right as i32;
+		let window = options.point_options.window;
+		let max_samples = max_samples(downsampling, plot_width, max_points, series.len());
+		let capacity = series_capacity(window, downsampling, max_points, series.len());
+		let animations = options
+			.animation_options
+			.with_policy(options.animation_policy);
+		let series = handle_data(
+			series,
+			&[],
 			&[],
-			height - layout.positioning.bottom - layout.positioning.top,
+			downsampling,
+			max_samples,
+			(f32::NEG_INFINITY, f32::INFINITY),
+			capacity,
 			start_timestamp,
 		);
-
-		let max_points = options.point_options.max_points;
-
+		let (min_x, max_x, scale) = calculate_domain(&series, layout.value_axis_layout.domain());
+		let interpolation = options.line_options.interpolation;
+		let curve_segments = options.line_options.curve_segments as usize;
+		let points_per_segment = interpolation.max_points_per_segment(curve_segments);
+		// The samples of every series together fit in max_points, each series adds a segment to
+		// the point past either edge of the viewport
+		let max_curve_segments = (max_points + max_series) * points_per_segment;
+		let max_line_vertices = max_curve_segments * (VERTICES_PER_QUAD + ROUND_JOIN_SEGMENTS * 3);
+
+		// One series at a time, which can have every sample plus the two edge points
+		let point_positions = PreAllocatedCollection::new((0., 0.), 0, max_points + 2);
+		let curve = PreAllocatedCollection::new((0., 0.), 0, (max_points + 1) * points_per_segment + 1);
+
+		let max_general_quads =
+			max_scale_lines + max_x_axis_ticks + CROSSHAIR_QUADS + FOCUS_RING_QUADS + BRUSH_QUADS;
 		let vertex_positions_general =
-			PreAllocatedCollection::new(0., 0, max_scale_lines * VERTICES_PER_QUAD * 2);
+			PreAllocatedCollection::new(0., 0, max_general_quads * VERTICES_PER_QUAD * 2);
 		let vertex_colors_general =
-			PreAllocatedCollection::new(0., 0, max_scale_lines * VERTICES_PER_QUAD * 4);
+			PreAllocatedCollection::new(0., 0, max_general_quads * VERTICES_PER_QUAD * 4);
+
+		let vertex_positions_area =
+			PreAllocatedCollection::new(0., 0, max_curve_segments * VERTICES_PER_QUAD * 2);
+		let vertex_colors_area =
+			PreAllocatedCollection::new(0., 0, max_curve_segments * VERTICES_PER_QUAD * 4);
+
+		let vertex_positions_line = PreAllocatedCollection::new(0., 0, max_line_vertices * 2);
+		let vertex_colors_line = PreAllocatedCollection::new(0., 0, max_line_vertices * 4);
+		let vertex_relative_line_positions = PreAllocatedCollection::new(0., 0, max_line_vertices);
 
 		let vertex_positions_points =
 			PreAllocatedCollection::new(0., 0, max_points * VERTICES_PER_QUAD * 2);
@@ -233,13 +928,13 @@ impl LineChart {
 			PreAllocatedCollection::new(0., 0, max_points * VERTICES_PER_QUAD * 4);
 
 		Self {
-			data,
+			series,
 			start_timestamp,
 			width,
 			height,
 			background_color: options.background_color,
-			points,
 			scale_lines,
+			x_axis_ticks,
 			bottom: layout.positioning.bottom,
 			top: layout.positioning.top,
 			left: layout.positioning.left,
@@ -248,21 +943,73 @@ impl LineChart {
 			value_axis_width: layout.value_axis_layout.value_axis_width,
 			value_axis_smallest_scale: layout.value_axis_layout.value_axis_smallest_scale,
 			value_axis_min_pixel_distance: layout.value_axis_layout.value_axis_min_pixel_distance,
-			hover_scale: options.point_options.hover_scale,
+			x_axis_tick_length: layout.x_axis_layout.x_axis_tick_length,
+			x_axis_tick_thickness: layout.x_axis_layout.x_axis_tick_thickness,
+			x_axis_min_tick_distance: layout.x_axis_layout.x_axis_min_tick_distance,
+			legend_swatch_size: layout.legend_layout.swatch_size,
+			legend_item_width: layout.legend_layout.item_width,
+			legend_gap: layout.legend_layout.gap,
 			min_x,
 			max_x,
-			max_y,
+			x_domain: None,
+			view: (min_x, max_x),
+			viewport_from: (min_x, max_x),
+			viewport_anim: ViewportAnimData {
+				timestamp: start_timestamp,
+				t: AnimationStateData { from: 1., to: 1. },
+			},
+			viewport_settled: true,
+			value_axis_domain: layout.value_axis_layout.domain().clone(),
+			domain_scale: scale,
+			scale,
 			is_animating: true,
-			selected_point_index: None,
-			hovered_point_index: None,
-			point_color: options.point_options.color,
-			point_hover_color: options.point_options.hover_color,
-			point_selected_color: options.point_options.selected_color,
+			animations,
+			animation_options: options.animation_options,
+			selected_points: Vec::new(),
+			selected_point_indices: PreAllocatedCollection::new(0., 0, max_points * 2),
+			multi_select: options.selection_options.multi_select(),
+			brush: Brush::new(&options.selection_options),
+			hovered_point: None,
+			focused_point: None,
+			focus_ring: FocusRing::new(&options.focus_ring_options, start_timestamp),
+			crosshair: Crosshair::new(&options.crosshair_options, start_timestamp),
+			exiting_points: Vec::new(),
+			point_ids: Vec::new(),
+			points_index: SpatialIndex::new(),
+			pointer: None,
+			tooltip_options: options.tooltip_options,
+			tooltip: None,
+			gestures: GestureRecognizer::new(&options.gesture_options),
+			line_width: options.line_options.width,
+			line_join: options.line_options.join,
+			interpolation,
+			curve_segments,
+			downsampling,
+			max_points,
+			max_series,
+			window,
+			point_positions,
+			curve,
+			area_enabled: options.area_options.enabled,
+			area_opacity: options.area_options.opacity,
+			area_gradient: options.area_options.gradient,
+			area_fill_t: 0.,
+			point_interaction: PointInteraction {
+				hover_color: options.point_options.hover_color,
+				selected_color: options.point_options.selected_color,
+				hover_scale: options.point_options.hover_scale,
+			},
 			value_axis_color: options.value_axis_color,
 			updated_data: false,
+			has_geometry: false,
 
 			vertex_positions_general,
 			vertex_colors_general,
+			vertex_positions_area,
+			vertex_colors_area,
+			vertex_positions_line,
+			vertex_colors_line,
+			vertex_relative_line_positions,
 			vertex_positions_points,
 			vertex_colors_points,
 		}
@@ -279,32 +1026,225 @@ impl LineChart {
 	pub fn resize(&mut self, width: u32, height: u32) {
 		self.width = width;
 		self.height = height;
+		self.resample();
+		self.points_index.invalidate();
+	}
+
+	fn max_samples(&self, series_count: usize) -> usize {
+		let plot_width =
+			self.width as i32 - self.left as i32 - self.value_axis_width as i32 - self.right as i32;
+		max_samples(self.downsampling, plot_width, self.max_points, series_count)
+	}
+
+	// The number of pixel columns or the viewport changed, so every series is downsampled again
+	fn resample(&mut self) {
+		self.resample_within(self.view);
+	}
+
+	fn resample_within(&mut self, view: (f32, f32)) {
+		let max_samples = self.max_samples(self.series.len());
+		for series in &mut self.series {
+			sample(series, self.downsampling, max_samples, view);
+		}
+		// More samples can leave less room for the points still exiting
+		self.limit_exiting_points();
 	}
 
-	pub fn update_data(&mut self, data: Vec<LineChartDataPoint>, timestamp: f64) {
+	pub fn update_data(&mut self, series: Vec<LineChartSeries>, timestamp: f64) {
 		trace!(format!(
 			"Updating data from {:#?} to {:#?}",
-			self.data, data
+			self.series, series
 		));
-		let (data, points, min_x, max_x, max_y) = handle_data(
-			data,
-			&self.points,
-			self.height - self.bottom - self.top,
+		if series.len() > self.max_series {
+			log_warn!(
+				"update_data: more series than max_series, ignoring the data",
+				series.len(),
+				self.max_series
+			);
+			return;
+		}
+		let max_samples = self.max_samples(series.len());
+		let capacity = series_capacity(
+			self.window,
+			self.downsampling,
+			self.max_points,
+			series.len(),
+		);
+		let matches = match_points(&self.series, &series);
+		let exiting_points = exiting_points(&self.series, &matches, timestamp);
+		self.series = handle_data(
+			series,
+			&matches,
+			&self.series,
+			self.downsampling,
+			max_samples,
+			self.view,
+			capacity,
 			timestamp,
 		);
-		self.data = data;
-		self.points = points;
-		self.min_x = min_x;
-		self.max_x = max_x;
-		self.max_y = max_y;
+		self.exiting_points.extend(exiting_points);
+		self.limit_exiting_points();
+		(self.min_x, self.max_x, self.domain_scale) =
+			calculate_domain(&self.series, &self.value_axis_domain);
+		self.points_index.invalidate();
+		self.selected_points = find_selected_points(&self.series);
+		// Focus follows its point when it moves
+		self.focused_point = self.focused_point.and_then(|id| {
+			let index = matches
+				.get(id.series)?
+				.iter()
+				.position(|old| *old == Some(id.index))?;
+			Some(PointId {
+				series: id.series,
+				index,
+			})
+		});
 		self.start_timestamp = timestamp;
 		self.updated_data = true;
 	}
 
+	// Exiting points are drawn after the current ones, so only as many as there is room for are kept
+	fn limit_exiting_points(&mut self) {
+		let drawn: usize = self.series.iter().map(|series| series.samples.len()).sum();
+		let room = self.max_points.saturating_sub(drawn);
+		if self.exiting_points.len() > room {
+			let overflow = self.exiting_points.len() - room;
+			self.exiting_points.drain(..overflow);
+		}
+	}
+
+	// Adds points to the end of a series without reallocating. With a sliding window the oldest points
+	// are dropped to make room, otherwise nothing is added when the points don't fit
+	pub fn append_points(
+		&mut self,
+		series_index: usize,
+		points: Vec<LineChartDataPoint>,
+		timestamp: f64,
+	) -> bool {
+		let Some(series) = self.series.get(series_index) else {
+			log_warn!("append_points: series index out of bounds", series_index);
+			return false;
+		};
+
+		let capacity = series.data.capacity();
+		let mut points = points.as_slice();
+		let overflow = (series.data.len() + points.len()).saturating_sub(capacity);
+		if self.window > 0 {
+			// Only the newest points fit when there are more than the whole window
+			points = &points[points.len().saturating_sub(capacity)..];
+			self.shift_series(
+				series_index,
+				overflow.min(self.series[series_index].data.len()),
+			);
+		} else if overflow > 0 {
+			log_warn!(
+				"append_points: series is full, ignoring the points",
+				series_index,
+				capacity
+			);
+			return false;
+		}
+
+		let series = &mut self.series[series_index];
+		for data_point in points {
+			series.data.push(data_point.clone());
+			series.points.push(new_point(
+				series.color,
+				SelectedState::None { timestamp },
+				0.,
+				timestamp,
+			));
+		}
+
+		self.handle_streamed_data(series_index);
+		true
+	}
+
+	// Drops the oldest points of a series, the remaining points keep their animation state
+	pub fn shift_points(&mut self, series_index: usize, count: usize) {
+		if series_index >= self.series.len() {
+			log_warn!("shift_points: series index out of bounds", series_index);
+			return;
+		}
+
+		self.shift_series(series_index, count);
+		self.handle_streamed_data(series_index);
+	}
+
+	fn shift_series(&mut self, series_index: usize, count: usize) {
+		if count == 0 {
+			return;
+		}
+
+		let series = &mut self.series[series_index];
+		series.data.shift(count);
+		series.points.shift(count);
+
+		let shift = |point: PointId| {
+			if point.series != series_index {
+				Some(point)
+			} else {
+				point.index.checked_sub(count).map(|index| PointId {
+					series: series_index,
+					index,
+				})
+			}
+		};
+		self.selected_points = self
+			.selected_points
+			.iter()
+			.copied()
+			.filter_map(shift)
+			.collect();
+		self.focused_point = self.focused_point.and_then(shift);
+	}
+
+	fn handle_streamed_data(&mut self, series_index: usize) {
+		let max_samples = self.max_samples(self.series.len());
+		sample(
+			&mut self.series[series_index],
+			self.downsampling,
+			max_samples,
+			self.view,
+		);
+		self.limit_exiting_points();
+
+		(self.min_x, self.max_x, self.domain_scale) =
+			calculate_domain(&self.series, &self.value_axis_domain);
+		self.points_index.invalidate();
+		self.updated_data = true;
+	}
+
+	pub fn set_series_visible(&mut self, index: usize, visible: bool, timestamp: f64) {
+		let Some(series) = self.series.get_mut(index) else {
+			log_warn!("set_series_visible: series index out of bounds", index);
+			return;
+		};
+
+		if series.visible == visible {
+			return;
+		}
+		series.visible = visible;
+
+		if !visible {
+			for point in &mut series.points {
+				point.state.selected_state = SelectedState::None { timestamp };
+			}
+			self.selected_points.retain(|point| point.series != index);
+			self.focused_point = self.focused_point.filter(|point| point.series != index);
+		}
+
+		(self.min_x, self.max_x, self.domain_scale) =
+			calculate_domain(&self.series, &self.value_axis_domain);
+		self.points_index.invalidate();
+		self.updated_data = true;
+	}
+
 	fn get_scale_line_vertex_positions(&mut self) {
 		let positions = &mut self.vertex_positions_general;
 		trace!(positions.len());
-		for (i, scale_line) in self.scale_lines.into_iter().enumerate() {
+		let lines = self.scale_lines.into_iter().chain(&self.x_axis_ticks);
+		for (i, scale_line) in lines.enumerate() {
 			let vert_index = i * VERTICES_PER_QUAD * 2;
 
 			let left = scale_line.x;
@@ -335,7 +1275,8 @@ impl LineChart {
 
 	fn get_scale_line_vertex_colors(&mut self) {
 		let colors = &mut self.vertex_colors_general;
-		for (i, scale_line) in self.scale_lines.into_iter().enumerate() {
+		let lines = self.scale_lines.into_iter().chain(&self.x_axis_ticks);
+		for (i, scale_line) in lines.enumerate() {
 			let vert_index = i * VERTICES_PER_QUAD * 4;
 
 			let color = self
@@ -352,70 +1293,244 @@ impl LineChart {
 		}
 	}
 
-	fn get_points_vertex_positions(&mut self) -> WasmFloat32Array {
-		let positions = &mut self.vertex_positions_points;
-		positions.set_size(self.points.len() * VERTICES_PER_QUAD * 2);
+	fn calculate_area(&mut self, timestamp: f64) {
+		let anim_data = AreaFillAnimData {
+			timestamp: self.start_timestamp,
+			fill_t: AnimationStateData { from: 0.0, to: 1.0 },
+		};
+		let animation = Animation::new(&anim_data, timestamp, self.animations.enter());
 
-		for (i, point) in self.points.iter().enumerate() {
-			let vert_index = i * VERTICES_PER_QUAD * 2;
+		if !animation.is_completed() {
+			self.is_animating = true;
+		}
 
-			let width = (10 as f32 * point.scale) as u32; // TODO
-			let left_px = point.x as f32 - (width as f32 - 10 as f32) / 2.;
+		self.area_fill_t = animation.get_current().fill_t;
+	}
 
-			// Convert pixel positions to (-1 to 1) scale
-			let left = (left_px / self.width as f32) * 2. - 1.;
-			let right = ((left_px + width as f32) / self.width as f32) * 2. - 1.;
-			let bottom = -(((point.y as f32 + 10 as f32) / self.height as f32) * 2. - 1.); // TODO
-			let top = -((point.y as f32 / self.height as f32) * 2. - 1.);
+	// Interpolates every visible series in turn and appends its area and line geometry
+	fn calculate_series_geometry(&mut self) {
+		let size = (self.width, self.height);
+		let baseline = (self.height as i32 - self.bottom as i32) as f32;
+		let graph_height = (self.height as i32 - self.top as i32 - self.bottom as i32).max(1) as f32;
+		let gradient_height = self.area_gradient.then_some(graph_height);
+		let half_width = self.line_width / 2.;
+
+		// The points just outside the viewport are placed off the plot, where the curve gets clipped
+		let edge = |series: &SeriesData, index: Option<usize>| {
+			index.map(|index| {
+				let data_point = &series.data[index];
+				self.data_to_pixel(data_point.x, data_point.y)
+			})
+		};
+		let edges: Vec<_> = self
+			.series
+			.iter()
+			.map(|series| {
+				(
+					edge(series, series.before_view),
+					edge(series, series.after_view),
+				)
+			})
+			.collect();
+
+		let mut area_writer = VertexWriter {
+			positions: &mut self.vertex_positions_area,
+			colors: &mut self.vertex_colors_area,
+			vertex: 0,
+			size,
+		};
+		let mut line_writer = VertexWriter {
+			positions: &mut self.vertex_positions_line,
+			colors: &mut self.vertex_colors_line,
+			vertex: 0,
+			size,
+		};
+
+		let plot_left = (self.left + self.value_axis_width) as f32;
+		let plot_right = self.width as f32 - self.right as f32;
+
+		for (series_index, (before, after)) in edges.into_iter().enumerate() {
+			let series = &self.series[series_index];
+			if !series.visible {
+				continue;
+			}
 
-			// Every two indices is an x and a y position. Two triangles per loop.
-			positions[vert_index] = left;
-			positions[vert_index + 1] = bottom;
-			positions[vert_index + 2] = left;
-			positions[vert_index + 3] = top;
-			positions[vert_index + 4] = right;
-			positions[vert_index + 5] = top;
+			let positions = before
+				.into_iter()
+				.chain(series.samples.iter().map(|index| {
+					let point = &series.points[*index];
+					(point.x as f32, point.y as f32)
+				}))
+				.chain(after);
+			let mut len = 0;
+			for position in positions.take(self.point_positions.capacity()) {
+				self.point_positions[len] = position;
+				len += 1;
+			}
+			self.point_positions.set_size(len);
 
-			positions[vert_index + 6] = right;
-			positions[vert_index + 7] = bottom;
-			positions[vert_index + 8] = left;
-			positions[vert_index + 9] = bottom;
-			positions[vert_index + 10] = right;
-			positions[vert_index + 11] = top;
+			interpolate(
+				self.point_positions.as_slice(),
+				self.interpolation,
+				self.curve_segments,
+				&mut self.curve,
+			);
+			clip_curve(&mut self.curve, plot_left, plot_right);
+
+			let series = &self.series[series_index];
+
+			if self.area_enabled {
+				let color = Color {
+					a: (series.color.a as f32 * self.area_opacity).to_u8(),
+					..series.color
+				};
+				write_area(
+					self.curve.as_slice(),
+					baseline,
+					self.area_fill_t,
+					color,
+					gradient_height,
+					&mut area_writer,
+				);
+			}
+
+			write_line(
+				self.curve.as_slice(),
+				self.line_join,
+				half_width,
+				series.color,
+				&mut line_writer,
+				&mut self.vertex_relative_line_positions,
+			);
 		}
 
-		positions.into()
+		area_writer.finish();
+		let line_vertices = line_writer.finish();
+		self.vertex_relative_line_positions.set_size(line_vertices);
 	}
 
-	fn get_points_vertex_colors(&mut self) -> WasmFloat32Array {
-		let colors = &mut self.vertex_colors_points;
-		colors.set_size(self.points.len() * VERTICES_PER_QUAD * 4);
+	fn calculate_points_geometry(&mut self) {
+		let mut writer = VertexWriter {
+			positions: &mut self.vertex_positions_points,
+			colors: &mut self.vertex_colors_points,
+			vertex: 0,
+			size: (self.width, self.height),
+		};
+
+		let points = self
+			.series
+			.iter()
+			.filter(|series| series.visible)
+			.flat_map(|series| {
+				series.samples.iter().map(|index| {
+					let point = &series.points[*index];
+					(
+						point.x,
+						point.y,
+						point.state.scale,
+						point.state.color,
+						series.point_style,
+					)
+				})
+			})
+			.chain(self.exiting_points.iter().map(|point| {
+				(
+					point.x,
+					point.y,
+					point.scale,
+					point.color,
+					point.point_style,
+				)
+			}));
+
+		// Points are centered on where the value is, which is where the line goes through
+		for (x, y, scale, color, point_style) in points {
+			let half_size = self.point_radius as f32 * scale / 2.;
+			let left = x as f32 - half_size;
+			let right = x as f32 + half_size;
+			let top = y as f32 - half_size;
+			let bottom = y as f32 + half_size;
+
+			let positions = match point_style {
+				PointStyle::Square => [
+					(left, bottom),
+					(left, top),
+					(right, top),
+					(right, bottom),
+					(left, bottom),
+					(right, top),
+				],
+				PointStyle::Diamond => {
+					let center_x = (left + right) / 2.;
+					let center_y = (top + bottom) / 2.;
+					[
+						(center_x, top),
+						(right, center_y),
+						(center_x, bottom),
+						(center_x, bottom),
+						(left, center_y),
+						(center_x, top),
+					]
+				}
+				PointStyle::Hidden => continue,
+			};
 
-		for (i, point) in self.points.iter().enumerate() {
-			let vert_index = i * VERTICES_PER_QUAD * 4;
+			for position in positions {
+				writer.push(position, color);
+			}
+		}
 
-			let color = point.color;
+		writer.finish();
+	}
 
-			for offset in 0..VERTICES_PER_QUAD {
-				let offset = offset * 4;
-				colors[vert_index + offset] = color.r as f32 / 255.;
-				colors[vert_index + offset + 1] = color.g as f32 / 255.;
-				colors[vert_index + offset + 2] = color.b as f32 / 255.;
-				colors[vert_index + offset + 3] = color.a as f32 / 255.;
+	fn calculate_legend(&mut self) {
+		let start_x = self.left + self.value_axis_width;
+		let end_x = self.width.saturating_sub(self.right);
+
+		// Items flow left to right from the top left of the graph and wrap onto new rows
+		let mut x = start_x;
+		let mut y = self.legend_gap;
+		for series in &mut self.series {
+			if x > start_x && x + self.legend_item_width > end_x {
+				x = start_x;
+				y += self.legend_swatch_size + self.legend_gap;
 			}
-		}
 
-		colors.into()
+			series.legend_x = x;
+			series.legend_y = y;
+
+			x += self.legend_item_width + self.legend_gap;
+		}
 	}
 
 	fn get_general_vertex_positions(&mut self) -> WasmFloat32Array {
 		trace!("get_general_vertex_positions");
 
-		self
-			.vertex_positions_general
-			.set_size(self.scale_lines.len() * VERTICES_PER_QUAD * 2);
+		let scale_lines_len = self.scale_lines.len() + self.x_axis_ticks.len();
+		let crosshair_len = self.crosshair.quads_len();
+		let focus_ring_len = self.focus_ring.quads_len();
+		self.vertex_positions_general.set_size(
+			(scale_lines_len + crosshair_len + focus_ring_len + self.brush.quads_len())
+				* VERTICES_PER_QUAD
+				* 2,
+		);
 
 		self.get_scale_line_vertex_positions();
+		self.crosshair.write_vertex_positions(
+			&mut self.vertex_positions_general,
+			scale_lines_len,
+			(self.width, self.height),
+		);
+		self.focus_ring.write_vertex_positions(
+			&mut self.vertex_positions_general,
+			scale_lines_len + crosshair_len,
+			(self.width, self.height),
+		);
+		self.brush.write_vertex_positions(
+			&mut self.vertex_positions_general,
+			scale_lines_len + crosshair_len + focus_ring_len,
+			(self.width, self.height),
+		);
 
 		(&self.vertex_positions_general).into()
 	}
@@ -423,25 +1538,73 @@ impl LineChart {
 	fn get_general_vertex_colors(&mut self) -> WasmFloat32Array {
 		trace!("get_general_vertex_colors");
 
-		self
-			.vertex_colors_general
-			.set_size(self.scale_lines.len() * VERTICES_PER_QUAD * 4);
+		let scale_lines_len = self.scale_lines.len() + self.x_axis_ticks.len();
+		let crosshair_len = self.crosshair.quads_len();
+		let focus_ring_len = self.focus_ring.quads_len();
+		self.vertex_colors_general.set_size(
+			(scale_lines_len + crosshair_len + focus_ring_len + self.brush.quads_len())
+				* VERTICES_PER_QUAD
+				* 4,
+		);
 
 		self.get_scale_line_vertex_colors();
+		self
+			.crosshair
+			.write_vertex_colors(&mut self.vertex_colors_general, scale_lines_len);
+		self.focus_ring.write_vertex_colors(
+			&mut self.vertex_colors_general,
+			scale_lines_len + crosshair_len,
+		);
+		self.brush.write_vertex_colors(
+			&mut self.vertex_colors_general,
+			scale_lines_len + crosshair_len + focus_ring_len,
+		);
 
 		(&self.vertex_colors_general).into()
 	}
 
-	pub fn get_points_len(&self) -> usize {
-		self.points.len()
+	pub fn get_series_count(&self) -> usize {
+		self.series.len()
+	}
+
+	pub fn get_series_name_at(&self, index: usize) -> String {
+		self.series[index].name.clone()
+	}
+
+	pub fn get_series_color_at(&self, index: usize) -> Color {
+		self.series[index].color
+	}
+
+	pub fn get_series_visible_at(&self, index: usize) -> bool {
+		self.series[index].visible
+	}
+
+	pub fn get_legend_swatch_x_at(&self, index: usize) -> u32 {
+		self.series[index].legend_x
+	}
+
+	pub fn get_legend_swatch_y_at(&self, index: usize) -> u32 {
+		self.series[index].legend_y
+	}
+
+	pub fn get_legend_swatch_size(&self) -> u32 {
+		self.legend_swatch_size
+	}
+
+	pub fn get_legend_item_width(&self) -> u32 {
+		self.legend_item_width
+	}
+
+	pub fn get_points_len(&self, series: usize) -> usize {
+		self.series[series].points.len()
 	}
 
-	pub fn get_point_x_at(&self, index: usize) -> u32 {
-		self.points[index].x
+	pub fn get_point_x_at(&self, series: usize, index: usize) -> u32 {
+		self.series[series].points[index].x
 	}
 
-	pub fn get_point_y_at(&self, index: usize) -> u32 {
-		self.points[index].y
+	pub fn get_point_y_at(&self, series: usize, index: usize) -> u32 {
+		self.series[series].points[index].y
 	}
 
 	pub fn get_scale_lines_count(&self) -> usize {
@@ -460,276 +1623,825 @@ impl LineChart {
 		self.scale_lines[index].value
 	}
 
+	pub fn get_x_axis_ticks_count(&self) -> usize {
+		self.x_axis_ticks.len()
+	}
+
+	pub fn get_x_axis_tick_x_at(&self, index: usize) -> u32 {
+		let tick = &self.x_axis_ticks[index];
+		tick.x + tick.width / 2
+	}
+
+	pub fn get_x_axis_tick_y_at(&self, index: usize) -> u32 {
+		self.x_axis_ticks[index].y
+	}
+
+	pub fn get_x_axis_tick_value_at(&self, index: usize) -> f32 {
+		self.x_axis_ticks[index].value
+	}
+
+	// Applies to animations already running too, so they jump to where they end up
+	pub fn set_animation_policy(&mut self, policy: AnimationPolicy) {
+		self.animations = self.animation_options.with_policy(policy);
+		self.is_animating = true;
+	}
+
 	pub fn get_is_animating(&self) -> bool {
 		self.is_animating
 	}
 
+	pub fn get_max_general_vertices(&self) -> usize {
+		self.vertex_colors_general.capacity() / 4
+	}
+
+	pub fn get_max_area_vertices(&self) -> usize {
+		self.vertex_colors_area.capacity() / 4
+	}
+
+	pub fn get_max_line_vertices(&self) -> usize {
+		self.vertex_colors_line.capacity() / 4
+	}
+
+	pub fn get_max_points_vertices(&self) -> usize {
+		self.vertex_colors_points.capacity() / 4
+	}
+
+	// In pixels, the line shader fades out the last one on either side
+	pub fn get_line_half_width(&self) -> f32 {
+		self.line_width / 2.
+	}
+
+	// The most recently selected point
+	pub fn get_selected_point_series(&self) -> Option<usize> {
+		self.selected_points.last().map(|point| point.series)
+	}
+
 	pub fn get_selected_point_index(&self) -> Option<usize> {
-		self.selected_point_index
+		self.selected_points.last().map(|point| point.index)
+	}
+
+	// Series and index pairs of every selected point, in the order they were selected. Downsampled data
+	// can have more selected points than there is room for, then only the latest are given
+	pub fn get_selected_point_indices(&mut self) -> WasmFloat32Array {
+		let indices = &mut self.selected_point_indices;
+		let count = self.selected_points.len().min(indices.capacity() / 2);
+		let skipped = self.selected_points.len() - count;
+		indices.set_size(count * 2);
+		for (i, point) in self.selected_points[skipped..].iter().enumerate() {
+			indices[i * 2] = point.series as f32;
+			indices[i * 2 + 1] = point.index as f32;
+		}
+
+		indices.into()
+	}
+
+	pub fn get_hovered_point_series(&self) -> Option<usize> {
+		self.hovered_point.map(|point| point.series)
 	}
 
 	pub fn get_hovered_point_index(&self) -> Option<usize> {
-		self.hovered_point_index
-	}
-
-	fn toggle_point_selection_at(&mut self, index: usize, timestamp: f64) {
-		for i in 0..self.points.len() {
-			let selected = i == index;
-
-			if selected {
-				if let SelectedState::Selected { timestamp: _ } = self.points[i].selected_state {
-					trace!("Deselect point", i);
-					self.points[i].selected_state = SelectedState::None { timestamp };
-					self.selected_point_index = None;
-				} else {
-					trace!("Select point", i);
-					self.points[i].selected_state = SelectedState::Selected { timestamp };
-					self.selected_point_index = Some(index);
+		self.hovered_point.map(|point| point.index)
+	}
+
+	pub fn get_focused_point_series(&self) -> Option<usize> {
+		self.focused_point.map(|point| point.series)
+	}
+
+	pub fn get_focused_point_index(&self) -> Option<usize> {
+		self.focused_point.map(|point| point.index)
+	}
+
+	// Selects the point, either on its own or added to the current selection
+	pub fn select_point(
+		&mut self,
+		series_index: usize,
+		index: usize,
+		additive: bool,
+		timestamp: f64,
+	) {
+		let id = PointId {
+			series: series_index,
+			index,
+		};
+		if !self.focusable_points().any(|point| point == id) {
+			log_warn!("select_point: no drawn point at", series_index, index);
+			return;
+		}
+
+		if !additive || !self.multi_select {
+			for point in self.selected_points.clone() {
+				if point != id {
+					self.set_point_selected(point, false, timestamp);
 				}
-			} else if let SelectedState::None { timestamp: _ } = self.points[i].selected_state {
-			} else {
-				self.points[i].selected_state = SelectedState::None { timestamp };
 			}
 		}
+		self.set_point_selected(id, true, timestamp);
+		self.is_animating = true;
 	}
 
-	fn deselect_points(&mut self, timestamp: f64) {
-		for i in 0..self.points.len() {
-			let selected = matches!(
-				self.points[i].selected_state,
-				SelectedState::Selected { timestamp: _ }
-			);
+	pub fn clear_selection(&mut self, timestamp: f64) {
+		if !self.selected_points.is_empty() {
+			self.deselect_points(timestamp);
+			self.is_animating = true;
+		}
+	}
 
-			if selected {
-				trace!("Deselect point", i);
-				self.points[i].selected_state = SelectedState::None { timestamp };
-			}
+	// Moves focus to the next drawn point, on to the next series after the last point and wrapping
+	// around to the first
+	pub fn focus_next(&mut self) {
+		let points: Vec<PointId> = self.focusable_points().collect();
+		let position = self
+			.focused_point
+			.and_then(|id| points.iter().position(|point| *point == id));
+		let next = match position {
+			Some(position) => points.get((position + 1) % points.len()),
+			None => points.first(),
+		};
+		self.focus_point(next.copied());
+	}
+
+	// Moves focus to the previous drawn point, wrapping around to the last
+	pub fn focus_previous(&mut self) {
+		let points: Vec<PointId> = self.focusable_points().collect();
+		let position = self
+			.focused_point
+			.and_then(|id| points.iter().position(|point| *point == id));
+		let previous = match position {
+			Some(position) => points.get(position.checked_sub(1).unwrap_or(points.len() - 1)),
+			None => points.last(),
+		};
+		self.focus_point(previous.copied());
+	}
+
+	pub fn clear_focus(&mut self) {
+		if self.focused_point.is_some() {
+			self.focused_point = None;
+			self.is_animating = true;
 		}
-		self.selected_point_index = None;
 	}
 
-	fn calculate_points(
-		&mut self,
-		timestamp: f64,
-		pointer_x: Option<u32>,
-		pointer_y: Option<u32>,
-		clicking_state: ClickingState,
-	) {
-		trace!("calculate_points");
-		let points_count = self.data.len();
+	// Works out the tooltip for where the pointer is, placed for a tooltip of the given size.
+	// Returns whether there is one
+	pub fn calculate_tooltip(&mut self, tooltip_width: u32, tooltip_height: u32) -> bool {
+		let points = match self.tooltip_options.mode() {
+			TooltipMode::Hovered => self.hovered_point.into_iter().collect(),
+			TooltipMode::NearestX => self.points_at_nearest_x(),
+		};
+
+		let Some(anchor) = points
+			.first()
+			.map(|id| self.series[id.series].points[id.index].x)
+			.zip(
+				points
+					.iter()
+					.map(|id| self.series[id.series].points[id.index].y)
+					.min(),
+			)
+		else {
+			self.tooltip = None;
+			return false;
+		};
+
+		let entries = points
+			.iter()
+			.map(|id| TooltipEntry {
+				series: id.series,
+				index: id.index,
+				name: self.series[id.series].name.clone(),
+				value: self.series[id.series].data[id.index].y,
+			})
+			.collect();
+
+		self.tooltip = Some(Tooltip::new(
+			anchor,
+			(tooltip_width, tooltip_height),
+			self.tooltip_options.offset(),
+			(
+				self.left,
+				self.top,
+				self.width.saturating_sub(self.right),
+				self.height.saturating_sub(self.bottom),
+			),
+			entries,
+		));
+		true
+	}
 
-		let mut left = self.left + self.value_axis_width;
+	pub fn get_tooltip_anchor_x(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.anchor_x)
+	}
 
-		let bottom = self.bottom;
+	pub fn get_tooltip_anchor_y(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.anchor_y)
+	}
 
-		let width = self.width as i32 - left as i32 - self.right as i32;
-		let height = self.height as i32 - self.top as i32 - bottom as i32;
+	pub fn get_tooltip_x(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.x)
+	}
 
-		let mut all_animations_done = true;
-		let mut any_point_was_clicked = false;
-		self.hovered_point_index = None;
+	pub fn get_tooltip_y(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.y)
+	}
 
-		for point_index in 0..points_count {
-			let point = &mut self.points[point_index];
+	pub fn get_tooltip_width(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.width)
+	}
 
-			let x_pos = (point_index as f32 * 20. + left as f32).to_u32(); // TODO: calculate x pos based on data
-			let x_pos = (self.data[point_index].x * width as f32).to_u32() + left;
+	pub fn get_tooltip_height(&self) -> Option<u32> {
+		self.tooltip.as_ref().map(|tooltip| tooltip.height)
+	}
 
-			let height = (height as f32 * self.data[point_index].y).to_u32();
-			let y_pos = (self.height as i32 - bottom as i32 - height as i32).to_u32();
+	pub fn get_tooltip_entries_len(&self) -> usize {
+		self
+			.tooltip
+			.as_ref()
+			.map_or(0, |tooltip| tooltip.entries.len())
+	}
 
-			point.x = x_pos;
-			point.y = y_pos;
+	pub fn get_tooltip_entry_series_at(&self, index: usize) -> Option<usize> {
+		self.tooltip_entry_at(index).map(|entry| entry.series)
+	}
 
-			if let Some(pointer_x) = pointer_x
-				&& let Some(pointer_y) = pointer_y
-				&& pointer_x >= x_pos
-				&& pointer_x <= x_pos + self.point_radius
-				&& pointer_y >= y_pos
-				&& pointer_y <= y_pos + self.point_radius
-			{
-				self.hovered_point_index = Some(point_index);
-
-				if let PointerState::Hover = point.pointer_state {
-				} else {
-					point.pointer_state = PointerState::Hover;
-					point.hover_anim = PointHoverAnimationData {
-						timestamp,
-						scale: AnimationStateData {
-							from: point.scale,
-							to: self.hover_scale,
-						},
-					};
-				}
+	pub fn get_tooltip_entry_index_at(&self, index: usize) -> Option<usize> {
+		self.tooltip_entry_at(index).map(|entry| entry.index)
+	}
 
-				match clicking_state {
-					ClickingState::Holding => {
-						if let ClickingState::Holding = point.clicking_state {
-						} else {
-							point.clicking_point_anim = ClickingPointAnimData {
-								timestamp,
-								color_t: AnimationStateData {
-									from: point.color_t,
-									to: self.point_hover_color.a as f32 / 255.,
-								},
-							};
-							point.clicking_state = ClickingState::Holding;
-						}
-					}
-					ClickingState::JustReleased => {
-						point.clicking_point_anim = ClickingPointAnimData {
-							timestamp,
-							color_t: AnimationStateData {
-								from: point.color_t,
-								to: 0.,
-							},
-						};
-						point.clicking_state = ClickingState::JustReleased;
+	pub fn get_tooltip_entry_name_at(&self, index: usize) -> Option<String> {
+		self.tooltip_entry_at(index).map(|entry| entry.name.clone())
+	}
 
-						self.toggle_point_selection_at(point_index, timestamp);
-						any_point_was_clicked = true;
-					}
-					_ => {}
-				}
-			} else if let PointerState::None = point.pointer_state {
-			} else {
-				point.pointer_state = PointerState::None;
+	pub fn get_tooltip_entry_value_at(&self, index: usize) -> Option<f32> {
+		self.tooltip_entry_at(index).map(|entry| entry.value)
+	}
 
-				point.hover_anim = PointHoverAnimationData {
-					timestamp,
-					scale: AnimationStateData {
-						from: point.scale,
-						to: 1.0,
-					},
-				};
-				point.clicking_point_anim = ClickingPointAnimData {
-					timestamp,
-					color_t: AnimationStateData {
-						from: point.color_t,
-						to: 0.,
-					},
-				};
-				point.clicking_state = ClickingState::None;
-			}
+	fn tooltip_entry_at(&self, index: usize) -> Option<&TooltipEntry> {
+		self
+			.tooltip
+			.as_ref()
+			.and_then(|tooltip| tooltip.entries.get(index))
+	}
+
+	// The pointer snapped to the nearest data X, with the value line through the series closest to the
+	// pointer there and a marker on every series. Only while the pointer is over the plot (left, top,
+	// right, bottom)
+	fn crosshair_target(
+		&self,
+		(left, top, right, bottom): (u32, u32, u32, u32),
+	) -> Option<CrosshairTarget> {
+		let (pointer_x, pointer_y) = self.pointer?;
+		if !self.crosshair.is_enabled()
+			|| !(left..=right).contains(&pointer_x)
+			|| !(top..=bottom).contains(&pointer_y)
+		{
+			return None;
+		}
 
-			let point = &mut self.points[point_index];
+		let markers: Vec<(u32, u32, Color)> = self
+			.points_at_nearest_x()
+			.iter()
+			.map(|id| {
+				let point = &self.series[id.series].points[id.index];
+				(point.x, point.y, self.series[id.series].color)
+			})
+			.collect();
+		let (x, _, _) = markers.first()?;
+		let (_, y, _) = markers
+			.iter()
+			.min_by_key(|(_, y, _)| y.abs_diff(pointer_y))?;
+
+		Some(CrosshairTarget {
+			x: *x,
+			y: *y,
+			markers,
+		})
+	}
+
+	// The drawn point horizontally closest to the pointer, followed by the point of every other visible
+	// series closest to its X
+	fn points_at_nearest_x(&self) -> Vec<PointId> {
+		let Some((pointer_x, _)) = self.pointer else {
+			return Vec::new();
+		};
+		let Some(nearest) = self.focusable_points().min_by_key(|id| {
+			self.series[id.series].points[id.index]
+				.x
+				.abs_diff(pointer_x)
+		}) else {
+			return Vec::new();
+		};
+
+		let x = self.series[nearest.series].data[nearest.index].x;
+		let others = self
+			.series
+			.iter()
+			.enumerate()
+			.filter(|(series_index, series)| series.visible && *series_index != nearest.series)
+			.filter_map(|(series_index, series)| {
+				series
+					.samples
+					.iter()
+					.min_by(|a, b| {
+						(series.data[**a].x - x)
+							.abs()
+							.total_cmp(&(series.data[**b].x - x).abs())
+					})
+					.map(|index| PointId {
+						series: series_index,
+						index: *index,
+					})
+			});
+
+		std::iter::once(nearest).chain(others).collect()
+	}
 
-			let animation = Animation::new(&point.hover_anim, timestamp, 200.0, 0.0);
+	// Drawn points of visible series, in the order focus moves through them
+	fn focusable_points(&self) -> impl Iterator<Item = PointId> {
+		self
+			.series
+			.iter()
+			.enumerate()
+			.filter(|(_, series)| series.visible)
+			.flat_map(|(series_index, series)| {
+				series.samples.iter().map(move |index| PointId {
+					series: series_index,
+					index: *index,
+				})
+			})
+	}
 
-			if !animation.is_completed() {
-				all_animations_done = false;
+	fn focus_point(&mut self, id: Option<PointId>) {
+		if id.is_some() {
+			self.focused_point = id;
+			self.is_animating = true;
+		}
+	}
+
+	fn set_point_selected(&mut self, id: PointId, selected: bool, timestamp: f64) {
+		let point = &mut self.series[id.series].points[id.index];
+		match (point.state.selected_state, selected) {
+			(SelectedState::None { timestamp: _ }, true) => {
+				trace!("Select point", id.series, id.index);
+				point.state.selected_state = SelectedState::Selected { timestamp };
+				self.selected_points.push(id);
+			}
+			(SelectedState::Selected { timestamp: _ }, false) => {
+				trace!("Deselect point", id.series, id.index);
+				point.state.selected_state = SelectedState::None { timestamp };
+				self.selected_points.retain(|selected| *selected != id);
 			}
+			_ => {}
+		}
+	}
 
-			point.scale = animation.get_current().scale;
+	// A click selects only the point, or deselects it when it is the only one selected. Additive clicks
+	// toggle the point and leave the rest of the selection alone
+	fn toggle_point_selection_at(&mut self, id: PointId, additive: bool, timestamp: f64) {
+		let selected = self.selected_points.contains(&id);
+		if additive {
+			self.set_point_selected(id, !selected, timestamp);
+			return;
+		}
 
-			let anim_data = SelectPointAnimData {
-				color_t: match point.selected_state {
-					SelectedState::None { timestamp: _ } => AnimationStateData { from: 1., to: 0. },
-					SelectedState::Selected { timestamp: _ } => AnimationStateData { from: 0., to: 1. },
-				},
-				timestamp: point.selected_state.get_timestamp(),
-			};
-			let animation = Animation::new(&anim_data, timestamp, 200.0, 0.0);
+		let only_selected = selected && self.selected_points.len() == 1;
+		for point in self.selected_points.clone() {
+			if point != id {
+				self.set_point_selected(point, false, timestamp);
+			}
+		}
+		self.set_point_selected(id, !only_selected, timestamp);
+	}
 
-			point.color = self
-				.point_color
-				.lerp(&self.point_selected_color, animation.get_current().color_t);
+	// Selects every drawn point the brushed rectangle touches
+	fn brush_select(&mut self, rect: (u32, u32, u32, u32), additive: bool, timestamp: f64) {
+		if !additive {
+			self.deselect_points(timestamp);
+		}
 
-			if !animation.is_completed() {
-				all_animations_done = false;
+		let size = self.point_radius;
+		let points: Vec<PointId> = self.focusable_points().collect();
+		for id in points {
+			let point = &self.series[id.series].points[id.index];
+			let (left, top) = (
+				point.x.saturating_sub(size / 2),
+				point.y.saturating_sub(size / 2),
+			);
+			if selection::intersects(rect, (left, top, size, size)) {
+				self.set_point_selected(id, true, timestamp);
 			}
+		}
+	}
 
-			let animation = Animation::new(&point.clicking_point_anim, timestamp, 200.0, 0.0);
+	fn deselect_points(&mut self, timestamp: f64) {
+		for series in &mut self.series {
+			for point in &mut series.points {
+				if point.state.is_selected() {
+					trace!("Deselect point", series.name, point.x, point.y);
+					point.state.selected_state = SelectedState::None { timestamp };
+				}
+			}
+		}
+		self.selected_points.clear();
+	}
 
-			if !animation.is_completed() {
-				all_animations_done = false;
+	// Positions only depend on the data and the size of the graph, so they are laid out along with the index
+	fn layout_points(&mut self) {
+		trace!("layout_points");
+		self.position_points();
+
+		self.point_ids.clear();
+		for series_index in 0..self.series.len() {
+			let series = &self.series[series_index];
+			if series.visible {
+				self
+					.point_ids
+					.extend(series.samples.iter().map(|index| PointId {
+						series: series_index,
+						index: *index,
+					}));
 			}
+		}
 
-			point.color_t = animation.get_current().color_t;
+		let half_size = self.point_radius as f32 / 2.;
+		let series = &self.series;
+		self.points_index.rebuild(
+			(self.width, self.height),
+			self.point_ids.iter().enumerate().map(|(item, id)| {
+				let point = &series[id.series].points[id.index];
+				let (x, y) = (point.x as f32, point.y as f32);
+				(
+					item,
+					(x - half_size, y - half_size, x + half_size, y + half_size),
+				)
+			}),
+		);
+	}
 
-			point.color = point.color.lerp(&self.point_hover_color, point.color_t);
+	fn position_points(&mut self) {
+		for series_index in 0..self.series.len() {
+			for point_index in 0..self.series[series_index].points.len() {
+				let data_point = &self.series[series_index].data[point_index];
+				let (x, y) = self.data_to_pixel(data_point.x, data_point.y);
+
+				let point = &mut self.series[series_index].points[point_index];
+				point.x = x.to_u32();
+				point.y = y.to_u32();
+			}
 		}
+	}
 
-		if matches!(clicking_state, ClickingState::JustReleased) && !any_point_was_clicked {
-			self.deselect_points(timestamp);
-			all_animations_done = false;
+	// Where a value is drawn, points outside the viewport land outside the plot
+	fn data_to_pixel(&self, x: f32, y: f32) -> (f32, f32) {
+		let left = self.left + self.value_axis_width;
+		let width = self.width as i32 - left as i32 - self.right as i32;
+		let height = self.height as i32 - self.top as i32 - self.bottom as i32;
+
+		let (min_x, max_x) = self.view;
+		let x_range = if max_x > min_x { max_x - min_x } else { 1. };
+		let x_ratio = (x - min_x) / x_range;
+		let y_ratio = self.scale.normalize(y);
+
+		let x = left as f32 + (x_ratio * width as f32);
+		let y = self.height as f32 - self.bottom as f32 - (height as f32 * y_ratio).to_u32() as f32;
+		(x, y)
+	}
+
+	// Zooms in by factor (out when it is below 1) keeping the X under pointer_x where it is
+	pub fn zoom_at(&mut self, pointer_x: u32, factor: f32, timestamp: f64) {
+		if factor <= 0. || !factor.is_finite() {
+			log_warn!("zoom_at: factor has to be above 0", factor);
+			return;
 		}
 
-		self.is_animating = !all_animations_done;
+		let (min_x, max_x) = self.x_domain.unwrap_or((self.min_x, self.max_x));
+		let left = self.left + self.value_axis_width;
+		let width = (self.width as i32 - left as i32 - self.right as i32).max(1) as f32;
+		let ratio = ((pointer_x as f32 - left as f32) / width).clamp(0., 1.);
+		let anchor = lerp(min_x, max_x, ratio);
+
+		let range = (max_x - min_x) / factor;
+		let min_x = anchor - range * ratio;
+		self.set_viewport(Some((min_x, min_x + range)), timestamp);
 	}
 
-	fn calculate_scale_lines(&mut self) {
-		trace!("calculate_scale_lines");
-		let thickness = 2;
-		let x_offset = self.value_axis_width;
+	// Moves the viewport by dx pixels, positive dx drags the data to the right
+	pub fn pan_by(&mut self, dx: f32, timestamp: f64) {
+		let Some((min_x, max_x)) = self.x_domain else {
+			return;
+		};
 
-		let smallest_scale = self.value_axis_smallest_scale;
-		let min_pixel_dist = self.value_axis_min_pixel_distance as f32;
+		let left = self.left + self.value_axis_width;
+		let width = (self.width as i32 - left as i32 - self.right as i32).max(1) as f32;
+		let offset = dx / width * (max_x - min_x);
+		self.set_viewport(Some((min_x - offset, max_x - offset)), timestamp);
+	}
 
-		let height = (self.height as i32 - self.top as i32 - self.bottom as i32) as f32;
-		let mut pixel_distance = (smallest_scale / self.max_y) * height;
+	pub fn set_x_domain(&mut self, min: f32, max: f32, timestamp: f64) {
+		if max.is_nan() || min.is_nan() || max <= min {
+			log_warn!("set_x_domain: max has to be above min", min, max);
+			return;
+		}
 
-		let mut mult: i64 = 1;
+		self.x_domain = Some((min, max));
+		self.animate_viewport(timestamp);
+	}
 
-		if pixel_distance < min_pixel_dist {
-			mult = (min_pixel_dist / pixel_distance).ceil_nearest_power_2() as i64;
-			pixel_distance *= mult as f32;
+	pub fn reset_zoom(&mut self, timestamp: f64) {
+		if self.x_domain.is_some() {
+			self.x_domain = None;
+			self.animate_viewport(timestamp);
 		}
+	}
 
-		if pixel_distance < 1. {
-			log_warn!("pixel_distance < 1");
-			return;
+	// The X range zoomed or panned to, even while still animating towards it
+	pub fn get_x_domain_min(&self) -> f32 {
+		self.x_domain.map_or(self.min_x, |(min_x, _)| min_x)
+	}
+
+	pub fn get_x_domain_max(&self) -> f32 {
+		self.x_domain.map_or(self.max_x, |(_, max_x)| max_x)
+	}
+
+	// Keeps zooming and panning within the data, zooming out past all of it resets the zoom
+	fn set_viewport(&mut self, domain: Option<(f32, f32)>, timestamp: f64) {
+		self.x_domain = domain.and_then(|(min_x, max_x)| {
+			let range = max_x - min_x;
+			if range >= self.max_x - self.min_x {
+				return None;
+			}
+
+			let min_x = min_x.clamp(self.min_x, self.max_x - range);
+			Some((min_x, min_x + range))
+		});
+		self.animate_viewport(timestamp);
+	}
+
+	// Skips the animation, so that the view follows the fingers
+	fn jump_viewport(&mut self, timestamp: f64) {
+		self.viewport_from = self.x_domain.unwrap_or((self.min_x, self.max_x));
+		self.viewport_anim = ViewportAnimData {
+			timestamp,
+			t: AnimationStateData { from: 1., to: 1. },
+		};
+		self.viewport_settled = false;
+	}
+
+	fn animate_viewport(&mut self, timestamp: f64) {
+		self.viewport_from = self.view;
+		self.viewport_anim = ViewportAnimData {
+			timestamp,
+			t: AnimationStateData { from: 0., to: 1. },
+		};
+		self.viewport_settled = false;
+		self.is_animating = true;
+
+		// Sampled once for everything the animation passes over, rather than again on every frame
+		let (to_min, to_max) = self.x_domain.unwrap_or((self.min_x, self.max_x));
+		let (from_min, from_max) = self.viewport_from;
+		self.resample_within((from_min.min(to_min), from_max.max(to_max)));
+	}
+
+	// Moves the view towards the viewport, the data range when there is no zoom. Returns whether it is
+	// done moving
+	fn calculate_viewport(&mut self, timestamp: f64) -> bool {
+		let (to_min, to_max) = self.x_domain.unwrap_or((self.min_x, self.max_x));
+		let animation = Animation::new(&self.viewport_anim, timestamp, self.animations.viewport());
+		let t = animation.get_current().t;
+		let done = animation.is_completed();
+		let (from_min, from_max) = self.viewport_from;
+		let view = (lerp(from_min, to_min, t), lerp(from_max, to_max, t));
+
+		let view_changed = view != self.view;
+		self.view = view;
+		// A finished animation follows the data range straight away when it changes
+		let settling = done && (!self.viewport_settled || view_changed);
+		if settling {
+			self.viewport_settled = true;
+			self.resample();
 		}
 
-		let line_count = (height / pixel_distance).to_u32() + 1;
-		let mut success_line_count = line_count;
-		for i in 1..line_count {
-			let total_pixel_dist = pixel_distance * i as f32;
+		self.scale = if view == (self.min_x, self.max_x) {
+			self.domain_scale
+		} else {
+			calculate_view_scale(&self.series, view, &self.value_axis_domain)
+		};
+
+		// The index is only rebuilt once the view stops moving, until then the points just follow it
+		if settling {
+			self.points_index.invalidate();
+		} else if view_changed && !self.points_index.is_dirty() {
+			self.position_points();
+		}
+
+		done
+	}
 
-			if (self.height as f32 - self.bottom as f32 - total_pixel_dist - self.top as f32)
-				< min_pixel_dist * (2.0 / 3.0)
+	// The point whose center is closest to (x, y), as long as it is within radius
+	fn nearest_point(&self, x: u32, y: u32, radius: f32) -> Option<PointId> {
+		let mut nearest: Option<(PointId, f32)> = None;
+		for item in self.points_index.query(x as f32, y as f32, radius) {
+			let id = self.point_ids[item];
+			let point = &self.series[id.series].points[id.index];
+			let dx = x as f32 - point.x as f32;
+			let dy = y as f32 - point.y as f32;
+			let distance = (dx * dx + dy * dy).sqrt();
+
+			if distance <= radius
+				&& nearest.is_none_or(|(_, nearest_distance)| distance < nearest_distance)
 			{
-				success_line_count -= 1;
-				continue;
+				nearest = Some((id, distance));
 			}
+		}
 
-			let ratio = total_pixel_dist / height;
+		nearest.map(|(id, _)| id)
+	}
 
-			let value = ratio * self.max_y;
+	// The last point whose hit box contains (x, y)
+	fn point_at(&self, x: u32, y: u32) -> Option<PointId> {
+		self
+			.points_index
+			.query(x as f32, y as f32, 0.)
+			.map(|item| self.point_ids[item])
+			.filter(|id| {
+				let point = &self.series[id.series].points[id.index];
+				let half_size = self.point_radius / 2;
+				x.abs_diff(point.x) <= half_size && y.abs_diff(point.y) <= half_size
+			})
+			.max_by_key(|id| (id.series, id.index))
+	}
+
+	pub fn get_nearest_point_series(&self, x: u32, y: u32, radius: f32) -> Option<usize> {
+		self.nearest_point(x, y, radius).map(|id| id.series)
+	}
 
-			let modu = (value.round() % (smallest_scale * mult as f32 * 2.0)).to_u32();
-			let y =
-				(self.height as i32 - self.bottom as i32 - thickness as i32 - total_pixel_dist as i32)
-					.to_u32();
+	pub fn get_nearest_point_index(&self, x: u32, y: u32, radius: f32) -> Option<usize> {
+		self.nearest_point(x, y, radius).map(|id| id.index)
+	}
 
-			let scale_line = &mut self.scale_lines[i as usize];
-			scale_line.x = x_offset;
-			scale_line.y = y;
-			scale_line.width = (self.width as i32 - x_offset as i32).to_u32();
-			scale_line.height = thickness;
-			scale_line.intensity = if modu == 0 { 100 } else { 50 };
-			scale_line.value = value;
+	fn calculate_points(
+		&mut self,
+		timestamp: f64,
+		pointer_x: Option<u32>,
+		pointer_y: Option<u32>,
+		clicking_state: ClickingState,
+		additive: bool,
+	) {
+		trace!("calculate_points");
+		let additive = additive && self.multi_select;
+		if self.points_index.is_dirty() {
+			self.layout_points();
 		}
 
-		let scale_line = &mut self.scale_lines[0];
-		scale_line.x = x_offset;
-		scale_line.y = self.top;
-		scale_line.width = (self.width as i32 - x_offset as i32).to_u32();
-		scale_line.height = thickness;
-		scale_line.intensity = 255;
-		scale_line.value = self.max_y;
+		let mut all_animations_done = true;
+		let mut any_point_was_clicked = false;
+		self.hovered_point = match (pointer_x, pointer_y) {
+			(Some(pointer_x), Some(pointer_y)) => self.point_at(pointer_x, pointer_y),
+			_ => None,
+		};
+
+		let brushed = self.brush.update(pointer_x, pointer_y, clicking_state);
+		if self.brush.is_brushing() {
+			all_animations_done = false;
+		}
 
-		let scale_line = &mut self.scale_lines[success_line_count as usize];
-		scale_line.x = x_offset;
-		scale_line.y = (self.height as i32 - self.bottom as i32 - thickness as i32).to_u32();
-		scale_line.width = (self.width as i32 - x_offset as i32).to_u32();
-		scale_line.height = thickness;
-		scale_line.intensity = 200;
-		scale_line.value = 0.0;
+		let interaction = self.point_interaction;
+		for series_index in 0..self.series.len() {
+			let series_color = self.series[series_index].color;
 
-		self
-			.scale_lines
-			.set_size(usize::try_from(success_line_count).unwrap_or(usize::MAX - 1) + 1);
+			for sample in 0..self.series[series_index].samples.len() {
+				let point_index = self.series[series_index].samples[sample];
+				let id = PointId {
+					series: series_index,
+					index: point_index,
+				};
+
+				let point = &mut self.series[series_index].points[point_index];
+				if point.state.update_pointer(
+					self.hovered_point == Some(id),
+					clicking_state,
+					&interaction,
+					&self.animations,
+					timestamp,
+				) {
+					// Letting go of a brush selects what it covers rather than the point under the pointer
+					if brushed.is_none() {
+						self.toggle_point_selection_at(id, additive, timestamp);
+					}
+					any_point_was_clicked = true;
+				}
+
+				let point = &mut self.series[series_index].points[point_index];
+				if !point
+					.state
+					.animate(series_color, &interaction, &self.animations, timestamp)
+				{
+					all_animations_done = false;
+				}
+			}
+		}
+
+		if let Some(rect) = brushed {
+			self.brush_select(rect, additive, timestamp);
+			all_animations_done = false;
+		} else if matches!(clicking_state, ClickingState::JustReleased)
+			&& !any_point_was_clicked
+			&& !additive
+		{
+			self.deselect_points(timestamp);
+			all_animations_done = false;
+		}
+
+		// Removed points shrink away and are dropped once they are gone
+		self.exiting_points.retain_mut(|point| {
+			let animation = Animation::new(&point.exit_anim, timestamp, self.animations.exit());
+			point.scale = animation.get_current().scale;
+
+			!animation.is_completed()
+		});
+		if !self.exiting_points.is_empty() {
+			all_animations_done = false;
+		}
+
+		// The focused point may have been sampled away or hidden since it was focused
+		let focus_rect = self
+			.focused_point
+			.filter(|id| self.focusable_points().any(|point| point == *id))
+			.map(|id| {
+				let point = &self.series[id.series].points[id.index];
+				let size = self.point_radius as f32 * point.state.scale;
+				let left = (point.x as f32 - size / 2.).to_u32();
+				let top = (point.y as f32 - size / 2.).to_u32();
+				(left, top, size as u32, size as u32)
+			});
+		if !self
+			.focus_ring
+			.update(focus_rect, self.animations.hover(), timestamp)
+		{
+			all_animations_done = false;
+		}
+
+		let bounds = (
+			self.left + self.value_axis_width,
+			self.top,
+			self.width.saturating_sub(self.right),
+			self.height.saturating_sub(self.bottom),
+		);
+		let crosshair = self.crosshair_target(bounds);
+		if !self
+			.crosshair
+			.update(crosshair, bounds, self.animations.hover(), timestamp)
+		{
+			all_animations_done = false;
+		}
+
+		self.is_animating = !all_animations_done;
+	}
+
+	fn calculate_scale_lines(&mut self) {
+		trace!("calculate_scale_lines");
+		scale::calculate_scale_lines(
+			&mut self.scale_lines,
+			&self.scale,
+			(self.value_axis_width, self.width),
+			(self.top as f32, self.height as f32 - self.bottom as f32),
+			self.value_axis_smallest_scale,
+			self.value_axis_min_pixel_distance as f32,
+		);
+	}
+
+	fn calculate_x_axis(&mut self) {
+		trace!("calculate_x_axis");
+		let left = self.left + self.value_axis_width;
+		let width = self.width as i32 - left as i32 - self.right as i32;
+		let (min_x, max_x) = self.view;
+		let range = max_x - min_x;
+
+		if width <= 0 || range <= 0. || self.x_axis_tick_length == 0 {
+			self.x_axis_ticks.set_size(0);
+			return;
+		}
+
+		let max_ticks = width as usize / self.x_axis_min_tick_distance.max(1) as usize;
+		let step = nice_step(range, max_ticks);
+		let y = (self.height as i32 - self.bottom as i32).to_u32();
+		let thickness = self.x_axis_tick_thickness;
+
+		let capacity = self.x_axis_ticks.get_data().len();
+		let mut count = 0;
+		for value in ticks_in(min_x, max_x, step).take(capacity) {
+			let x = left as f32 + (value - min_x) / range * width as f32;
+
+			let tick = &mut self.x_axis_ticks[count];
+			tick.x = (x - thickness as f32 / 2.).to_u32();
+			tick.y = y;
+			tick.width = thickness;
+			tick.height = self.x_axis_tick_length;
+			tick.intensity = 200;
+			tick.value = value;
+
+			count += 1;
+		}
+
+		self.x_axis_ticks.set_size(count);
 	}
 
 	pub fn update(
@@ -738,27 +2450,261 @@ impl LineChart {
 		pointer_x: Option<u32>,
 		pointer_y: Option<u32>,
 		clicking_state: ClickingState,
+		additive: bool,
+		touches: &[f32],
 	) -> WasmLineChartData {
 		trace!("update");
 
+		let gestures = self.gestures.update(touches, timestamp);
+		for gesture in &gestures {
+			match *gesture {
+				Gesture::Pinch { center_x, factor } => {
+					self.zoom_at(center_x, factor, timestamp);
+					self.jump_viewport(timestamp);
+				}
+				Gesture::Pan { dx } => {
+					self.pan_by(dx, timestamp);
+					self.jump_viewport(timestamp);
+				}
+				_ => {}
+			}
+		}
+		let (pointer_x, pointer_y, clicking_state, additive) =
+			if self.gestures.is_touching() || !gestures.is_empty() {
+				match self.gestures.pointer(&gestures) {
+					Some((x, y, clicking_state, additive)) => (Some(x), Some(y), clicking_state, additive),
+					None => (None, None, ClickingState::None, false),
+				}
+			} else {
+				(pointer_x, pointer_y, clicking_state, additive)
+			};
+
+		self.pointer = pointer_x.zip(pointer_y);
+		let viewport_done = self.calculate_viewport(timestamp);
 		self.calculate_scale_lines();
-		self.calculate_points(timestamp, pointer_x, pointer_y, clicking_state);
+		self.calculate_x_axis();
+		self.calculate_legend();
+		self.calculate_points(timestamp, pointer_x, pointer_y, clicking_state, additive);
+		if !viewport_done || self.gestures.is_pending() {
+			self.is_animating = true;
+		}
+		self.calculate_area(timestamp);
+		self.calculate_series_geometry();
+		self.calculate_points_geometry();
 
 		let vertex_array_general = self.get_general_vertex_positions();
 		let colors_array_general = self.get_general_vertex_colors();
-		let vertex_array_points = self.get_points_vertex_positions();
-		let colors_array_points = self.get_points_vertex_colors();
 
 		if self.updated_data {
 			self.is_animating = true;
 			self.updated_data = false;
 		}
+		self.has_geometry = true;
 
 		WasmLineChartData {
 			vertex_array_general,
 			colors_array_general,
-			vertex_array_points,
-			colors_array_points,
+			vertex_array_area: (&self.vertex_positions_area).into(),
+			colors_array_area: (&self.vertex_colors_area).into(),
+			vertex_array_line: (&self.vertex_positions_line).into(),
+			colors_array_line: (&self.vertex_colors_line).into(),
+			relative_line_positions: (&self.vertex_relative_line_positions).into(),
+			vertex_array_points: (&self.vertex_positions_points).into(),
+			colors_array_points: (&self.vertex_colors_points).into(),
+		}
+	}
+
+	// The last update drawn without WebGL, as RGBA pixels row by row from the top. None until update has
+	// been called, as that is what lays out what is drawn
+	pub fn rasterize(&self) -> Option<Vec<u8>> {
+		self.raster().map(Raster::into_pixels)
+	}
+
+	pub fn rasterize_png(&self) -> Option<Vec<u8>> {
+		self.raster().map(|raster| raster.encode_png())
+	}
+
+	// In the order the WebGL programs draw them
+	fn raster(&self) -> Option<Raster> {
+		if !self.has_geometry {
+			return None;
 		}
+
+		let mut raster = Raster::new(self.width, self.height, self.background_color);
+		raster.fill_triangles(
+			self.vertex_positions_general.as_slice(),
+			self.vertex_colors_general.as_slice(),
+		);
+		raster.fill_triangles(
+			self.vertex_positions_area.as_slice(),
+			self.vertex_colors_area.as_slice(),
+		);
+		raster.fill_lines(
+			self.vertex_positions_line.as_slice(),
+			self.vertex_colors_line.as_slice(),
+			self.vertex_relative_line_positions.as_slice(),
+			self.line_width / 2.,
+		);
+		raster.fill_triangles(
+			self.vertex_positions_points.as_slice(),
+			self.vertex_colors_points.as_slice(),
+		);
+		Some(raster)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::animation::easing::{Easing, EasingType};
+	use crate::graph_types::shared::scale::ScaleType;
+
+	fn timing(duration: f64) -> AnimationTiming {
+		AnimationTiming::new(
+			duration,
+			0.,
+			0.,
+			Easing::new(EasingType::Linear, 0., 0., 0., 0., 0., 0.),
+		)
+	}
+
+	fn series(keys: &str, values: &[f32]) -> LineChartSeries {
+		LineChartSeries::new(
+			keys.to_string(),
+			Color::new(255, 0, 0, 255),
+			PointStyle::Square,
+			true,
+			values
+				.iter()
+				.enumerate()
+				.map(|(x, y)| LineChartDataPoint::new(x as f32, *y, Some(format!("{keys}{x}"))))
+				.collect(),
+		)
+	}
+
+	fn chart(
+		series: Vec<LineChartSeries>,
+		width: u32,
+		height: u32,
+		downsampling: Downsampling,
+		exit: AnimationTiming,
+	) -> LineChart {
+		let white = Color::new(255, 255, 255, 255);
+		let instant = timing(0.);
+
+		LineChart::new(
+			series,
+			0.,
+			width,
+			height,
+			LineChartLayout::new(
+				Positioning::new(0, 0, 0, 0),
+				PointLayout::new(2),
+				ValueAxisLayout::new(
+					0,
+					1.,
+					20,
+					ValueAxisDomain::new(ScaleType::Linear, 10, Some(0.), Some(4.), false),
+				),
+				XAxisLayout::new(0, 0, 50),
+				LegendLayout::new(0, 0, 0),
+			),
+			LineChartOptions::new(
+				Color::new(0, 0, 0, 255),
+				LineOptions::new(4., LineJoin::Miter, Interpolation::Linear, 1, downsampling),
+				AreaOptions::new(false, 0., false),
+				PointOptions::new(white, white, 1., 10, 1, 0),
+				Color::new(0, 0, 255, 255),
+				FocusRingOptions::new(white, 0, 0),
+				CrosshairOptions::new(false, white, 0, 0),
+				SelectionOptions::new(false, false, white),
+				TooltipOptions::new(TooltipMode::Hovered, 0),
+				GestureOptions::new(10, 300., 500., 10),
+				AnimationOptions::new(
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					exit,
+					instant,
+				),
+				AnimationPolicy::Full,
+			),
+		)
+	}
+
+	fn update(chart: &mut LineChart, timestamp: f64) {
+		chart.update(timestamp, None, None, ClickingState::None, false, &[]);
+	}
+
+	#[test]
+	fn resampling_keeps_exiting_points_within_max_points() {
+		let values = [1., 5., 2., 8., 3., 9., 4., 7., 6., 2.];
+		// Four pixel columns, so only four of the ten points are drawn
+		let mut chart = chart(
+			vec![series("a", &values)],
+			4,
+			20,
+			Downsampling::Lttb,
+			timing(1000.),
+		);
+		update(&mut chart, 0.);
+
+		chart.update_data(vec![series("b", &values)], 0.);
+		update(&mut chart, 10.);
+		assert_eq!(chart.exiting_points.len(), 4);
+
+		// Every point is drawn once it is wide enough, leaving no room for the ones still shrinking away
+		chart.resize(100, 20);
+		update(&mut chart, 20.);
+		assert_eq!(chart.series[0].samples.len(), 10);
+		assert!(chart.exiting_points.is_empty());
+	}
+
+	#[test]
+	fn golden_small_chart() {
+		let mut chart = chart(
+			vec![series("a", &[2., 2., 2.])],
+			12,
+			10,
+			Downsampling::None,
+			timing(0.),
+		);
+		assert!(chart.rasterize().is_none());
+		update(&mut chart, 0.);
+		let pixels = chart.rasterize().unwrap();
+
+		// A 4 pixel wide red line through the middle of the 0 to 4 domain, fading out over its outermost
+		// pixels like lines.frag does. Scale lines are blue
+		let rows: Vec<String> = pixels
+			.chunks_exact(12 * 4)
+			.map(|row| {
+				row
+					.chunks_exact(4)
+					.map(|pixel| match pixel {
+						[0, 0, 0, 255] => '.',
+						[255, 0, 0, 255] => '#',
+						[128, 0, 0, 255] => '+',
+						[0, 0, _, 255] => '-',
+						_ => '?',
+					})
+					.collect()
+			})
+			.collect();
+		assert_eq!(
+			rows,
+			[
+				"------------",
+				"------------",
+				"............",
+				"++++++++++++",
+				"############",
+				"############",
+				"++++++++++++",
+				"............",
+				"------------",
+				"------------",
+			]
+		);
 	}
 }
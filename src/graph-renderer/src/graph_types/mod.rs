@@ -1,4 +1,6 @@
 mod bar_chart;
 mod line_chart;
+mod pie_chart;
+mod scatter_chart;
 mod shared;
 mod utils;
@@ -0,0 +1,825 @@
+use proc_macros::*;
+use std::f32::consts::PI;
+use std::f32::consts::TAU;
+use wasm_bindgen::prelude::*;
+
+use crate::DefineAnimation;
+use crate::DefineSpringAnimation;
+use crate::animation::spring::{Spring, SpringStateData};
+use crate::animation::*;
+use crate::graph_types::shared::gestures::{GestureOptions, GestureRecognizer};
+use crate::graph_types::shared::types::ClickingState;
+use crate::graph_types::shared::types::PointerState;
+use crate::graph_types::shared::types::Positioning;
+use crate::graph_types::shared::types::SelectedState;
+use crate::graph_types::utils::*;
+use crate::utils::*;
+use crate::{log_warn, trace};
+
+DefineSpringAnimation!(SliceHoverAnimationData, CurrentSliceHoverAnimData, scale);
+
+DefineAnimation!(SliceSweepAnimData, CurrentSliceSweepAnimData, sweep_t);
+
+DefineAnimation!(SelectSliceAnimData, CurrentSelectSliceAnimData, color_t);
+DefineAnimation!(ClickingSliceAnimData, CurrentClickingSliceAnimData, color_t);
+
+const VERTICES_PER_TRIANGLE: usize = 3;
+
+#[wasm_struct]
+pub struct PieChartDataPoint {
+	value: f32,
+	color: Color,
+}
+
+#[derive(Debug)]
+struct SliceData {
+	start_angle: f32,
+	end_angle: f32,
+	scale: f32,
+	color_t: f32,
+	color: Color,
+	pointer_state: PointerState,
+	selected_state: SelectedState,
+
+	from_start_angle: f32,
+	from_end_angle: f32,
+	hover_anim: SliceHoverAnimationData,
+	clicking_state: ClickingState,
+	clicking_slice_anim: ClickingSliceAnimData,
+}
+
+#[wasm_struct]
+pub struct PieLayout {
+	// Ratio of the outer radius, 0 draws a pie and anything above draws a donut
+	inner_radius: f32,
+	// Number of segments a full circle is split into
+	segments: u32,
+}
+
+#[wasm_struct]
+pub struct PieChartLayout {
+	positioning: Positioning,
+
+	pie_layout: PieLayout,
+}
+
+#[wasm_struct]
+pub struct SliceOptions {
+	hover_color: Color,
+	selected_color: Color,
+	hover_scale: f32,
+	max_slices: usize,
+}
+
+#[wasm_struct]
+pub struct PieChartOptions {
+	slice_options: SliceOptions,
+	gesture_options: GestureOptions,
+	animation_options: AnimationOptions,
+	animation_policy: AnimationPolicy,
+}
+
+#[wasm_bindgen]
+pub struct WasmPieChartData {
+	pub vertex_array_slices: WasmFloat32Array,
+	pub colors_array_slices: WasmFloat32Array,
+}
+
+#[wasm_bindgen]
+pub struct PieChart {
+	data: Vec<PieChartDataPoint>,
+	start_timestamp: f64,
+	width: u32,
+	height: u32,
+
+	slices: Vec<SliceData>,
+
+	slice_hover_color: Color,
+	slice_selected_color: Color,
+
+	bottom: u32,
+	top: u32,
+	left: u32,
+	right: u32,
+
+	inner_radius: f32,
+	segments: u32,
+
+	hover_scale: f32,
+
+	total: f32,
+
+	center_x: f32,
+	center_y: f32,
+	radius: f32,
+
+	is_animating: bool,
+	// The animation options with the animation policy applied
+	animations: AnimationOptions,
+	animation_options: AnimationOptions,
+	max_slices: usize,
+	gestures: GestureRecognizer,
+	selected_slice_index: Option<usize>,
+	hovered_slice_index: Option<usize>,
+
+	updated_data: bool,
+
+	vertex_positions_slices: PreAllocatedCollection<f32>,
+	vertex_colors_slices: PreAllocatedCollection<f32>,
+}
+
+fn handle_data(
+	mut data: Vec<PieChartDataPoint>,
+	old_slices: &[SliceData],
+	timestamp: f64,
+) -> (Vec<PieChartDataPoint>, Vec<SliceData>, f32) {
+	let mut slices: Vec<SliceData> = Vec::with_capacity(data.len());
+	let mut total = 0.0;
+	for data_point in &data {
+		total += data_point.value.max(0.);
+	}
+
+	for (index, data_point) in &mut data.iter_mut().enumerate() {
+		let mut from_start_angle = 0.;
+		let mut from_end_angle = 0.;
+		let mut selected_state = SelectedState::None { timestamp };
+		if index < old_slices.len() {
+			let old_slice = &old_slices[index];
+			from_start_angle = old_slice.start_angle;
+			from_end_angle = old_slice.end_angle;
+			selected_state = old_slice.selected_state;
+		} else if let Some(last_slice) = old_slices.last() {
+			from_start_angle = last_slice.end_angle;
+			from_end_angle = last_slice.end_angle;
+		}
+
+		slices.push(SliceData {
+			start_angle: from_start_angle,
+			end_angle: from_end_angle,
+			scale: 1.0,
+			color_t: 0.,
+			color: data_point.color,
+			pointer_state: PointerState::None,
+			selected_state,
+			from_start_angle,
+			from_end_angle,
+			hover_anim: SliceHoverAnimationData {
+				timestamp,
+				scale: SpringStateData {
+					from: 1.0,
+					to: 1.0,
+					velocity: 0.,
+				},
+			},
+			clicking_state: ClickingState::None,
+			clicking_slice_anim: ClickingSliceAnimData {
+				timestamp,
+				color_t: AnimationStateData { from: 0., to: 0. },
+			},
+		});
+
+		data_point.value = if total > 0. {
+			data_point.value.max(0.) / total
+		} else {
+			0.
+		};
+	}
+
+	(data, slices, total)
+}
+
+fn segment_count(slice: &SliceData, segments: u32) -> usize {
+	let sweep = (slice.end_angle - slice.start_angle).max(0.);
+	((sweep / TAU) * segments as f32).ceil() as usize
+}
+
+// Starts at 12 o'clock and goes clockwise, same as the slices
+fn pointer_angle(dx: f32, dy: f32) -> f32 {
+	(dy.atan2(dx) + PI / 2.).rem_euclid(TAU)
+}
+
+#[wasm_bindgen]
+impl PieChart {
+	#[wasm_bindgen(constructor)]
+	pub fn new(
+		mut data: Vec<PieChartDataPoint>,
+		start_timestamp: f64,
+		width: u32,
+		height: u32,
+		layout: PieChartLayout,
+
+		options: PieChartOptions,
+	) -> PieChart {
+		// The slice buffers only have room for the segments of max_slices
+		let max_slices = options.slice_options.max_slices;
+		if data.len() > max_slices {
+			log_warn!(
+				"new: more slices than max_slices, dropping the rest",
+				data.len(),
+				max_slices
+			);
+			data.truncate(max_slices);
+		}
+		let (data, slices, total) = handle_data(data, &[], start_timestamp);
+
+		let animations = options
+			.animation_options
+			.with_policy(options.animation_policy);
+		let segments = layout.pie_layout.segments.max(3);
+
+		// Every slice can add one extra segment from rounding its segment count up
+		let max_segments = segments as usize + max_slices;
+
+		let vertex_positions_slices =
+			PreAllocatedCollection::new(0., 0, max_segments * VERTICES_PER_TRIANGLE * 2 * 2);
+		let vertex_colors_slices =
+			PreAllocatedCollection::new(0., 0, max_segments * VERTICES_PER_TRIANGLE * 2 * 4);
+
+		PieChart {
+			data,
+			start_timestamp,
+			width,
+			height,
+			slices,
+			bottom: layout.positioning.bottom,
+			top: layout.positioning.top,
+			left: layout.positioning.left,
+			right: layout.positioning.right,
+			inner_radius: layout.pie_layout.inner_radius.clamp(0., 1.),
+			segments,
+			hover_scale: options.slice_options.hover_scale,
+			total,
+			center_x: 0.,
+			center_y: 0.,
+			radius: 0.,
+			is_animating: true,
+			animations,
+			animation_options: options.animation_options,
+			max_slices,
+			gestures: GestureRecognizer::new(&options.gesture_options),
+			selected_slice_index: None,
+			hovered_slice_index: None,
+			slice_hover_color: options.slice_options.hover_color,
+			slice_selected_color: options.slice_options.selected_color,
+			updated_data: false,
+
+			vertex_positions_slices,
+			vertex_colors_slices,
+		}
+	}
+
+	pub fn get_width(&self) -> u32 {
+		self.width
+	}
+
+	pub fn get_height(&self) -> u32 {
+		self.height
+	}
+
+	pub fn resize(&mut self, width: u32, height: u32) {
+		self.width = width;
+		self.height = height;
+	}
+
+	pub fn update_data(&mut self, data: Vec<PieChartDataPoint>, timestamp: f64) {
+		trace!(format!(
+			"Updating data from {:#?} to {:#?}",
+			self.data, data
+		));
+		if data.len() > self.max_slices {
+			log_warn!(
+				"update_data: more slices than max_slices, ignoring the data",
+				data.len(),
+				self.max_slices
+			);
+			return;
+		}
+		let (data, slices, total) = handle_data(data, &self.slices, timestamp);
+		self.data = data;
+		self.slices = slices;
+		self.total = total;
+		self.start_timestamp = timestamp;
+		self.updated_data = true;
+	}
+
+	fn get_slice_vertex_positions(&mut self) -> WasmFloat32Array {
+		let is_donut = self.inner_radius > 0.;
+		let vertices_per_segment = if is_donut {
+			VERTICES_PER_TRIANGLE * 2
+		} else {
+			VERTICES_PER_TRIANGLE
+		};
+
+		let total_segments: usize = self
+			.slices
+			.iter()
+			.map(|slice| segment_count(slice, self.segments))
+			.sum();
+
+		let positions = &mut self.vertex_positions_slices;
+		positions.set_size(total_segments * vertices_per_segment * 2);
+
+		// Convert pixel positions to (-1 to 1) scale
+		let width = self.width as f32;
+		let height = self.height as f32;
+		let to_clip = |x: f32, y: f32| ((x / width) * 2. - 1., -((y / height) * 2. - 1.));
+
+		let mut vert_index = 0;
+		for slice in &self.slices {
+			let segment_count = segment_count(slice, self.segments);
+			let outer_radius = self.radius * slice.scale;
+			let inner_radius = outer_radius * self.inner_radius;
+
+			let sweep = slice.end_angle - slice.start_angle;
+
+			for segment in 0..segment_count {
+				let a0 = slice.start_angle + sweep * (segment as f32 / segment_count as f32);
+				let a1 = slice.start_angle + sweep * ((segment + 1) as f32 / segment_count as f32);
+
+				// Angles start at 12 o'clock and go clockwise
+				let (sin0, cos0) = (a0 - PI / 2.).sin_cos();
+				let (sin1, cos1) = (a1 - PI / 2.).sin_cos();
+
+				let outer0 = to_clip(
+					self.center_x + cos0 * outer_radius,
+					self.center_y + sin0 * outer_radius,
+				);
+				let outer1 = to_clip(
+					self.center_x + cos1 * outer_radius,
+					self.center_y + sin1 * outer_radius,
+				);
+
+				if is_donut {
+					let inner0 = to_clip(
+						self.center_x + cos0 * inner_radius,
+						self.center_y + sin0 * inner_radius,
+					);
+					let inner1 = to_clip(
+						self.center_x + cos1 * inner_radius,
+						self.center_y + sin1 * inner_radius,
+					);
+
+					// Two triangles per segment
+					for (x, y) in [inner0, outer0, outer1, outer1, inner1, inner0] {
+						positions[vert_index] = x;
+						positions[vert_index + 1] = y;
+						vert_index += 2;
+					}
+				} else {
+					let center = to_clip(self.center_x, self.center_y);
+
+					// Triangle fan around the center
+					for (x, y) in [center, outer0, outer1] {
+						positions[vert_index] = x;
+						positions[vert_index + 1] = y;
+						vert_index += 2;
+					}
+				}
+			}
+		}
+
+		positions.into()
+	}
+
+	fn get_slice_vertex_colors(&mut self) -> WasmFloat32Array {
+		let vertices_per_segment = if self.inner_radius > 0. {
+			VERTICES_PER_TRIANGLE * 2
+		} else {
+			VERTICES_PER_TRIANGLE
+		};
+
+		let total_segments: usize = self
+			.slices
+			.iter()
+			.map(|slice| segment_count(slice, self.segments))
+			.sum();
+
+		let colors = &mut self.vertex_colors_slices;
+		colors.set_size(total_segments * vertices_per_segment * 4);
+
+		let mut vert_index = 0;
+		for slice in &self.slices {
+			let segment_count = segment_count(slice, self.segments);
+			let color = slice.color;
+
+			for _ in 0..(segment_count * vertices_per_segment) {
+				colors[vert_index] = color.r as f32 / 255.;
+				colors[vert_index + 1] = color.g as f32 / 255.;
+				colors[vert_index + 2] = color.b as f32 / 255.;
+				colors[vert_index + 3] = color.a as f32 / 255.;
+				vert_index += 4;
+			}
+		}
+
+		colors.into()
+	}
+
+	pub fn get_slices_len(&self) -> usize {
+		self.slices.len()
+	}
+
+	pub fn get_slice_start_angle_at(&self, index: usize) -> f32 {
+		self.slices[index].start_angle
+	}
+
+	pub fn get_slice_end_angle_at(&self, index: usize) -> f32 {
+		self.slices[index].end_angle
+	}
+
+	pub fn get_slice_label_x_at(&self, index: usize) -> f32 {
+		let slice = &self.slices[index];
+		let angle = (slice.start_angle + slice.end_angle) / 2. - PI / 2.;
+		let distance = self.radius * (1. + self.inner_radius) / 2.;
+		self.center_x + angle.cos() * distance
+	}
+
+	pub fn get_slice_label_y_at(&self, index: usize) -> f32 {
+		let slice = &self.slices[index];
+		let angle = (slice.start_angle + slice.end_angle) / 2. - PI / 2.;
+		let distance = self.radius * (1. + self.inner_radius) / 2.;
+		self.center_y + angle.sin() * distance
+	}
+
+	pub fn get_center_x(&self) -> f32 {
+		self.center_x
+	}
+
+	pub fn get_center_y(&self) -> f32 {
+		self.center_y
+	}
+
+	pub fn get_radius(&self) -> f32 {
+		self.radius
+	}
+
+	pub fn get_inner_radius(&self) -> f32 {
+		self.radius * self.inner_radius
+	}
+
+	pub fn get_total(&self) -> f32 {
+		self.total
+	}
+
+	pub fn get_is_animating(&self) -> bool {
+		self.is_animating
+	}
+
+	pub fn set_animation_policy(&mut self, policy: AnimationPolicy) {
+		self.animations = self.animation_options.with_policy(policy);
+		self.is_animating = true;
+	}
+
+	// What the slice buffers were sized for, so the WebGL buffers can be made to match
+	pub fn get_max_slice_vertices(&self) -> usize {
+		self.vertex_colors_slices.capacity() / 4
+	}
+
+	pub fn get_selected_slice_index(&self) -> Option<usize> {
+		self.selected_slice_index
+	}
+
+	pub fn get_hovered_slice_index(&self) -> Option<usize> {
+		self.hovered_slice_index
+	}
+
+	fn toggle_slice_selection_at(&mut self, index: usize, timestamp: f64) {
+		for i in 0..self.slices.len() {
+			let selected = i == index;
+
+			if selected {
+				if let SelectedState::Selected { timestamp: _ } = self.slices[i].selected_state {
+					trace!("Deselect slice", i);
+					self.slices[i].selected_state = SelectedState::None { timestamp };
+					self.selected_slice_index = None;
+				} else {
+					trace!("Select slice", i);
+					self.slices[i].selected_state = SelectedState::Selected { timestamp };
+					self.selected_slice_index = Some(index);
+				}
+			} else if let SelectedState::None { timestamp: _ } = self.slices[i].selected_state {
+			} else {
+				self.slices[i].selected_state = SelectedState::None { timestamp };
+			}
+		}
+	}
+
+	fn deselect_slices(&mut self, timestamp: f64) {
+		for i in 0..self.slices.len() {
+			let selected = matches!(
+				self.slices[i].selected_state,
+				SelectedState::Selected { timestamp: _ }
+			);
+
+			if selected {
+				trace!("Deselect slice", i);
+				self.slices[i].selected_state = SelectedState::None { timestamp };
+			}
+		}
+		self.selected_slice_index = None;
+	}
+
+	fn calculate_slices(
+		&mut self,
+		timestamp: f64,
+		pointer_x: Option<u32>,
+		pointer_y: Option<u32>,
+		clicking_state: ClickingState,
+	) {
+		trace!("calculate_slices");
+		let slices_count = self.data.len();
+
+		let width = (self.width as i32 - self.left as i32 - self.right as i32).max(0) as f32;
+		let height = (self.height as i32 - self.top as i32 - self.bottom as i32).max(0) as f32;
+
+		self.center_x = self.left as f32 + width / 2.;
+		self.center_y = self.top as f32 + height / 2.;
+		// Leave room for hovered slices to grow without being clipped
+		self.radius = width.min(height) / 2. / self.hover_scale.max(1.);
+
+		let mut all_animations_done = true;
+		let mut any_slice_was_clicked = false;
+		self.hovered_slice_index = None;
+
+		let anim_data = SliceSweepAnimData {
+			timestamp: self.start_timestamp,
+			sweep_t: AnimationStateData { from: 0.0, to: 1.0 },
+		};
+		let animation = Animation::new(&anim_data, timestamp, self.animations.enter());
+
+		if !animation.is_completed() {
+			all_animations_done = false;
+		}
+
+		let sweep_t = animation.get_current().sweep_t;
+
+		let pointer = if let Some(pointer_x) = pointer_x
+			&& let Some(pointer_y) = pointer_y
+		{
+			let dx = pointer_x as f32 - self.center_x;
+			let dy = pointer_y as f32 - self.center_y;
+			Some(((dx * dx + dy * dy).sqrt(), pointer_angle(dx, dy)))
+		} else {
+			None
+		};
+
+		let mut angle = 0.;
+		for slice_index in 0..slices_count {
+			let slice = &mut self.slices[slice_index];
+
+			let target_start_angle = angle;
+			let target_end_angle = angle + self.data[slice_index].value * TAU;
+			angle = target_end_angle;
+
+			slice.start_angle = lerp(slice.from_start_angle, target_start_angle, sweep_t);
+			slice.end_angle = lerp(slice.from_end_angle, target_end_angle, sweep_t);
+
+			let outer_radius = self.radius * slice.scale;
+			let inner_radius = outer_radius * self.inner_radius;
+
+			if let Some((distance, pointer_angle)) = pointer
+				&& distance >= inner_radius
+				&& distance <= outer_radius
+				&& pointer_angle >= slice.start_angle
+				&& pointer_angle < slice.end_angle
+			{
+				self.hovered_slice_index = Some(slice_index);
+
+				if let PointerState::Hover = slice.pointer_state {
+				} else {
+					slice.pointer_state = PointerState::Hover;
+					slice.hover_anim = slice.hover_anim.retarget(
+						self.animations.hover(),
+						timestamp,
+						CurrentSliceHoverAnimData {
+							scale: self.hover_scale,
+						},
+					);
+				}
+
+				match clicking_state {
+					ClickingState::Holding => {
+						if let ClickingState::Holding = slice.clicking_state {
+						} else {
+							slice.clicking_slice_anim = ClickingSliceAnimData {
+								timestamp,
+								color_t: AnimationStateData {
+									from: slice.color_t,
+									to: self.slice_hover_color.a as f32 / 255.,
+								},
+							};
+							slice.clicking_state = ClickingState::Holding;
+						}
+					}
+					ClickingState::JustReleased => {
+						slice.clicking_slice_anim = ClickingSliceAnimData {
+							timestamp,
+							color_t: AnimationStateData {
+								from: slice.color_t,
+								to: 0.,
+							},
+						};
+						slice.clicking_state = ClickingState::JustReleased;
+
+						self.toggle_slice_selection_at(slice_index, timestamp);
+						any_slice_was_clicked = true;
+					}
+					_ => {}
+				}
+			} else if let PointerState::None = slice.pointer_state {
+			} else {
+				slice.pointer_state = PointerState::None;
+
+				slice.hover_anim = slice.hover_anim.retarget(
+					self.animations.hover(),
+					timestamp,
+					CurrentSliceHoverAnimData { scale: 1.0 },
+				);
+				slice.clicking_slice_anim = ClickingSliceAnimData {
+					timestamp,
+					color_t: AnimationStateData {
+						from: slice.color_t,
+						to: 0.,
+					},
+				};
+				slice.clicking_state = ClickingState::None;
+			}
+
+			let slice = &mut self.slices[slice_index];
+
+			let animation = Animation::new(&slice.hover_anim, timestamp, self.animations.hover());
+
+			if !animation.is_completed() {
+				all_animations_done = false;
+			}
+
+			slice.scale = animation.get_current().scale;
+
+			let anim_data = SelectSliceAnimData {
+				color_t: match slice.selected_state {
+					SelectedState::None { timestamp: _ } => AnimationStateData { from: 1., to: 0. },
+					SelectedState::Selected { timestamp: _ } => AnimationStateData { from: 0., to: 1. },
+				},
+				timestamp: slice.selected_state.get_timestamp(),
+			};
+			let animation = Animation::new(&anim_data, timestamp, self.animations.select());
+
+			slice.color = self.data[slice_index]
+				.color
+				.lerp(&self.slice_selected_color, animation.get_current().color_t);
+
+			if !animation.is_completed() {
+				all_animations_done = false;
+			}
+
+			let animation = Animation::new(
+				&slice.clicking_slice_anim,
+				timestamp,
+				self.animations.click(),
+			);
+
+			if !animation.is_completed() {
+				all_animations_done = false;
+			}
+
+			slice.color_t = animation.get_current().color_t;
+
+			slice.color = slice.color.lerp(&self.slice_hover_color, slice.color_t);
+		}
+
+		if matches!(clicking_state, ClickingState::JustReleased) && !any_slice_was_clicked {
+			self.deselect_slices(timestamp);
+			all_animations_done = false;
+		}
+
+		self.is_animating = !all_animations_done;
+	}
+
+	pub fn update(
+		&mut self,
+		timestamp: f64,
+		pointer_x: Option<u32>,
+		pointer_y: Option<u32>,
+		clicking_state: ClickingState,
+		touches: &[f32],
+	) -> WasmPieChartData {
+		trace!("update");
+
+		// Slices have nothing to pan or zoom, only taps and long-presses stand in for the pointer
+		let gestures = self.gestures.update(touches, timestamp);
+		let (pointer_x, pointer_y, clicking_state) =
+			if self.gestures.is_touching() || !gestures.is_empty() {
+				match self.gestures.pointer(&gestures) {
+					Some((x, y, clicking_state, _)) => (Some(x), Some(y), clicking_state),
+					None => (None, None, ClickingState::None),
+				}
+			} else {
+				(pointer_x, pointer_y, clicking_state)
+			};
+
+		self.calculate_slices(timestamp, pointer_x, pointer_y, clicking_state);
+
+		let vertex_array_slices = self.get_slice_vertex_positions();
+		let colors_array_slices = self.get_slice_vertex_colors();
+
+		if self.gestures.is_pending() {
+			self.is_animating = true;
+		}
+
+		if self.updated_data {
+			self.is_animating = true;
+			self.updated_data = false;
+		}
+
+		WasmPieChartData {
+			vertex_array_slices,
+			colors_array_slices,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::animation::easing::{Easing, EasingType};
+
+	fn chart(values: &[f32], inner_radius: f32) -> PieChart {
+		let instant = AnimationTiming::new(
+			0.,
+			0.,
+			0.,
+			Easing::new(EasingType::Linear, 0., 0., 0., 0., 0., 0.),
+		);
+		let white = Color::new(255, 255, 255, 255);
+
+		PieChart::new(
+			values
+				.iter()
+				.map(|value| PieChartDataPoint::new(*value, Color::new(255, 0, 0, 255)))
+				.collect(),
+			0.,
+			100,
+			100,
+			PieChartLayout::new(
+				Positioning::new(0, 0, 0, 0),
+				PieLayout::new(inner_radius, 8),
+			),
+			PieChartOptions::new(
+				SliceOptions::new(white, white, 1., 4),
+				GestureOptions::new(10, 300., 500., 10),
+				AnimationOptions::new(
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant,
+				),
+				AnimationPolicy::None,
+			),
+		)
+	}
+
+	fn hovered_slice(chart: &mut PieChart, x: u32, y: u32) -> Option<usize> {
+		chart.update(0., Some(x), Some(y), ClickingState::None, &[]);
+		chart.get_hovered_slice_index()
+	}
+
+	#[test]
+	fn new_drops_the_slices_past_max_slices() {
+		let mut chart = chart(&[1.; 6], 0.);
+		assert_eq!(chart.get_slices_len(), 4);
+
+		chart.update(0., None, None, ClickingState::None, &[]);
+		assert_eq!(chart.get_total(), 4.);
+	}
+
+	#[test]
+	fn pie_draws_a_triangle_and_donut_two_per_segment() {
+		// A quarter, a quarter and a half of a circle split into 8 segments
+		let mut pie = chart(&[1., 1., 2.], 0.);
+		pie.update(0., None, None, ClickingState::None, &[]);
+		assert_eq!(pie.vertex_positions_slices.len(), 8 * 3 * 2);
+		assert_eq!(pie.vertex_colors_slices.len(), 8 * 3 * 4);
+
+		let mut donut = chart(&[1., 1., 2.], 0.5);
+		donut.update(0., None, None, ClickingState::None, &[]);
+		assert_eq!(donut.vertex_positions_slices.len(), 8 * 6 * 2);
+		assert_eq!(donut.vertex_colors_slices.len(), 8 * 6 * 4);
+	}
+
+	#[test]
+	fn hit_tests_slices_by_angle_from_12_oclock() {
+		let mut pie = chart(&[1., 1., 2.], 0.);
+		assert_eq!(hovered_slice(&mut pie, 70, 30), Some(0));
+		assert_eq!(hovered_slice(&mut pie, 70, 70), Some(1));
+		assert_eq!(hovered_slice(&mut pie, 30, 70), Some(2));
+		assert_eq!(hovered_slice(&mut pie, 30, 30), Some(2));
+		// Outside of the radius
+		assert_eq!(hovered_slice(&mut pie, 95, 5), None);
+
+		let mut donut = chart(&[1., 1., 2.], 0.5);
+		assert_eq!(hovered_slice(&mut donut, 80, 20), Some(0));
+		// Inside of the hole
+		assert_eq!(hovered_slice(&mut donut, 55, 45), None);
+	}
+}
@@ -0,0 +1,882 @@
+use proc_macros::wasm_struct;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::animation::*;
+use crate::graph_types::shared::consts::VERTICES_PER_QUAD;
+use crate::graph_types::shared::gestures::{GestureOptions, GestureRecognizer};
+use crate::graph_types::shared::points::{PointInteraction, PointState};
+use crate::graph_types::shared::scale::{self, Scale};
+use crate::graph_types::shared::spatial_index::SpatialIndex;
+use crate::graph_types::shared::ticks::{nice_step, ticks_in};
+use crate::graph_types::shared::types::{
+	ClickingState, Positioning, ScaleLineObject, SelectedState, ValueAxisDomain, ValueAxisLayout,
+	XAxisLayout,
+};
+use crate::graph_types::utils::Color;
+use crate::utils::{NumUtils, PreAllocatedCollection, WasmFloat32Array, lerp};
+use crate::{log_warn, trace};
+
+const FLOATS_PER_INSTANCE: usize = 3;
+
+#[wasm_bindgen]
+pub struct WasmScatterChartData {
+	pub vertex_array_general: WasmFloat32Array,
+	pub colors_array_general: WasmFloat32Array,
+	// Center x and y in clip space followed by the radius in pixels, one entry per circle
+	pub instance_array_points: WasmFloat32Array,
+	pub colors_array_points: WasmFloat32Array,
+}
+
+#[wasm_struct]
+pub struct ScatterChartDataPoint {
+	x: f32,
+	y: f32,
+	size: f32,
+	// Index into the category colors, points without one use the point color
+	category: Option<u32>,
+}
+
+#[derive(Debug)]
+struct PointData {
+	x: f32,
+	y: f32,
+	radius: f32,
+	state: PointState,
+}
+
+#[wasm_struct]
+pub struct BubbleLayout {
+	// Radius of the smallest and largest size in the data, sizes in between scale by area
+	min_radius: f32,
+	max_radius: f32,
+}
+
+#[wasm_struct]
+pub struct ScatterChartLayout {
+	positioning: Positioning,
+
+	bubble_layout: BubbleLayout,
+	value_axis_layout: ValueAxisLayout,
+	x_axis_layout: XAxisLayout,
+}
+
+#[wasm_struct]
+pub struct ScatterPointOptions {
+	color: Color,
+	category_colors: Vec<Color>,
+	hover_color: Color,
+	selected_color: Color,
+	hover_scale: f32,
+	max_points: usize,
+}
+
+#[wasm_struct]
+pub struct ScatterChartOptions {
+	background_color: Color,
+	point_options: ScatterPointOptions,
+	value_axis_color: Color,
+	gesture_options: GestureOptions,
+	animation_options: AnimationOptions,
+	animation_policy: AnimationPolicy,
+}
+
+#[wasm_bindgen]
+pub struct ScatterChart {
+	data: Vec<ScatterChartDataPoint>,
+	points: Vec<PointData>,
+	start_timestamp: f64,
+	width: u32,
+	height: u32,
+	background_color: Color,
+
+	scale_lines: PreAllocatedCollection<ScaleLineObject>,
+	x_axis_ticks: PreAllocatedCollection<ScaleLineObject>,
+
+	point_color: Color,
+	category_colors: Vec<Color>,
+	point_interaction: PointInteraction,
+	max_points: usize,
+
+	bottom: u32,
+	top: u32,
+	left: u32,
+	right: u32,
+
+	min_radius: f32,
+	max_radius: f32,
+
+	value_axis_width: u32,
+	value_axis_smallest_scale: f32,
+	value_axis_min_pixel_distance: u32,
+	value_axis_domain: ValueAxisDomain,
+
+	value_axis_color: Color,
+
+	x_axis_tick_length: u32,
+	x_axis_tick_thickness: u32,
+	x_axis_min_tick_distance: u32,
+
+	min_x: f32,
+	max_x: f32,
+	max_size: f32,
+	scale: Scale,
+
+	is_animating: bool,
+	// The animation options with the animation policy applied
+	animations: AnimationOptions,
+	animation_options: AnimationOptions,
+	selected_point: Option<usize>,
+	hovered_point: Option<usize>,
+	points_index: SpatialIndex,
+	gestures: GestureRecognizer,
+
+	updated_data: bool,
+
+	vertex_positions_general: PreAllocatedCollection<f32>,
+	vertex_colors_general: PreAllocatedCollection<f32>,
+	instance_positions_points: PreAllocatedCollection<f32>,
+	instance_colors_points: PreAllocatedCollection<f32>,
+}
+
+fn handle_data(
+	data: &[ScatterChartDataPoint],
+	old_points: &[PointData],
+	timestamp: f64,
+) -> Vec<PointData> {
+	(0..data.len())
+		.map(|index| {
+			let selected_state = match old_points.get(index) {
+				Some(old_point) => old_point.state.selected_state,
+				None => SelectedState::None { timestamp },
+			};
+
+			PointData {
+				x: 0.,
+				y: 0.,
+				radius: 0.,
+				state: PointState::new(Color::new(0, 0, 0, 0), selected_state, 1.0, timestamp),
+			}
+		})
+		.collect()
+}
+
+// Bounds of the data on both axes and the largest size, which maps to the max radius
+fn calculate_domain(
+	data: &[ScatterChartDataPoint],
+	domain: &ValueAxisDomain,
+) -> (f32, f32, f32, Scale) {
+	let (mut min_x, mut max_x, mut min_y, mut max_y) = match data.first() {
+		Some(first) => (first.x, first.x, first.y, first.y),
+		None => (0., 0., 0., 0.),
+	};
+	let mut max_size: f32 = 0.;
+	let mut min_positive: Option<f32> = None;
+	for data_point in data {
+		min_x = data_point.x.min(min_x);
+		max_x = data_point.x.max(max_x);
+		min_y = data_point.y.min(min_y);
+		max_y = data_point.y.max(max_y);
+		max_size = data_point.size.max(max_size);
+		if data_point.y > 0. {
+			min_positive = Some(min_positive.map_or(data_point.y, |min| data_point.y.min(min)));
+		}
+	}
+
+	trace!("calculate_domain", min_x, max_x, min_y, max_y, max_size);
+
+	(
+		min_x,
+		max_x,
+		max_size,
+		Scale::new(domain, min_y, max_y, min_positive),
+	)
+}
+
+fn find_selected_point(points: &[PointData]) -> Option<usize> {
+	points.iter().position(|point| point.state.is_selected())
+}
+
+#[wasm_bindgen]
+impl ScatterChart {
+	#[wasm_bindgen(constructor)]
+	pub fn new(
+		mut data: Vec<ScatterChartDataPoint>,
+		start_timestamp: f64,
+		width: u32,
+		height: u32,
+		layout: ScatterChartLayout,
+
+		options: ScatterChartOptions,
+	) -> Self {
+		let max_scale_lines = 100;
+
+		let max_x_axis_ticks = 100;
+
+		let empty_line = ScaleLineObject {
+			x: 0,
+			y: 0,
+			width: 0,
+			height: 0,
+			intensity: 0,
+			value: 0.0,
+		};
+		let scale_lines = PreAllocatedCollection::new(empty_line, 0, max_scale_lines);
+		let x_axis_ticks = PreAllocatedCollection::new(empty_line, 0, max_x_axis_ticks);
+
+		// The instance buffers only have room for max_points
+		let max_points = options.point_options.max_points;
+		if data.len() > max_points {
+			log_warn!(
+				"new: more points than max_points, dropping the rest",
+				data.len(),
+				max_points
+			);
+			data.truncate(max_points);
+		}
+		let points = handle_data(&data, &[], start_timestamp);
+		let (min_x, max_x, max_size, scale) =
+			calculate_domain(&data, layout.value_axis_layout.domain());
+
+		let animations = options
+			.animation_options
+			.with_policy(options.animation_policy);
+
+		let max_general_quads = max_scale_lines + max_x_axis_ticks;
+		let vertex_positions_general =
+			PreAllocatedCollection::new(0., 0, max_general_quads * VERTICES_PER_QUAD * 2);
+		let vertex_colors_general =
+			PreAllocatedCollection::new(0., 0, max_general_quads * VERTICES_PER_QUAD * 4);
+
+		let instance_positions_points =
+			PreAllocatedCollection::new(0., 0, max_points * FLOATS_PER_INSTANCE);
+		let instance_colors_points = PreAllocatedCollection::new(0., 0, max_points * 4);
+
+		Self {
+			data,
+			points,
+			start_timestamp,
+			width,
+			height,
+			background_color: options.background_color,
+			scale_lines,
+			x_axis_ticks,
+			point_color: options.point_options.color,
+			category_colors: options.point_options.category_colors,
+			point_interaction: PointInteraction {
+				hover_color: options.point_options.hover_color,
+				selected_color: options.point_options.selected_color,
+				hover_scale: options.point_options.hover_scale,
+			},
+			max_points,
+			bottom: layout.positioning.bottom,
+			top: layout.positioning.top,
+			left: layout.positioning.left,
+			right: layout.positioning.right,
+			min_radius: layout.bubble_layout.min_radius,
+			max_radius: layout.bubble_layout.max_radius,
+			value_axis_width: layout.value_axis_layout.value_axis_width,
+			value_axis_smallest_scale: layout.value_axis_layout.value_axis_smallest_scale,
+			value_axis_min_pixel_distance: layout.value_axis_layout.value_axis_min_pixel_distance,
+			value_axis_domain: layout.value_axis_layout.domain().clone(),
+			value_axis_color: options.value_axis_color,
+			x_axis_tick_length: layout.x_axis_layout.x_axis_tick_length,
+			x_axis_tick_thickness: layout.x_axis_layout.x_axis_tick_thickness,
+			x_axis_min_tick_distance: layout.x_axis_layout.x_axis_min_tick_distance,
+			min_x,
+			max_x,
+			max_size,
+			scale,
+			is_animating: true,
+			animations,
+			animation_options: options.animation_options,
+			selected_point: None,
+			hovered_point: None,
+			points_index: SpatialIndex::new(),
+			gestures: GestureRecognizer::new(&options.gesture_options),
+			updated_data: false,
+
+			vertex_positions_general,
+			vertex_colors_general,
+			instance_positions_points,
+			instance_colors_points,
+		}
+	}
+
+	pub fn get_width(&self) -> u32 {
+		self.width
+	}
+
+	pub fn get_height(&self) -> u32 {
+		self.height
+	}
+
+	pub fn resize(&mut self, width: u32, height: u32) {
+		self.width = width;
+		self.height = height;
+		self.points_index.invalidate();
+	}
+
+	pub fn update_data(&mut self, data: Vec<ScatterChartDataPoint>, timestamp: f64) {
+		trace!(format!(
+			"Updating data from {:#?} to {:#?}",
+			self.data, data
+		));
+		// The instance buffers only have room for max_points
+		if data.len() > self.max_points {
+			log_warn!(
+				"update_data: more points than max_points, ignoring the data",
+				data.len(),
+				self.max_points
+			);
+			return;
+		}
+		self.points = handle_data(&data, &self.points, timestamp);
+		(self.min_x, self.max_x, self.max_size, self.scale) =
+			calculate_domain(&data, &self.value_axis_domain);
+		self.data = data;
+		self.selected_point = find_selected_point(&self.points);
+		self.points_index.invalidate();
+		self.start_timestamp = timestamp;
+		self.updated_data = true;
+	}
+
+	fn get_scale_line_vertex_positions(&mut self) {
+		let positions = &mut self.vertex_positions_general;
+		let lines = self.scale_lines.into_iter().chain(&self.x_axis_ticks);
+		for (i, scale_line) in lines.enumerate() {
+			let vert_index = i * VERTICES_PER_QUAD * 2;
+
+			let left = scale_line.x;
+			let right = left + scale_line.width;
+			let top = scale_line.y;
+			let bottom = top + scale_line.height;
+
+			let left = (left as f32 / self.width as f32) * 2. - 1.;
+			let right = (right as f32 / self.width as f32) * 2. - 1.;
+			let top = -((top as f32 / self.height as f32) * 2. - 1.);
+			let bottom = -((bottom as f32 / self.height as f32) * 2. - 1.);
+
+			positions[vert_index] = left;
+			positions[vert_index + 1] = bottom;
+			positions[vert_index + 2] = left;
+			positions[vert_index + 3] = top;
+			positions[vert_index + 4] = right;
+			positions[vert_index + 5] = top;
+
+			positions[vert_index + 6] = right;
+			positions[vert_index + 7] = bottom;
+			positions[vert_index + 8] = left;
+			positions[vert_index + 9] = bottom;
+			positions[vert_index + 10] = right;
+			positions[vert_index + 11] = top;
+		}
+	}
+
+	fn get_scale_line_vertex_colors(&mut self) {
+		let colors = &mut self.vertex_colors_general;
+		let lines = self.scale_lines.into_iter().chain(&self.x_axis_ticks);
+		for (i, scale_line) in lines.enumerate() {
+			let vert_index = i * VERTICES_PER_QUAD * 4;
+
+			let color = self
+				.background_color
+				.lerp(&self.value_axis_color, scale_line.intensity as f32 / 255.);
+
+			for offset in 0..VERTICES_PER_QUAD {
+				let offset = offset * 4;
+				colors[vert_index + offset] = color.r as f32 / 255.;
+				colors[vert_index + offset + 1] = color.g as f32 / 255.;
+				colors[vert_index + offset + 2] = color.b as f32 / 255.;
+				colors[vert_index + offset + 3] = color.a as f32 / 255.;
+			}
+		}
+	}
+
+	fn get_general_vertex_positions(&mut self) -> WasmFloat32Array {
+		trace!("get_general_vertex_positions");
+
+		self
+			.vertex_positions_general
+			.set_size((self.scale_lines.len() + self.x_axis_ticks.len()) * VERTICES_PER_QUAD * 2);
+
+		self.get_scale_line_vertex_positions();
+
+		(&self.vertex_positions_general).into()
+	}
+
+	fn get_general_vertex_colors(&mut self) -> WasmFloat32Array {
+		trace!("get_general_vertex_colors");
+
+		self
+			.vertex_colors_general
+			.set_size((self.scale_lines.len() + self.x_axis_ticks.len()) * VERTICES_PER_QUAD * 4);
+
+		self.get_scale_line_vertex_colors();
+
+		(&self.vertex_colors_general).into()
+	}
+
+	// One instance per circle, the quad each one is drawn on is shared by every instance
+	fn calculate_points_instances(&mut self) {
+		let count = self.points.len();
+
+		let positions = &mut self.instance_positions_points;
+		let colors = &mut self.instance_colors_points;
+		for (i, point) in self.points.iter().enumerate() {
+			// Convert pixel positions to (-1 to 1) scale
+			positions[i * FLOATS_PER_INSTANCE] = (point.x / self.width as f32) * 2. - 1.;
+			positions[i * FLOATS_PER_INSTANCE + 1] = -((point.y / self.height as f32) * 2. - 1.);
+			positions[i * FLOATS_PER_INSTANCE + 2] = point.radius * point.state.scale;
+
+			let color = point.state.color;
+			colors[i * 4] = color.r as f32 / 255.;
+			colors[i * 4 + 1] = color.g as f32 / 255.;
+			colors[i * 4 + 2] = color.b as f32 / 255.;
+			colors[i * 4 + 3] = color.a as f32 / 255.;
+		}
+
+		positions.set_size(count * FLOATS_PER_INSTANCE);
+		colors.set_size(count * 4);
+	}
+
+	pub fn get_points_len(&self) -> usize {
+		self.points.len()
+	}
+
+	pub fn get_point_x_at(&self, index: usize) -> f32 {
+		self.points[index].x
+	}
+
+	pub fn get_point_y_at(&self, index: usize) -> f32 {
+		self.points[index].y
+	}
+
+	pub fn get_point_radius_at(&self, index: usize) -> f32 {
+		self.points[index].radius * self.points[index].state.scale
+	}
+
+	pub fn get_scale_lines_count(&self) -> usize {
+		self.scale_lines.len()
+	}
+
+	pub fn get_scale_line_x_at(&self, index: usize) -> u32 {
+		self.scale_lines[index].x
+	}
+
+	pub fn get_scale_line_y_at(&self, index: usize) -> u32 {
+		self.scale_lines[index].y
+	}
+
+	pub fn get_scale_line_value_at(&self, index: usize) -> f32 {
+		self.scale_lines[index].value
+	}
+
+	pub fn get_x_axis_ticks_count(&self) -> usize {
+		self.x_axis_ticks.len()
+	}
+
+	pub fn get_x_axis_tick_x_at(&self, index: usize) -> u32 {
+		let tick = &self.x_axis_ticks[index];
+		tick.x + tick.width / 2
+	}
+
+	pub fn get_x_axis_tick_y_at(&self, index: usize) -> u32 {
+		self.x_axis_ticks[index].y
+	}
+
+	pub fn get_x_axis_tick_value_at(&self, index: usize) -> f32 {
+		self.x_axis_ticks[index].value
+	}
+
+	pub fn get_is_animating(&self) -> bool {
+		self.is_animating
+	}
+
+	pub fn set_animation_policy(&mut self, policy: AnimationPolicy) {
+		self.animations = self.animation_options.with_policy(policy);
+		self.is_animating = true;
+	}
+
+	// What the buffers were sized for, so the WebGL buffers can be made to match
+	pub fn get_max_general_vertices(&self) -> usize {
+		self.vertex_colors_general.capacity() / 4
+	}
+
+	pub fn get_max_point_instances(&self) -> usize {
+		self.instance_colors_points.capacity() / 4
+	}
+
+	pub fn get_selected_point_index(&self) -> Option<usize> {
+		self.selected_point
+	}
+
+	pub fn get_hovered_point_index(&self) -> Option<usize> {
+		self.hovered_point
+	}
+
+	fn toggle_point_selection_at(&mut self, index: usize, timestamp: f64) {
+		for (i, point) in self.points.iter_mut().enumerate() {
+			if i == index {
+				if point.state.is_selected() {
+					trace!("Deselect point", i);
+					point.state.selected_state = SelectedState::None { timestamp };
+					self.selected_point = None;
+				} else {
+					trace!("Select point", i);
+					point.state.selected_state = SelectedState::Selected { timestamp };
+					self.selected_point = Some(index);
+				}
+			} else if point.state.is_selected() {
+				point.state.selected_state = SelectedState::None { timestamp };
+			}
+		}
+	}
+
+	fn deselect_points(&mut self, timestamp: f64) {
+		for point in &mut self.points {
+			if point.state.is_selected() {
+				trace!("Deselect point");
+				point.state.selected_state = SelectedState::None { timestamp };
+			}
+		}
+		self.selected_point = None;
+	}
+
+	fn point_radius(&self, size: f32) -> f32 {
+		// Sizes are mapped to the area of the circle so bigger values don't look disproportionately big
+		let t = if self.max_size > 0. {
+			(size.max(0.) / self.max_size).sqrt()
+		} else {
+			1.
+		};
+		lerp(self.min_radius, self.max_radius, t)
+	}
+
+	// Positions only depend on the data and the size of the graph, so they are laid out along with the index
+	fn layout_points(&mut self) {
+		trace!("layout_points");
+		let left = self.left + self.value_axis_width;
+
+		let width = (self.width as i32 - left as i32 - self.right as i32) as f32;
+		let height = (self.height as i32 - self.top as i32 - self.bottom as i32) as f32;
+
+		let x_range = if self.max_x > self.min_x {
+			self.max_x - self.min_x
+		} else {
+			1.
+		};
+
+		for index in 0..self.points.len() {
+			let data_point = &self.data[index];
+			let x = left as f32 + (data_point.x - self.min_x) / x_range * width;
+			let y = self.height as f32 - self.bottom as f32 - self.scale.normalize(data_point.y) * height;
+			let radius = self.point_radius(data_point.size);
+
+			let point = &mut self.points[index];
+			point.x = x;
+			point.y = y;
+			point.radius = radius;
+		}
+
+		// Room for the point to grow while hovered
+		let max_scale = self.point_interaction.hover_scale.max(1.);
+		self.points_index.rebuild(
+			(self.width, self.height),
+			self.points.iter().enumerate().map(|(index, point)| {
+				let radius = point.radius * max_scale;
+				(
+					index,
+					(
+						point.x - radius,
+						point.y - radius,
+						point.x + radius,
+						point.y + radius,
+					),
+				)
+			}),
+		);
+	}
+
+	// The point whose edge is closest to (x, y), as long as it is within radius
+	fn nearest_point(&self, x: f32, y: f32, radius: f32) -> Option<usize> {
+		let mut nearest: Option<(usize, f32)> = None;
+		for index in self.points_index.query(x, y, radius) {
+			let point = &self.points[index];
+			let dx = x - point.x;
+			let dy = y - point.y;
+			let distance = ((dx * dx + dy * dy).sqrt() - point.radius * point.state.scale).max(0.);
+
+			// Ties go to the point drawn last, which is the one on top
+			if distance <= radius
+				&& nearest.is_none_or(|(nearest_index, nearest_distance)| {
+					distance < nearest_distance || (distance == nearest_distance && index > nearest_index)
+				}) {
+				nearest = Some((index, distance));
+			}
+		}
+
+		nearest.map(|(index, _)| index)
+	}
+
+	pub fn get_nearest_point_index(&self, x: u32, y: u32, radius: f32) -> Option<usize> {
+		self.nearest_point(x as f32, y as f32, radius)
+	}
+
+	fn calculate_points(
+		&mut self,
+		timestamp: f64,
+		pointer_x: Option<u32>,
+		pointer_y: Option<u32>,
+		clicking_state: ClickingState,
+	) {
+		trace!("calculate_points");
+		if self.points_index.is_dirty() {
+			self.layout_points();
+		}
+
+		let mut all_animations_done = true;
+		let mut any_point_was_clicked = false;
+
+		self.hovered_point = match (pointer_x, pointer_y) {
+			(Some(pointer_x), Some(pointer_y)) => {
+				self.nearest_point(pointer_x as f32, pointer_y as f32, 0.)
+			}
+			_ => None,
+		};
+
+		let interaction = self.point_interaction;
+		for index in 0..self.points.len() {
+			let base_color = self.data[index]
+				.category
+				.and_then(|category| self.category_colors.get(category as usize))
+				.copied()
+				.unwrap_or(self.point_color);
+
+			let point = &mut self.points[index];
+			if point.state.update_pointer(
+				self.hovered_point == Some(index),
+				clicking_state,
+				&interaction,
+				&self.animations,
+				timestamp,
+			) {
+				self.toggle_point_selection_at(index, timestamp);
+				any_point_was_clicked = true;
+			}
+
+			let point = &mut self.points[index];
+			if !point
+				.state
+				.animate(base_color, &interaction, &self.animations, timestamp)
+			{
+				all_animations_done = false;
+			}
+		}
+
+		if matches!(clicking_state, ClickingState::JustReleased) && !any_point_was_clicked {
+			self.deselect_points(timestamp);
+			all_animations_done = false;
+		}
+
+		self.is_animating = !all_animations_done;
+	}
+
+	fn calculate_scale_lines(&mut self) {
+		trace!("calculate_scale_lines");
+		scale::calculate_scale_lines(
+			&mut self.scale_lines,
+			&self.scale,
+			(self.value_axis_width, self.width),
+			(self.top as f32, self.height as f32 - self.bottom as f32),
+			self.value_axis_smallest_scale,
+			self.value_axis_min_pixel_distance as f32,
+		);
+	}
+
+	fn calculate_x_axis(&mut self) {
+		trace!("calculate_x_axis");
+		let left = self.left + self.value_axis_width;
+		let width = self.width as i32 - left as i32 - self.right as i32;
+		let range = self.max_x - self.min_x;
+
+		if width <= 0 || range <= 0. || self.x_axis_tick_length == 0 {
+			self.x_axis_ticks.set_size(0);
+			return;
+		}
+
+		let max_ticks = width as usize / self.x_axis_min_tick_distance.max(1) as usize;
+		let step = nice_step(range, max_ticks);
+		let y = (self.height as i32 - self.bottom as i32).to_u32();
+		let thickness = self.x_axis_tick_thickness;
+
+		let capacity = self.x_axis_ticks.get_data().len();
+		let mut count = 0;
+		for value in ticks_in(self.min_x, self.max_x, step).take(capacity) {
+			let x = left as f32 + (value - self.min_x) / range * width as f32;
+
+			let tick = &mut self.x_axis_ticks[count];
+			tick.x = (x - thickness as f32 / 2.).to_u32();
+			tick.y = y;
+			tick.width = thickness;
+			tick.height = self.x_axis_tick_length;
+			tick.intensity = 200;
+			tick.value = value;
+
+			count += 1;
+		}
+
+		self.x_axis_ticks.set_size(count);
+	}
+
+	pub fn update(
+		&mut self,
+		timestamp: f64,
+		pointer_x: Option<u32>,
+		pointer_y: Option<u32>,
+		clicking_state: ClickingState,
+		touches: &[f32],
+	) -> WasmScatterChartData {
+		trace!("update");
+
+		// Scatter charts don't pan or zoom, only taps and long-presses stand in for the pointer
+		let gestures = self.gestures.update(touches, timestamp);
+		let (pointer_x, pointer_y, clicking_state) =
+			if self.gestures.is_touching() || !gestures.is_empty() {
+				match self.gestures.pointer(&gestures) {
+					Some((x, y, clicking_state, _)) => (Some(x), Some(y), clicking_state),
+					None => (None, None, ClickingState::None),
+				}
+			} else {
+				(pointer_x, pointer_y, clicking_state)
+			};
+
+		self.calculate_scale_lines();
+		self.calculate_x_axis();
+		self.calculate_points(timestamp, pointer_x, pointer_y, clicking_state);
+		self.calculate_points_instances();
+
+		let vertex_array_general = self.get_general_vertex_positions();
+		let colors_array_general = self.get_general_vertex_colors();
+
+		if self.gestures.is_pending() {
+			self.is_animating = true;
+		}
+
+		if self.updated_data {
+			self.is_animating = true;
+			self.updated_data = false;
+		}
+
+		WasmScatterChartData {
+			vertex_array_general,
+			colors_array_general,
+			instance_array_points: (&self.instance_positions_points).into(),
+			colors_array_points: (&self.instance_colors_points).into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::animation::easing::{Easing, EasingType};
+	use crate::graph_types::shared::scale::ScaleType;
+
+	fn domain() -> ValueAxisDomain {
+		ValueAxisDomain::new(ScaleType::Linear, 10, None, None, false)
+	}
+
+	fn data(points: &[(f32, f32)]) -> Vec<ScatterChartDataPoint> {
+		points
+			.iter()
+			.map(|(x, y)| ScatterChartDataPoint::new(*x, *y, 1., None))
+			.collect()
+	}
+
+	// Every point has a radius of 5 and the plot covers all of the 100x100 chart
+	fn chart(points: &[(f32, f32)]) -> ScatterChart {
+		let instant = AnimationTiming::new(
+			0.,
+			0.,
+			0.,
+			Easing::new(EasingType::Linear, 0., 0., 0., 0., 0., 0.),
+		);
+		let white = Color::new(255, 255, 255, 255);
+
+		let mut chart = ScatterChart::new(
+			data(points),
+			0.,
+			100,
+			100,
+			ScatterChartLayout::new(
+				Positioning::new(0, 0, 0, 0),
+				BubbleLayout::new(5., 5.),
+				ValueAxisLayout::new(0, 1., 20, domain()),
+				XAxisLayout::new(0, 0, 50),
+			),
+			ScatterChartOptions::new(
+				Color::new(0, 0, 0, 255),
+				ScatterPointOptions::new(white, vec![], white, white, 1., 4),
+				white,
+				GestureOptions::new(10, 300., 500., 10),
+				AnimationOptions::new(
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant.clone(),
+					instant,
+				),
+				AnimationPolicy::None,
+			),
+		);
+		chart.update(0., None, None, ClickingState::None, &[]);
+		chart
+	}
+
+	#[test]
+	fn domain_fits_the_data_instead_of_starting_at_zero() {
+		let (min_x, max_x, max_size, scale) = calculate_domain(
+			&data(&[(1005., 20.), (1000., 30.), (1010., 25.)]),
+			&domain(),
+		);
+		assert_eq!((min_x, max_x, max_size), (1000., 1010., 1.));
+		assert_eq!(scale.normalize(20.), 0.);
+		assert_eq!(scale.normalize(30.), 1.);
+	}
+
+	#[test]
+	fn new_drops_the_points_past_max_points() {
+		let chart = chart(&[(0., 0.); 6]);
+		assert_eq!(chart.get_points_len(), 4);
+		assert_eq!(
+			chart.instance_positions_points.len(),
+			4 * FLOATS_PER_INSTANCE
+		);
+	}
+
+	#[test]
+	fn nearest_point_goes_by_the_edge_of_the_circle() {
+		// At (0, 100), (50, 50) and (100, 0) in pixels
+		let chart = chart(&[(0., 0.), (5., 5.), (10., 10.)]);
+		assert_eq!(chart.nearest_point(50., 50., 0.), Some(1));
+		assert_eq!(chart.nearest_point(54., 53., 0.), Some(1));
+		assert_eq!(chart.nearest_point(20., 80., 0.), None);
+		assert_eq!(chart.nearest_point(20., 80., 30.), Some(0));
+	}
+
+	#[test]
+	fn nearest_point_includes_the_edge_of_the_radius() {
+		let chart = chart(&[(0., 0.), (5., 5.), (10., 10.)]);
+		// 15 pixels from the center is 10 from the edge of the circle
+		assert_eq!(chart.nearest_point(50., 65., 10.), Some(1));
+		assert_eq!(chart.nearest_point(50., 66., 10.), None);
+	}
+
+	#[test]
+	fn nearest_point_picks_the_one_on_top_when_they_overlap() {
+		let chart = chart(&[(0., 0.), (5., 5.), (5., 5.), (10., 10.)]);
+		assert_eq!(chart.nearest_point(50., 50., 0.), Some(2));
+		assert_eq!(chart.nearest_point(50., 60., 10.), Some(2));
+	}
+}
@@ -0,0 +1,209 @@
+use proc_macros::wasm_struct;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::DefineAnimation;
+use crate::animation::*;
+use crate::graph_types::shared::consts::VERTICES_PER_QUAD;
+use crate::graph_types::utils::Color;
+use crate::utils::{PreAllocatedCollection, lerp};
+
+// Series past this get no marker
+pub const MAX_CROSSHAIR_MARKERS: usize = 32;
+// The vertical guide, the horizontal value line and a marker on each series
+pub const CROSSHAIR_QUADS: usize = 2 + MAX_CROSSHAIR_MARKERS;
+
+DefineAnimation!(CrosshairAnimData, CurrentCrosshairAnimData, opacity);
+
+#[wasm_struct]
+pub struct CrosshairOptions {
+	enabled: bool,
+	color: Color,
+	thickness: u32,
+	marker_size: u32,
+}
+
+// Where the lines cross, and a marker (x, y, series color) on each series
+#[derive(Debug, Clone)]
+pub struct CrosshairTarget {
+	pub x: u32,
+	pub y: u32,
+	pub markers: Vec<(u32, u32, Color)>,
+}
+
+// Guide lines through the data X closest to the pointer, with a marker where each series crosses it
+#[derive(Debug, Clone)]
+pub struct Crosshair {
+	enabled: bool,
+	color: Color,
+	thickness: u32,
+	marker_size: u32,
+
+	visible: bool,
+	// Where it was last drawn, so that it fades out in place once the pointer leaves
+	x: u32,
+	y: u32,
+	bounds: (u32, u32, u32, u32),
+	markers: Vec<(u32, u32, Color)>,
+	opacity: f32,
+	fade_anim: CrosshairAnimData,
+}
+
+impl Crosshair {
+	pub fn new(options: &CrosshairOptions, timestamp: f64) -> Self {
+		Self {
+			enabled: options.enabled,
+			color: options.color,
+			thickness: options.thickness,
+			marker_size: options.marker_size,
+			visible: false,
+			x: 0,
+			y: 0,
+			bounds: (0, 0, 0, 0),
+			markers: Vec::new(),
+			opacity: 0.,
+			fade_anim: CrosshairAnimData {
+				timestamp,
+				opacity: AnimationStateData { from: 0., to: 0. },
+			},
+		}
+	}
+
+	pub fn is_enabled(&self) -> bool {
+		self.enabled
+	}
+
+	// Moves the lines through (x, y) across the plot bounds (left, top, right, bottom), fading them in or
+	// out as the pointer comes and goes. Returns whether they are done moving and fading
+	pub fn update(
+		&mut self,
+		target: Option<CrosshairTarget>,
+		bounds: (u32, u32, u32, u32),
+		timing: &AnimationTiming,
+		timestamp: f64,
+	) -> bool {
+		if target.is_some() != self.visible {
+			self.visible = target.is_some();
+			self.fade_anim = CrosshairAnimData {
+				timestamp,
+				opacity: AnimationStateData {
+					from: self.opacity,
+					to: if self.visible { 1. } else { 0. },
+				},
+			};
+		}
+
+		// Moving lines have to be drawn even once the fade is done
+		let mut moved = false;
+		if let Some(mut target) = target {
+			target.markers.truncate(MAX_CROSSHAIR_MARKERS);
+			moved = (target.x, target.y) != (self.x, self.y)
+				|| target.markers.len() != self.markers.len()
+				|| target
+					.markers
+					.iter()
+					.zip(&self.markers)
+					.any(|(a, b)| (a.0, a.1) != (b.0, b.1));
+			self.x = target.x;
+			self.y = target.y;
+			self.bounds = bounds;
+			self.markers = target.markers;
+		}
+
+		let animation = Animation::new(&self.fade_anim, timestamp, timing);
+		self.opacity = animation.get_current().opacity;
+
+		animation.is_completed() && !moved
+	}
+
+	pub fn quads_len(&self) -> usize {
+		if self.opacity > 0. {
+			2 + self.markers.len()
+		} else {
+			0
+		}
+	}
+
+	pub fn write_vertex_positions(
+		&self,
+		positions: &mut PreAllocatedCollection<f32>,
+		first_quad: usize,
+		(width, height): (u32, u32),
+	) {
+		if self.quads_len() == 0 {
+			return;
+		}
+
+		let (left, top, right, bottom) = self.bounds;
+		let half_thickness = self.thickness as f32 / 2.;
+		let half_marker = self.marker_size as f32 / 2.;
+		let (x, y) = (self.x as f32, self.y as f32);
+
+		let lines = [
+			(
+				x - half_thickness,
+				top as f32,
+				x + half_thickness,
+				bottom as f32,
+			),
+			(
+				left as f32,
+				y - half_thickness,
+				right as f32,
+				y + half_thickness,
+			),
+		];
+		let markers = self.markers.iter().map(|(x, y, _)| {
+			let (x, y) = (*x as f32, *y as f32);
+			(
+				x - half_marker,
+				y - half_marker,
+				x + half_marker,
+				y + half_marker,
+			)
+		});
+		for (i, (left, top, right, bottom)) in lines.into_iter().chain(markers).enumerate() {
+			let vert_index = (first_quad + i) * VERTICES_PER_QUAD * 2;
+
+			let left = (left / width as f32) * 2. - 1.;
+			let right = (right / width as f32) * 2. - 1.;
+			let top = -((top / height as f32) * 2. - 1.);
+			let bottom = -((bottom / height as f32) * 2. - 1.);
+
+			positions[vert_index] = left;
+			positions[vert_index + 1] = bottom;
+			positions[vert_index + 2] = left;
+			positions[vert_index + 3] = top;
+			positions[vert_index + 4] = right;
+			positions[vert_index + 5] = top;
+
+			positions[vert_index + 6] = right;
+			positions[vert_index + 7] = bottom;
+			positions[vert_index + 8] = left;
+			positions[vert_index + 9] = bottom;
+			positions[vert_index + 10] = right;
+			positions[vert_index + 11] = top;
+		}
+	}
+
+	pub fn write_vertex_colors(&self, colors: &mut PreAllocatedCollection<f32>, first_quad: usize) {
+		if self.quads_len() == 0 {
+			return;
+		}
+
+		let quad_colors = [self.color, self.color]
+			.into_iter()
+			.chain(self.markers.iter().map(|(_, _, color)| *color));
+		for (i, color) in quad_colors.enumerate() {
+			let vert_index = (first_quad + i) * VERTICES_PER_QUAD * 4;
+			let alpha = color.a as f32 / 255. * self.opacity;
+
+			for offset in 0..VERTICES_PER_QUAD {
+				let offset = offset * 4;
+				colors[vert_index + offset] = color.r as f32 / 255.;
+				colors[vert_index + offset + 1] = color.g as f32 / 255.;
+				colors[vert_index + offset + 2] = color.b as f32 / 255.;
+				colors[vert_index + offset + 3] = alpha;
+			}
+		}
+	}
+}
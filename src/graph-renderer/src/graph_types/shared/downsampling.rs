@@ -0,0 +1,208 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum Downsampling {
+	None,
+	// Largest-Triangle-Three-Buckets, keeps the points that shape the line the most
+	Lttb,
+	// The lowest and highest point in every pixel column, so no spike is ever lost
+	MinMax,
+}
+
+impl Downsampling {
+	// How many points fit in the given number of pixel columns
+	pub fn max_samples(&self, columns: usize) -> usize {
+		match self {
+			Downsampling::None => usize::MAX,
+			Downsampling::Lttb => columns,
+			// Two points per column along with the first and last point
+			Downsampling::MinMax => columns * 2 + 2,
+		}
+	}
+}
+
+// Indices of the points to draw, in order. The points are expected to be sorted by x
+pub fn downsample(
+	downsampling: Downsampling,
+	len: usize,
+	point_at: impl Fn(usize) -> (f32, f32),
+	max_samples: usize,
+) -> Vec<usize> {
+	if len <= max_samples {
+		return (0..len).collect();
+	}
+
+	match downsampling {
+		Downsampling::None => (0..len).collect(),
+		Downsampling::Lttb => lttb(len, point_at, max_samples),
+		Downsampling::MinMax => min_max(len, point_at, max_samples),
+	}
+}
+
+fn lttb(len: usize, point_at: impl Fn(usize) -> (f32, f32), max_samples: usize) -> Vec<usize> {
+	if max_samples < 3 {
+		return [0, len - 1].into_iter().take(max_samples).collect();
+	}
+
+	let mut samples = Vec::with_capacity(max_samples);
+	samples.push(0);
+
+	// The first and last point are always kept, everything in between is split into equally sized buckets
+	let bucket_size = (len - 2) as f64 / (max_samples - 2) as f64;
+	let bucket_start = |bucket: usize| (bucket as f64 * bucket_size) as usize + 1;
+
+	let mut previous = 0;
+	for bucket in 0..max_samples - 2 {
+		let start = bucket_start(bucket);
+		let end = bucket_start(bucket + 1).min(len - 1);
+
+		// Average of the next bucket, or the last point when there are no buckets left
+		let next_start = end;
+		let next_end = bucket_start(bucket + 2).min(len - 1).max(next_start + 1);
+		let (mut average_x, mut average_y) = (0., 0.);
+		for index in next_start..next_end {
+			let (x, y) = point_at(index);
+			average_x += x;
+			average_y += y;
+		}
+		let next_count = (next_end - next_start) as f32;
+		average_x /= next_count;
+		average_y /= next_count;
+
+		// Keep the point making the largest triangle with the previously kept point and the next average
+		let (previous_x, previous_y) = point_at(previous);
+		let mut largest_area = -1.;
+		let mut largest = start;
+		for index in start..end {
+			let (x, y) = point_at(index);
+			let area = ((previous_x - average_x) * (y - previous_y)
+				- (previous_x - x) * (average_y - previous_y))
+				.abs();
+			if area > largest_area {
+				largest_area = area;
+				largest = index;
+			}
+		}
+
+		samples.push(largest);
+		previous = largest;
+	}
+
+	samples.push(len - 1);
+	samples
+}
+
+fn min_max(len: usize, point_at: impl Fn(usize) -> (f32, f32), max_samples: usize) -> Vec<usize> {
+	// Without room for a column between the first and last point only those are kept
+	if max_samples < 4 {
+		return [0, len - 1].into_iter().take(max_samples).collect();
+	}
+
+	let columns = (max_samples - 2) / 2;
+	let min_x = point_at(0).0;
+	let max_x = point_at(len - 1).0;
+	let range = if max_x > min_x { max_x - min_x } else { 1. };
+
+	// Index of the lowest and highest point in each column
+	let mut extremes: Vec<Option<(usize, usize)>> = vec![None; columns];
+	for index in 1..len - 1 {
+		let (x, y) = point_at(index);
+		let column = ((((x - min_x) / range) * columns as f32).max(0.) as usize).min(columns - 1);
+
+		extremes[column] = Some(match extremes[column] {
+			None => (index, index),
+			Some((min, max)) => (
+				if y < point_at(min).1 { index } else { min },
+				if y > point_at(max).1 { index } else { max },
+			),
+		});
+	}
+
+	let mut samples = Vec::with_capacity(columns * 2 + 2);
+	samples.push(0);
+	for (min, max) in extremes.into_iter().flatten() {
+		samples.push(min.min(max));
+		if min != max {
+			samples.push(min.max(max));
+		}
+	}
+	samples.push(len - 1);
+	samples
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// A flat line with a spike up at 30 and one down at 70
+	fn spiky(index: usize) -> (f32, f32) {
+		let y = match index {
+			30 => 100.,
+			70 => -100.,
+			_ => 0.,
+		};
+		(index as f32, y)
+	}
+
+	fn assert_valid(samples: &[usize], len: usize, max_samples: usize) {
+		assert!(samples.len() <= max_samples);
+		assert_eq!(samples.first(), Some(&0));
+		assert_eq!(samples.last(), Some(&(len - 1)));
+		assert!(samples.windows(2).all(|pair| pair[0] < pair[1]));
+	}
+
+	#[test]
+	fn keeps_everything_when_it_fits() {
+		for downsampling in [Downsampling::None, Downsampling::Lttb, Downsampling::MinMax] {
+			assert_eq!(downsample(downsampling, 5, spiky, 5), vec![0, 1, 2, 3, 4]);
+		}
+		assert_eq!(downsample(Downsampling::None, 100, spiky, 10).len(), 100);
+	}
+
+	#[test]
+	fn lttb_keeps_the_endpoints_and_the_spikes() {
+		let samples = downsample(Downsampling::Lttb, 101, spiky, 10);
+		assert_eq!(samples.len(), 10);
+		assert_valid(&samples, 101, 10);
+		assert!(samples.contains(&30));
+		assert!(samples.contains(&70));
+	}
+
+	#[test]
+	fn min_max_keeps_the_endpoints_and_the_spikes() {
+		let samples = downsample(Downsampling::MinMax, 101, spiky, 10);
+		assert_valid(&samples, 101, 10);
+		assert!(samples.contains(&30));
+		assert!(samples.contains(&70));
+	}
+
+	#[test]
+	fn samples_are_indices_into_the_original_points() {
+		let points: Vec<(f32, f32)> = (0..50).map(|i| (i as f32 * 2., (i % 7) as f32)).collect();
+		for downsampling in [Downsampling::Lttb, Downsampling::MinMax] {
+			let samples = downsample(downsampling, points.len(), |i| points[i], 12);
+			assert_valid(&samples, points.len(), 12);
+		}
+
+		// The column extremes are the original points with the lowest and highest y
+		let samples = downsample(Downsampling::MinMax, points.len(), |i| points[i], 4);
+		assert_eq!(samples.len(), 4);
+		assert_eq!(points[samples[1]].1.min(points[samples[2]].1), 0.);
+		assert_eq!(points[samples[1]].1.max(points[samples[2]].1), 6.);
+	}
+
+	#[test]
+	fn never_returns_more_than_max_samples() {
+		for downsampling in [Downsampling::Lttb, Downsampling::MinMax] {
+			for max_samples in 0..6 {
+				let samples = downsample(downsampling, 101, spiky, max_samples);
+				match max_samples {
+					0 => assert!(samples.is_empty()),
+					1 => assert_eq!(samples, vec![0]),
+					_ => assert_valid(&samples, 101, max_samples),
+				}
+			}
+		}
+	}
+}
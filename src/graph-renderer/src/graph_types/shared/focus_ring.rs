@@ -0,0 +1,158 @@
+use proc_macros::wasm_struct;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::DefineAnimation;
+use crate::animation::*;
+use crate::graph_types::shared::consts::VERTICES_PER_QUAD;
+use crate::graph_types::utils::Color;
+use crate::utils::{PreAllocatedCollection, lerp};
+
+// Top, bottom, left and right edges
+pub const FOCUS_RING_QUADS: usize = 4;
+
+DefineAnimation!(FocusRingAnimData, CurrentFocusRingAnimData, opacity);
+
+#[wasm_struct]
+pub struct FocusRingOptions {
+	color: Color,
+	thickness: u32,
+	// Space between the focused element and the ring
+	gap: u32,
+}
+
+// Outline around the element focused from the keyboard, kept apart from hover so the two can differ
+#[derive(Debug, Clone)]
+pub struct FocusRing {
+	color: Color,
+	thickness: u32,
+	gap: u32,
+
+	visible: bool,
+	// Where the ring was last drawn, so that it fades out in place once focus is cleared
+	rect: (u32, u32, u32, u32),
+	opacity: f32,
+	fade_anim: FocusRingAnimData,
+}
+
+impl FocusRing {
+	pub fn new(options: &FocusRingOptions, timestamp: f64) -> Self {
+		Self {
+			color: options.color,
+			thickness: options.thickness,
+			gap: options.gap,
+			visible: false,
+			rect: (0, 0, 0, 0),
+			opacity: 0.,
+			fade_anim: FocusRingAnimData {
+				timestamp,
+				opacity: AnimationStateData { from: 0., to: 0. },
+			},
+		}
+	}
+
+	// Moves the ring around rect (x, y, width, height), fading it in or out as focus comes and goes.
+	// Returns whether the fade is done
+	pub fn update(
+		&mut self,
+		rect: Option<(u32, u32, u32, u32)>,
+		timing: &AnimationTiming,
+		timestamp: f64,
+	) -> bool {
+		if rect.is_some() != self.visible {
+			self.visible = rect.is_some();
+			self.fade_anim = FocusRingAnimData {
+				timestamp,
+				opacity: AnimationStateData {
+					from: self.opacity,
+					to: if self.visible { 1. } else { 0. },
+				},
+			};
+		}
+
+		if let Some(rect) = rect {
+			self.rect = rect;
+		}
+
+		let animation = Animation::new(&self.fade_anim, timestamp, timing);
+		self.opacity = animation.get_current().opacity;
+
+		animation.is_completed()
+	}
+
+	pub fn quads_len(&self) -> usize {
+		if self.opacity > 0. {
+			FOCUS_RING_QUADS
+		} else {
+			0
+		}
+	}
+
+	pub fn write_vertex_positions(
+		&self,
+		positions: &mut PreAllocatedCollection<f32>,
+		first_quad: usize,
+		(width, height): (u32, u32),
+	) {
+		if self.quads_len() == 0 {
+			return;
+		}
+
+		let (x, y, rect_width, rect_height) = self.rect;
+		let offset = (self.gap + self.thickness) as f32;
+		let thickness = self.thickness as f32;
+		let left = x as f32 - offset;
+		let top = y as f32 - offset;
+		let right = (x + rect_width) as f32 + offset;
+		let bottom = (y + rect_height) as f32 + offset;
+
+		let edges = [
+			(left, top, right, top + thickness),
+			(left, bottom - thickness, right, bottom),
+			(left, top + thickness, left + thickness, bottom - thickness),
+			(
+				right - thickness,
+				top + thickness,
+				right,
+				bottom - thickness,
+			),
+		];
+		for (i, (left, top, right, bottom)) in edges.into_iter().enumerate() {
+			let vert_index = (first_quad + i) * VERTICES_PER_QUAD * 2;
+
+			let left = (left / width as f32) * 2. - 1.;
+			let right = (right / width as f32) * 2. - 1.;
+			let top = -((top / height as f32) * 2. - 1.);
+			let bottom = -((bottom / height as f32) * 2. - 1.);
+
+			positions[vert_index] = left;
+			positions[vert_index + 1] = bottom;
+			positions[vert_index + 2] = left;
+			positions[vert_index + 3] = top;
+			positions[vert_index + 4] = right;
+			positions[vert_index + 5] = top;
+
+			positions[vert_index + 6] = right;
+			positions[vert_index + 7] = bottom;
+			positions[vert_index + 8] = left;
+			positions[vert_index + 9] = bottom;
+			positions[vert_index + 10] = right;
+			positions[vert_index + 11] = top;
+		}
+	}
+
+	pub fn write_vertex_colors(&self, colors: &mut PreAllocatedCollection<f32>, first_quad: usize) {
+		let alpha = self.color.a as f32 / 255. * self.opacity;
+
+		for i in 0..self.quads_len() {
+			let vert_index = (first_quad + i) * VERTICES_PER_QUAD * 4;
+
+			for offset in 0..VERTICES_PER_QUAD {
+				let offset = offset * 4;
+				colors[vert_index + offset] = self.color.r as f32 / 255.;
+				colors[vert_index + offset + 1] = self.color.g as f32 / 255.;
+				colors[vert_index + offset + 2] = self.color.b as f32 / 255.;
+				colors[vert_index + offset + 3] = alpha;
+			}
+		}
+	}
+}
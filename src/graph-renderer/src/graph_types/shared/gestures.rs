@@ -0,0 +1,418 @@
+use proc_macros::wasm_struct;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::graph_types::shared::types::ClickingState;
+
+#[wasm_struct]
+pub struct GestureOptions {
+	// How far a finger can move, in pixels, and still tap or long-press. Past it a single finger pans
+	tap_max_distance: u32,
+	tap_max_duration: f64,
+	long_press_duration: f64,
+	// How far two fingers have to move or spread, in pixels, before they pan or pinch
+	two_finger_threshold: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+	Tap { x: u32, y: u32 },
+	LongPress { x: u32, y: u32 },
+	// Charts only pan and zoom along X
+	Pan { dx: f32 },
+	// Factor is how much further apart the fingers are than last update, above 1 when spreading
+	Pinch { center_x: u32, factor: f32 },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Touch {
+	id: i32,
+	start: (f32, f32),
+	position: (f32, f32),
+	start_timestamp: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TwoFingerState {
+	ids: (i32, i32),
+	start_center: (f32, f32),
+	start_distance: f32,
+	center: (f32, f32),
+	distance: f32,
+	// Past the threshold, from then on every movement pans or pinches
+	active: bool,
+}
+
+// Turns the touches down on the chart into taps, long-presses, pans and pinches
+#[derive(Debug, Clone)]
+pub struct GestureRecognizer {
+	tap_max_distance: u32,
+	tap_max_duration: f64,
+	long_press_duration: f64,
+	two_finger_threshold: u32,
+
+	touches: Vec<Touch>,
+	// Any of these and lifting the fingers is no longer a tap
+	moved: bool,
+	multi_touch: bool,
+	long_pressed: bool,
+	two_finger: Option<TwoFingerState>,
+}
+
+impl GestureRecognizer {
+	pub fn new(options: &GestureOptions) -> Self {
+		Self {
+			tap_max_distance: options.tap_max_distance,
+			tap_max_duration: options.tap_max_duration,
+			long_press_duration: options.long_press_duration,
+			two_finger_threshold: options.two_finger_threshold,
+			touches: Vec::new(),
+			moved: false,
+			multi_touch: false,
+			long_pressed: false,
+			two_finger: None,
+		}
+	}
+
+	pub fn is_touching(&self) -> bool {
+		!self.touches.is_empty()
+	}
+
+	// A finger is held still and could still turn into a long-press, so updates have to keep coming
+	pub fn is_pending(&self) -> bool {
+		self.touches.len() == 1 && !self.moved && !self.multi_touch && !self.long_pressed
+	}
+
+	// Takes the touches currently down as [id, x, y] triples, in the order they went down
+	pub fn update(&mut self, touches: &[f32], timestamp: f64) -> Vec<Gesture> {
+		let mut gestures = Vec::new();
+
+		let previous = std::mem::take(&mut self.touches);
+		for touch in touches.chunks_exact(3) {
+			let id = touch[0] as i32;
+			let position = (touch[1], touch[2]);
+			let touch = match previous.iter().find(|previous| previous.id == id) {
+				Some(previous) => Touch {
+					position,
+					..*previous
+				},
+				None => Touch {
+					id,
+					start: position,
+					position,
+					start_timestamp: timestamp,
+				},
+			};
+			self.touches.push(touch);
+		}
+
+		if self.touches.is_empty() {
+			if let [touch] = previous.as_slice()
+				&& !self.moved
+				&& !self.multi_touch
+				&& !self.long_pressed
+				&& timestamp - touch.start_timestamp <= self.tap_max_duration
+			{
+				let (x, y) = touch.position;
+				gestures.push(Gesture::Tap {
+					x: x.max(0.) as u32,
+					y: y.max(0.) as u32,
+				});
+			}
+
+			self.moved = false;
+			self.multi_touch = false;
+			self.long_pressed = false;
+			self.two_finger = None;
+			return gestures;
+		}
+
+		if let [touch] = self.touches.as_slice()
+			&& !self.multi_touch
+		{
+			// Once it starts panning everything the finger moved counts, so the chart stays under it
+			let from = match previous.iter().find(|previous| previous.id == touch.id) {
+				Some(previous) if self.moved => previous.position,
+				_ => touch.start,
+			};
+			if distance(touch.start, touch.position) > self.tap_max_distance as f32 {
+				self.moved = true;
+			}
+			if self.moved && touch.position.0 != from.0 {
+				gestures.push(Gesture::Pan {
+					dx: touch.position.0 - from.0,
+				});
+			}
+
+			if !self.moved
+				&& !self.long_pressed
+				&& timestamp - touch.start_timestamp >= self.long_press_duration
+			{
+				self.long_pressed = true;
+				let (x, y) = touch.position;
+				gestures.push(Gesture::LongPress {
+					x: x.max(0.) as u32,
+					y: y.max(0.) as u32,
+				});
+			}
+		}
+
+		if let [first, second, ..] = self.touches.as_slice() {
+			self.multi_touch = true;
+			let ids = (first.id, second.id);
+			let center = (
+				(first.position.0 + second.position.0) / 2.,
+				(first.position.1 + second.position.1) / 2.,
+			);
+			let spread = distance(first.position, second.position);
+
+			match &mut self.two_finger {
+				Some(state) if state.ids == ids => {
+					if !state.active {
+						let threshold = self.two_finger_threshold as f32;
+						state.active = distance(state.start_center, center) > threshold
+							|| (spread - state.start_distance).abs() > threshold;
+					}
+
+					if state.active {
+						if spread != state.distance && state.distance > 0. && spread > 0. {
+							gestures.push(Gesture::Pinch {
+								center_x: center.0.max(0.) as u32,
+								factor: spread / state.distance,
+							});
+						}
+						if center.0 != state.center.0 {
+							gestures.push(Gesture::Pan {
+								dx: center.0 - state.center.0,
+							});
+						}
+
+						state.center = center;
+						state.distance = spread;
+					}
+				}
+				// A new pair of fingers starts over
+				_ => {
+					self.two_finger = Some(TwoFingerState {
+						ids,
+						start_center: center,
+						start_distance: spread,
+						center,
+						distance: spread,
+						active: false,
+					});
+				}
+			}
+		} else {
+			self.two_finger = None;
+		}
+
+		gestures
+	}
+
+	// The pointer the touches stand in for: taps click, long-presses add to the selection and a single
+	// finger hovers. None while several fingers are down
+	pub fn pointer(&self, gestures: &[Gesture]) -> Option<(u32, u32, ClickingState, bool)> {
+		for gesture in gestures {
+			match *gesture {
+				Gesture::Tap { x, y } => return Some((x, y, ClickingState::JustReleased, false)),
+				Gesture::LongPress { x, y } => {
+					return Some((x, y, ClickingState::JustReleased, true));
+				}
+				_ => {}
+			}
+		}
+
+		match self.touches.as_slice() {
+			[touch] if !self.multi_touch => {
+				let (x, y) = touch.position;
+				Some((
+					x.max(0.) as u32,
+					y.max(0.) as u32,
+					ClickingState::None,
+					false,
+				))
+			}
+			_ => None,
+		}
+	}
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+	let dx = b.0 - a.0;
+	let dy = b.1 - a.1;
+	(dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Taps within 10 pixels and 300ms, long-presses after 500ms and two fingers past 10 pixels
+	fn recognizer() -> GestureRecognizer {
+		GestureRecognizer::new(&GestureOptions::new(10, 300., 500., 10))
+	}
+
+	// Feeds (timestamp, [id, x, y]...) updates in order and collects the gestures of each
+	fn feed(recognizer: &mut GestureRecognizer, updates: &[(f64, &[f32])]) -> Vec<Vec<Gesture>> {
+		updates
+			.iter()
+			.map(|(timestamp, touches)| recognizer.update(touches, *timestamp))
+			.collect()
+	}
+
+	#[test]
+	fn lifting_a_finger_quickly_taps() {
+		let gestures = feed(
+			&mut recognizer(),
+			&[(0., &[1., 50., 50.]), (50., &[1., 55., 52.]), (100., &[])],
+		);
+		assert_eq!(
+			gestures,
+			[vec![], vec![], vec![Gesture::Tap { x: 55, y: 52 }]]
+		);
+	}
+
+	#[test]
+	fn holding_too_long_to_tap_long_presses_once() {
+		let gestures = feed(
+			&mut recognizer(),
+			&[
+				(0., &[1., 50., 50.]),
+				(400., &[1., 50., 50.]),
+				(500., &[1., 52., 50.]),
+				(600., &[1., 52., 50.]),
+				(700., &[]),
+			],
+		);
+		assert_eq!(
+			gestures,
+			[
+				vec![],
+				vec![],
+				vec![Gesture::LongPress { x: 52, y: 50 }],
+				vec![],
+				vec![],
+			]
+		);
+	}
+
+	#[test]
+	fn lifting_a_finger_slowly_does_nothing() {
+		let mut recognizer = recognizer();
+		let gestures = feed(&mut recognizer, &[(0., &[1., 50., 50.]), (301., &[])]);
+		assert_eq!(gestures, [vec![], vec![]]);
+	}
+
+	#[test]
+	fn a_tap_that_moves_past_the_threshold_pans() {
+		let mut recognizer = recognizer();
+		let gestures = feed(
+			&mut recognizer,
+			&[
+				(0., &[1., 50., 50.]),
+				(16., &[1., 58., 50.]),
+				(32., &[1., 65., 50.]),
+				(48., &[1., 70., 60.]),
+				(64., &[1., 70., 70.]),
+				(80., &[]),
+			],
+		);
+		assert_eq!(
+			gestures,
+			[
+				vec![],
+				vec![],
+				// Everything since the finger went down, then what it moved since the last update
+				vec![Gesture::Pan { dx: 15. }],
+				vec![Gesture::Pan { dx: 5. }],
+				vec![],
+				// No tap once it has moved
+				vec![],
+			]
+		);
+
+		// Still held past the long-press duration, but it has already moved
+		let gestures = feed(
+			&mut recognizer,
+			&[(0., &[1., 50., 50.]), (20., &[1., 50., 70.])],
+		);
+		assert_eq!(gestures, [vec![], vec![]]);
+		assert!(!recognizer.is_pending());
+		assert!(recognizer.update(&[1., 50., 70.], 600.).is_empty());
+	}
+
+	#[test]
+	fn two_fingers_pan_once_they_move_past_the_threshold() {
+		let gestures = feed(
+			&mut recognizer(),
+			&[
+				(0., &[1., 40., 50., 2., 60., 50.]),
+				(16., &[1., 45., 50., 2., 65., 50.]),
+				(32., &[1., 60., 50., 2., 80., 50.]),
+				(48., &[1., 62., 50., 2., 82., 50.]),
+				(64., &[]),
+			],
+		);
+		assert_eq!(
+			gestures,
+			[
+				vec![],
+				vec![],
+				vec![Gesture::Pan { dx: 20. }],
+				vec![Gesture::Pan { dx: 2. }],
+				vec![],
+			]
+		);
+	}
+
+	#[test]
+	fn two_fingers_spreading_pinch() {
+		let gestures = feed(
+			&mut recognizer(),
+			&[
+				(0., &[1., 40., 50., 2., 60., 50.]),
+				(16., &[1., 30., 50., 2., 70., 50.]),
+				(32., &[1., 20., 50., 2., 80., 50.]),
+				(48., &[1., 30., 50.]),
+				(64., &[]),
+			],
+		);
+		assert_eq!(
+			gestures,
+			[
+				vec![],
+				vec![Gesture::Pinch {
+					center_x: 50,
+					factor: 2.
+				}],
+				vec![Gesture::Pinch {
+					center_x: 50,
+					factor: 1.5
+				}],
+				// Lifting one finger neither pans nor taps
+				vec![],
+				vec![],
+			]
+		);
+	}
+
+	#[test]
+	fn one_finger_stands_in_for_the_pointer() {
+		let mut recognizer = recognizer();
+		let gestures = recognizer.update(&[1., 50., 60.], 0.);
+		assert!(matches!(
+			recognizer.pointer(&gestures),
+			Some((50, 60, ClickingState::None, false))
+		));
+		assert!(recognizer.is_pending());
+
+		let gestures = recognizer.update(&[], 100.);
+		assert!(matches!(
+			recognizer.pointer(&gestures),
+			Some((50, 60, ClickingState::JustReleased, false))
+		));
+
+		let gestures = recognizer.update(&[1., 50., 60., 2., 70., 60.], 200.);
+		assert!(recognizer.pointer(&gestures).is_none());
+	}
+}
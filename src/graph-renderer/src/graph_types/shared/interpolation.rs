@@ -0,0 +1,262 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::utils::PreAllocatedCollection;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum Interpolation {
+	Linear,
+	MonotoneCubic,
+	CatmullRom,
+	StepBefore,
+	StepAfter,
+	StepMiddle,
+}
+
+impl Interpolation {
+	pub fn max_points_per_segment(&self, curve_segments: usize) -> usize {
+		match self {
+			Interpolation::Linear => 1,
+			Interpolation::MonotoneCubic | Interpolation::CatmullRom => curve_segments.max(1),
+			Interpolation::StepBefore | Interpolation::StepAfter => 2,
+			Interpolation::StepMiddle => 3,
+		}
+	}
+}
+
+struct CurveWriter<'a> {
+	curve: &'a mut PreAllocatedCollection<(f32, f32)>,
+	len: usize,
+}
+
+impl CurveWriter<'_> {
+	fn push(&mut self, point: (f32, f32)) {
+		self.curve[self.len] = point;
+		self.len += 1;
+	}
+}
+
+// Resamples the points into a polyline that follows the chosen interpolation
+pub fn interpolate(
+	points: &[(f32, f32)],
+	interpolation: Interpolation,
+	curve_segments: usize,
+	curve: &mut PreAllocatedCollection<(f32, f32)>,
+) {
+	let mut writer = CurveWriter { curve, len: 0 };
+
+	if let Some(first) = points.first() {
+		writer.push(*first);
+	}
+
+	if points.len() >= 2 {
+		match interpolation {
+			Interpolation::Linear => {
+				for point in &points[1..] {
+					writer.push(*point);
+				}
+			}
+			Interpolation::MonotoneCubic => monotone_cubic(points, curve_segments.max(1), &mut writer),
+			Interpolation::CatmullRom => catmull_rom(points, curve_segments.max(1), &mut writer),
+			Interpolation::StepBefore => {
+				for window in points.windows(2) {
+					let (start, end) = (window[0], window[1]);
+					writer.push((start.0, end.1));
+					writer.push(end);
+				}
+			}
+			Interpolation::StepAfter => {
+				for window in points.windows(2) {
+					let (start, end) = (window[0], window[1]);
+					writer.push((end.0, start.1));
+					writer.push(end);
+				}
+			}
+			Interpolation::StepMiddle => {
+				for window in points.windows(2) {
+					let (start, end) = (window[0], window[1]);
+					let middle = (start.0 + end.0) / 2.;
+					writer.push((middle, start.1));
+					writer.push((middle, end.1));
+					writer.push(end);
+				}
+			}
+		}
+	}
+
+	let len = writer.len;
+	curve.set_size(len);
+}
+
+fn slope(start: (f32, f32), end: (f32, f32)) -> f32 {
+	let dx = end.0 - start.0;
+	if dx.abs() < f32::EPSILON {
+		return 0.;
+	}
+
+	(end.1 - start.1) / dx
+}
+
+// Steffen's method, which keeps every segment monotone so the curve never overshoots the samples
+fn monotone_tangent(points: &[(f32, f32)], index: usize) -> f32 {
+	let last = points.len() - 1;
+
+	if index == 0 {
+		return slope(points[0], points[1]);
+	}
+	if index == last {
+		return slope(points[last - 1], points[last]);
+	}
+
+	let h0 = points[index].0 - points[index - 1].0;
+	let h1 = points[index + 1].0 - points[index].0;
+	let s0 = slope(points[index - 1], points[index]);
+	let s1 = slope(points[index], points[index + 1]);
+
+	if h0 + h1 <= 0. {
+		return 0.;
+	}
+
+	let p = (s0 * h1 + s1 * h0) / (h0 + h1);
+
+	(s0.signum() + s1.signum()) * s0.abs().min(s1.abs()).min(0.5 * p.abs())
+}
+
+fn monotone_cubic(points: &[(f32, f32)], curve_segments: usize, writer: &mut CurveWriter) {
+	let mut start_tangent = monotone_tangent(points, 0);
+
+	for index in 0..(points.len() - 1) {
+		let (x0, y0) = points[index];
+		let (x1, y1) = points[index + 1];
+		let h = x1 - x0;
+
+		let end_tangent = monotone_tangent(points, index + 1);
+
+		for step in 1..=curve_segments {
+			let t = step as f32 / curve_segments as f32;
+			let t2 = t * t;
+			let t3 = t2 * t;
+
+			// Cubic hermite basis
+			let h00 = 2. * t3 - 3. * t2 + 1.;
+			let h10 = t3 - 2. * t2 + t;
+			let h01 = -2. * t3 + 3. * t2;
+			let h11 = t3 - t2;
+
+			writer.push((
+				x0 + h * t,
+				h00 * y0 + h10 * h * start_tangent + h01 * y1 + h11 * h * end_tangent,
+			));
+		}
+
+		start_tangent = end_tangent;
+	}
+}
+
+fn catmull_rom(points: &[(f32, f32)], curve_segments: usize, writer: &mut CurveWriter) {
+	let last = points.len() - 1;
+
+	for index in 0..last {
+		let p0 = points[index.saturating_sub(1)];
+		let p1 = points[index];
+		let p2 = points[index + 1];
+		let p3 = points[(index + 2).min(last)];
+
+		for step in 1..=curve_segments {
+			let t = step as f32 / curve_segments as f32;
+			let t2 = t * t;
+			let t3 = t2 * t;
+
+			let catmull_rom = |a: f32, b: f32, c: f32, d: f32| {
+				0.5
+					* (2. * b
+						+ (-a + c) * t
+						+ (2. * a - 5. * b + 4. * c - d) * t2
+						+ (-a + 3. * b - 3. * c + d) * t3)
+			};
+
+			writer.push((
+				catmull_rom(p0.0, p1.0, p2.0, p3.0),
+				catmull_rom(p0.1, p1.1, p2.1, p3.1),
+			));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn curve_of(points: &[(f32, f32)], interpolation: Interpolation) -> Vec<(f32, f32)> {
+		let segments = 16;
+		let capacity = points.len() * interpolation.max_points_per_segment(segments);
+		let mut curve = PreAllocatedCollection::new((0., 0.), 0, capacity);
+		interpolate(points, interpolation, segments, &mut curve);
+		curve.as_slice().to_vec()
+	}
+
+	#[test]
+	fn monotone_cubic_never_dips_below_zero() {
+		let points = [
+			(0., 0.),
+			(1., 0.),
+			(2., 100.),
+			(3., 0.),
+			(4., 0.),
+			(5., 40.),
+		];
+		let curve = curve_of(&points, Interpolation::MonotoneCubic);
+
+		for (x, y) in &curve {
+			assert!(*y >= 0., "dipped below zero at ({x}, {y})");
+			assert!(*y <= 100., "overshot at ({x}, {y})");
+		}
+	}
+
+	#[test]
+	fn catmull_rom_overshoots_where_monotone_cubic_does_not() {
+		let points = [(0., 0.), (1., 0.), (2., 100.), (3., 0.), (4., 0.)];
+		let curve = curve_of(&points, Interpolation::CatmullRom);
+
+		assert!(curve.iter().any(|(_, y)| *y < 0.));
+	}
+
+	#[test]
+	fn curves_go_through_every_sample() {
+		let points = [(0., 10.), (2., 30.), (3., 5.), (7., 5.)];
+		for interpolation in [
+			Interpolation::Linear,
+			Interpolation::MonotoneCubic,
+			Interpolation::CatmullRom,
+			Interpolation::StepBefore,
+			Interpolation::StepAfter,
+			Interpolation::StepMiddle,
+		] {
+			let curve = curve_of(&points, interpolation);
+			for (x, y) in points {
+				assert!(
+					curve
+						.iter()
+						.any(|point| (point.0 - x).abs() < 1e-4 && (point.1 - y).abs() < 1e-4),
+					"{interpolation:?} misses ({x}, {y})"
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn step_after_holds_the_value_until_the_next_sample() {
+		let curve = curve_of(&[(0., 1.), (2., 3.)], Interpolation::StepAfter);
+
+		assert_eq!(curve, vec![(0., 1.), (2., 1.), (2., 3.)]);
+	}
+
+	#[test]
+	fn fewer_than_two_points() {
+		assert!(curve_of(&[], Interpolation::MonotoneCubic).is_empty());
+		assert_eq!(
+			curve_of(&[(1., 2.)], Interpolation::MonotoneCubic),
+			vec![(1., 2.)]
+		);
+	}
+}
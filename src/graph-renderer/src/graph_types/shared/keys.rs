@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+// The old item each new item continues from. Keyed items are matched by key wherever they moved to,
+// the rest by position as long as the old item there has no key either
+pub fn match_keys<'a>(
+	old_keys: impl Iterator<Item = Option<&'a str>>,
+	keys: impl Iterator<Item = Option<&'a str>>,
+) -> Vec<Option<usize>> {
+	let old_keys: Vec<Option<&str>> = old_keys.collect();
+	let old_indices: HashMap<&str, usize> = old_keys
+		.iter()
+		.enumerate()
+		.filter_map(|(index, key)| Some(((*key)?, index)))
+		.collect();
+
+	keys
+		.enumerate()
+		.map(|(index, key)| match key {
+			Some(key) => old_indices.get(key).copied(),
+			None => matches!(old_keys.get(index), Some(None)).then_some(index),
+		})
+		.collect()
+}
+
+// Whether anything continues from each of the old items
+pub fn kept(old_len: usize, matches: &[Option<usize>]) -> Vec<bool> {
+	let mut kept = vec![false; old_len];
+	for old_index in matches.iter().flatten() {
+		kept[*old_index] = true;
+	}
+	kept
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn keyed<'a>(keys: &'a [&'a str]) -> impl Iterator<Item = Option<&'a str>> {
+		keys.iter().map(|key| Some(*key))
+	}
+
+	#[test]
+	fn insert_at_front_keeps_the_old_items() {
+		let matches = match_keys(keyed(&["a", "b", "c"]), keyed(&["new", "a", "b", "c"]));
+		assert_eq!(matches, vec![None, Some(0), Some(1), Some(2)]);
+		assert_eq!(kept(3, &matches), vec![true, true, true]);
+	}
+
+	#[test]
+	fn reordered_items_follow_their_key() {
+		let matches = match_keys(keyed(&["a", "b", "c"]), keyed(&["c", "a", "b"]));
+		assert_eq!(matches, vec![Some(2), Some(0), Some(1)]);
+	}
+
+	#[test]
+	fn removed_items_are_not_kept() {
+		let matches = match_keys(keyed(&["a", "b", "c"]), keyed(&["c", "a"]));
+		assert_eq!(matches, vec![Some(2), Some(0)]);
+		assert_eq!(kept(3, &matches), vec![true, false, true]);
+	}
+
+	#[test]
+	fn unkeyed_items_match_by_position() {
+		let old = [None, Some("a"), None];
+		let new = [None, None, None, None];
+		let matches = match_keys(old.into_iter(), new.into_iter());
+		assert_eq!(matches, vec![Some(0), None, Some(2), None]);
+	}
+}
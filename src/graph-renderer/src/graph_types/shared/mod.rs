@@ -1,2 +1,15 @@
 pub mod consts;
+pub mod crosshair;
+pub mod downsampling;
+pub mod focus_ring;
+pub mod gestures;
+pub mod interpolation;
+pub mod keys;
+pub mod points;
+pub mod raster;
+pub mod scale;
+pub mod selection;
+pub mod spatial_index;
+pub mod ticks;
+pub mod tooltip;
 pub mod types;
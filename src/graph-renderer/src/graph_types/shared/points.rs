@@ -0,0 +1,170 @@
+use crate::animation::spring::{Spring, SpringStateData};
+use crate::animation::*;
+use crate::graph_types::shared::types::{ClickingState, PointerState, SelectedState};
+use crate::graph_types::utils::Color;
+use crate::utils::lerp;
+use crate::{DefineAnimation, DefineSpringAnimation};
+
+DefineSpringAnimation!(PointHoverAnimationData, CurrentPointHoverAnimData, scale);
+DefineAnimation!(ClickingPointAnimData, CurrentClickingPointAnimData, color_t);
+DefineAnimation!(SelectPointAnimData, CurrentSelectPointAnimData, color_t);
+
+// How every point of a chart reacts to the pointer
+#[derive(Debug, Clone, Copy)]
+pub struct PointInteraction {
+	pub hover_color: Color,
+	pub selected_color: Color,
+	pub hover_scale: f32,
+}
+
+// Hovering, clicking and selecting a point, along with the scale and color they animate it to
+#[derive(Debug, Clone)]
+pub struct PointState {
+	pub scale: f32,
+	pub color: Color,
+	pub selected_state: SelectedState,
+
+	color_t: f32,
+	pointer_state: PointerState,
+	hover_anim: PointHoverAnimationData,
+	clicking_state: ClickingState,
+	clicking_point_anim: ClickingPointAnimData,
+}
+
+impl PointState {
+	// Grows from scale to its full size
+	pub fn new(color: Color, selected_state: SelectedState, scale: f32, timestamp: f64) -> Self {
+		Self {
+			scale,
+			color,
+			selected_state,
+			color_t: 0.,
+			pointer_state: PointerState::None,
+			hover_anim: PointHoverAnimationData {
+				timestamp,
+				scale: SpringStateData {
+					from: scale,
+					to: 1.0,
+					velocity: 0.,
+				},
+			},
+			clicking_state: ClickingState::None,
+			clicking_point_anim: ClickingPointAnimData {
+				timestamp,
+				color_t: AnimationStateData { from: 0., to: 0. },
+			},
+		}
+	}
+
+	pub fn is_selected(&self) -> bool {
+		matches!(
+			self.selected_state,
+			SelectedState::Selected { timestamp: _ }
+		)
+	}
+
+	// Starts the hover and click animations for where the pointer is. Returns whether the pointer was
+	// released on the point, which the chart turns into a selection
+	pub fn update_pointer(
+		&mut self,
+		hovered: bool,
+		clicking_state: ClickingState,
+		interaction: &PointInteraction,
+		animations: &AnimationOptions,
+		timestamp: f64,
+	) -> bool {
+		if !hovered {
+			if let PointerState::None = self.pointer_state {
+				return false;
+			}
+
+			self.pointer_state = PointerState::None;
+			self.hover_anim = self.hover_anim.retarget(
+				animations.hover(),
+				timestamp,
+				CurrentPointHoverAnimData { scale: 1.0 },
+			);
+			self.clicking_point_anim = ClickingPointAnimData {
+				timestamp,
+				color_t: AnimationStateData {
+					from: self.color_t,
+					to: 0.,
+				},
+			};
+			self.clicking_state = ClickingState::None;
+			return false;
+		}
+
+		if let PointerState::None = self.pointer_state {
+			self.pointer_state = PointerState::Hover;
+			self.hover_anim = self.hover_anim.retarget(
+				animations.hover(),
+				timestamp,
+				CurrentPointHoverAnimData {
+					scale: interaction.hover_scale,
+				},
+			);
+		}
+
+		match clicking_state {
+			ClickingState::Holding => {
+				if !matches!(self.clicking_state, ClickingState::Holding) {
+					self.clicking_point_anim = ClickingPointAnimData {
+						timestamp,
+						color_t: AnimationStateData {
+							from: self.color_t,
+							to: interaction.hover_color.a as f32 / 255.,
+						},
+					};
+					self.clicking_state = ClickingState::Holding;
+				}
+				false
+			}
+			ClickingState::JustReleased => {
+				self.clicking_point_anim = ClickingPointAnimData {
+					timestamp,
+					color_t: AnimationStateData {
+						from: self.color_t,
+						to: 0.,
+					},
+				};
+				self.clicking_state = ClickingState::JustReleased;
+				true
+			}
+			ClickingState::None => false,
+		}
+	}
+
+	// Moves the scale and color along, starting from base_color. Returns whether they have settled
+	pub fn animate(
+		&mut self,
+		base_color: Color,
+		interaction: &PointInteraction,
+		animations: &AnimationOptions,
+		timestamp: f64,
+	) -> bool {
+		let mut done = true;
+
+		let animation = Animation::new(&self.hover_anim, timestamp, animations.hover());
+		done &= animation.is_completed();
+		self.scale = animation.get_current().scale;
+
+		let anim_data = SelectPointAnimData {
+			color_t: match self.selected_state {
+				SelectedState::None { timestamp: _ } => AnimationStateData { from: 1., to: 0. },
+				SelectedState::Selected { timestamp: _ } => AnimationStateData { from: 0., to: 1. },
+			},
+			timestamp: self.selected_state.get_timestamp(),
+		};
+		let animation = Animation::new(&anim_data, timestamp, animations.select());
+		done &= animation.is_completed();
+		self.color = base_color.lerp(&interaction.selected_color, animation.get_current().color_t);
+
+		let animation = Animation::new(&self.clicking_point_anim, timestamp, animations.click());
+		done &= animation.is_completed();
+		self.color_t = animation.get_current().color_t;
+		self.color = self.color.lerp(&interaction.hover_color, self.color_t);
+
+		done
+	}
+}
@@ -0,0 +1,436 @@
+use crate::graph_types::utils::Color;
+
+// A software stand-in for the WebGL programs, so charts can be drawn without a browser or a GPU. Takes the
+// same vertex arrays: positions are clip space (x, y) pairs and colors are (r, g, b, a) from 0 to 1, three
+// vertices to a triangle. Labels are laid out by the caller and are not part of it
+#[derive(Debug, Clone)]
+pub struct Raster {
+	width: u32,
+	height: u32,
+	// RGBA, row by row from the top
+	pixels: Vec<u8>,
+}
+
+impl Raster {
+	pub fn new(width: u32, height: u32, background_color: Color) -> Self {
+		let background = [
+			background_color.r,
+			background_color.g,
+			background_color.b,
+			background_color.a,
+		];
+		Self {
+			width,
+			height,
+			pixels: background.repeat(width as usize * height as usize),
+		}
+	}
+
+	pub fn into_pixels(self) -> Vec<u8> {
+		self.pixels
+	}
+
+	// What general.frag does, the vertex colors blended across each triangle
+	pub fn fill_triangles(&mut self, positions: &[f32], colors: &[f32]) {
+		for (triangle, positions) in positions.chunks_exact(6).enumerate() {
+			let Some(colors) = colors.get(triangle * 12..triangle * 12 + 12) else {
+				break;
+			};
+			self.fill_triangle(positions, |weights| Some(interpolate::<4>(colors, weights)));
+		}
+	}
+
+	// What bars.frag does: the top corners rounded off by corner_radius pixels, and the color darkening
+	// towards the baseline. Relative positions are (relative x, relative y, pixel width, pixel height)
+	pub fn fill_bars(
+		&mut self,
+		positions: &[f32],
+		colors: &[f32],
+		relative_positions: &[f32],
+		corner_radius: f32,
+	) {
+		for (triangle, positions) in positions.chunks_exact(6).enumerate() {
+			let (Some(colors), Some(relative_positions)) = (
+				colors.get(triangle * 12..triangle * 12 + 12),
+				relative_positions.get(triangle * 12..triangle * 12 + 12),
+			) else {
+				break;
+			};
+
+			self.fill_triangle(positions, |weights| {
+				let [relative_x, relative_y, width, height] = interpolate::<4>(relative_positions, weights);
+
+				// The right half is mirrored onto the left so that both corners are handled the same way
+				let x = (0.5 - (relative_x - 0.5).abs()) * width;
+				let y = relative_y * height;
+				if y > height - corner_radius && x < corner_radius {
+					let dx = x - corner_radius;
+					let dy = y - (height - corner_radius);
+					if dx * dx + dy * dy > corner_radius * corner_radius {
+						return None;
+					}
+				}
+
+				let [r, g, b, a] = interpolate::<4>(colors, weights);
+				let intensity = relative_y * 0.5 + 0.5;
+				Some([r * intensity, g * intensity, b * intensity, a])
+			});
+		}
+	}
+
+	// What lines.frag does, the color fading out over the last pixel on either side of the line. Relative
+	// positions are -1 on one edge of the line, 1 on the other and 0 in the middle
+	pub fn fill_lines(
+		&mut self,
+		positions: &[f32],
+		colors: &[f32],
+		relative_positions: &[f32],
+		half_width: f32,
+	) {
+		for (triangle, positions) in positions.chunks_exact(6).enumerate() {
+			let (Some(colors), Some(relative_positions)) = (
+				colors.get(triangle * 12..triangle * 12 + 12),
+				relative_positions.get(triangle * 3..triangle * 3 + 3),
+			) else {
+				break;
+			};
+
+			self.fill_triangle(positions, |weights| {
+				let [relative_position] = interpolate::<1>(relative_positions, weights);
+				let coverage = ((1. - relative_position.abs()) * half_width).clamp(0., 1.);
+
+				let [r, g, b, a] = interpolate::<4>(colors, weights);
+				Some([r, g, b, a * coverage])
+			});
+		}
+	}
+
+	// Shades every pixel whose center is inside the triangle, weights being how close it is to each vertex.
+	// Edges shared by two triangles are only drawn once, so see-through quads don't get a seam
+	fn fill_triangle(
+		&mut self,
+		positions: &[f32],
+		mut shade: impl FnMut([f32; 3]) -> Option<[f32; 4]>,
+	) {
+		let to_pixel = |x: f32, y: f32| {
+			(
+				(x + 1.) / 2. * self.width as f32,
+				(1. - y) / 2. * self.height as f32,
+			)
+		};
+		let a = to_pixel(positions[0], positions[1]);
+		let b = to_pixel(positions[2], positions[3]);
+		let c = to_pixel(positions[4], positions[5]);
+
+		let area = edge(a, b, c);
+		if area == 0. || !area.is_finite() {
+			return;
+		}
+
+		let min_x = a.0.min(b.0).min(c.0).floor().max(0.) as u32;
+		let max_x = (a.0.max(b.0).max(c.0).ceil().max(0.) as u32).min(self.width);
+		let min_y = a.1.min(b.1).min(c.1).floor().max(0.) as u32;
+		let max_y = (a.1.max(b.1).max(c.1).ceil().max(0.) as u32).min(self.height);
+
+		// Both windings get drawn, like with culling off
+		let sign = area.signum();
+		let edges = [(b, c), (c, a), (a, b)];
+
+		for y in min_y..max_y {
+			for x in min_x..max_x {
+				let center = (x as f32 + 0.5, y as f32 + 0.5);
+
+				let mut weights = [0.; 3];
+				let mut inside = true;
+				for (weight, (from, to)) in weights.iter_mut().zip(edges) {
+					let distance = edge(from, to, center) * sign;
+					if distance < 0. || (distance == 0. && !is_top_left(from, to, sign)) {
+						inside = false;
+						break;
+					}
+					*weight = distance / (area * sign);
+				}
+				if !inside {
+					continue;
+				}
+
+				if let Some(color) = shade(weights) {
+					self.blend(x, y, color);
+				}
+			}
+		}
+	}
+
+	// Paints over what is already there, letting it show through as much as the color is see-through
+	fn blend(&mut self, x: u32, y: u32, [r, g, b, a]: [f32; 4]) {
+		let index = (y as usize * self.width as usize + x as usize) * 4;
+		let pixel = &mut self.pixels[index..index + 4];
+
+		let src_a = a.clamp(0., 1.);
+		let dst_a = pixel[3] as f32 / 255.;
+		let out_a = src_a + dst_a * (1. - src_a);
+		if out_a <= 0. {
+			return;
+		}
+
+		for (channel, src) in pixel.iter_mut().zip([r, g, b]) {
+			let dst = *channel as f32 / 255.;
+			let out = (src.clamp(0., 1.) * src_a + dst * dst_a * (1. - src_a)) / out_a;
+			*channel = (out * 255.).round() as u8;
+		}
+		pixel[3] = (out_a * 255.).round() as u8;
+	}
+
+	// The pixels as a PNG file. Left uncompressed, it only has to be correct and quick to make
+	pub fn encode_png(&self) -> Vec<u8> {
+		let mut scanlines = Vec::with_capacity((self.width as usize * 4 + 1) * self.height as usize);
+		for row in self.pixels.chunks_exact((self.width as usize * 4).max(1)) {
+			// No filter
+			scanlines.push(0);
+			scanlines.extend_from_slice(row);
+		}
+
+		let mut header = Vec::with_capacity(13);
+		header.extend_from_slice(&self.width.to_be_bytes());
+		header.extend_from_slice(&self.height.to_be_bytes());
+		// 8 bits per channel, RGBA, deflate, adaptive filtering, not interlaced
+		header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+		let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+		write_chunk(&mut png, b"IHDR", &header);
+		write_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+		write_chunk(&mut png, b"IEND", &[]);
+		png
+	}
+}
+
+// Which side of from -> to the point is on, times the length of the edge
+fn edge(from: (f32, f32), to: (f32, f32), point: (f32, f32)) -> f32 {
+	(to.0 - from.0) * (point.1 - from.1) - (to.1 - from.1) * (point.0 - from.0)
+}
+
+// Pixels exactly on an edge belong to the triangle only if it is a top or a left edge
+fn is_top_left(from: (f32, f32), to: (f32, f32), sign: f32) -> bool {
+	let (dx, dy) = ((to.0 - from.0) * sign, (to.1 - from.1) * sign);
+	(dy == 0. && dx < 0.) || dy > 0.
+}
+
+fn interpolate<const N: usize>(values: &[f32], weights: [f32; 3]) -> [f32; N] {
+	let mut result = [0.; N];
+	for (i, value) in result.iter_mut().enumerate() {
+		*value = values[i] * weights[0] + values[N + i] * weights[1] + values[N * 2 + i] * weights[2];
+	}
+	result
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+	png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+	let start = png.len();
+	png.extend_from_slice(kind);
+	png.extend_from_slice(data);
+	let crc = crc32(&png[start..]);
+	png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// A zlib stream made of stored deflate blocks, which hold at most 65535 bytes each
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+	let mut stream = vec![0x78, 0x01];
+	let mut blocks = data.chunks(0xffff).peekable();
+	if blocks.peek().is_none() {
+		stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+	}
+	while let Some(block) = blocks.next() {
+		let is_last = blocks.peek().is_none();
+		let len = block.len() as u16;
+		stream.push(is_last as u8);
+		stream.extend_from_slice(&len.to_le_bytes());
+		stream.extend_from_slice(&(!len).to_le_bytes());
+		stream.extend_from_slice(block);
+	}
+	stream.extend_from_slice(&adler32(data).to_be_bytes());
+	stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+	let mut crc = 0xffff_ffff_u32;
+	for byte in data {
+		crc ^= *byte as u32;
+		for _ in 0..8 {
+			let mask = (crc & 1).wrapping_neg();
+			crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+		}
+	}
+	!crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+	let (mut a, mut b) = (1_u32, 0_u32);
+	for chunk in data.chunks(5552) {
+		for byte in chunk {
+			a += *byte as u32;
+			b += a;
+		}
+		a %= 65521;
+		b %= 65521;
+	}
+	(b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const BLACK: Color = Color {
+		r: 0,
+		g: 0,
+		b: 0,
+		a: 255,
+	};
+
+	// Two triangles between clip space left, top, right and bottom, all in one color
+	fn quad(
+		(left, top, right, bottom): (f32, f32, f32, f32),
+		color: [f32; 4],
+	) -> (Vec<f32>, Vec<f32>) {
+		let positions = vec![
+			left, top, right, top, left, bottom, right, top, right, bottom, left, bottom,
+		];
+		(positions, color.repeat(6))
+	}
+
+	fn pixel(raster: &Raster, x: u32, y: u32) -> [u8; 4] {
+		let index = (y * raster.width + x) as usize * 4;
+		raster.pixels[index..index + 4].try_into().unwrap()
+	}
+
+	#[test]
+	fn starts_out_as_the_background() {
+		let raster = Raster::new(2, 2, Color::new(1, 2, 3, 4));
+		assert_eq!(raster.into_pixels(), [1, 2, 3, 4].repeat(4));
+	}
+
+	#[test]
+	fn fill_triangles_covers_the_pixels_inside() {
+		let mut raster = Raster::new(4, 4, BLACK);
+		let (positions, colors) = quad((-1., 1., 0., -1.), [1., 0., 0., 1.]);
+		raster.fill_triangles(&positions, &colors);
+
+		for y in 0..4 {
+			assert_eq!(pixel(&raster, 0, y), [255, 0, 0, 255]);
+			assert_eq!(pixel(&raster, 1, y), [255, 0, 0, 255]);
+			assert_eq!(pixel(&raster, 2, y), [0, 0, 0, 255]);
+			assert_eq!(pixel(&raster, 3, y), [0, 0, 0, 255]);
+		}
+	}
+
+	#[test]
+	fn fill_triangles_draws_shared_edges_once() {
+		let mut raster = Raster::new(8, 8, BLACK);
+		let (positions, colors) = quad((-1., 1., 1., -1.), [1., 1., 1., 0.5]);
+		raster.fill_triangles(&positions, &colors);
+
+		// The diagonal would come out brighter if both triangles blended over it
+		assert_eq!(raster.into_pixels(), [128, 128, 128, 255].repeat(64));
+	}
+
+	#[test]
+	fn fill_bars_rounds_the_top_corners_and_darkens_towards_the_baseline() {
+		let mut raster = Raster::new(8, 8, BLACK);
+		let (positions, colors) = quad((-1., 1., 1., -1.), [1., 1., 1., 1.]);
+		// Relative x and y of each vertex, then the size of the bar in pixels
+		let corner = |x: f32, y: f32| [x, y, 8., 8.];
+		let relative_positions = [
+			corner(0., 1.),
+			corner(1., 1.),
+			corner(0., 0.),
+			corner(1., 1.),
+			corner(1., 0.),
+			corner(0., 0.),
+		]
+		.concat();
+		raster.fill_bars(&positions, &colors, &relative_positions, 4.);
+
+		assert_eq!(pixel(&raster, 0, 0), [0, 0, 0, 255]);
+		assert_eq!(pixel(&raster, 7, 0), [0, 0, 0, 255]);
+		assert_eq!(pixel(&raster, 0, 7), [135, 135, 135, 255]);
+		assert_eq!(pixel(&raster, 4, 0), [247, 247, 247, 255]);
+	}
+
+	#[test]
+	fn fill_lines_fades_out_over_the_last_pixel_of_either_edge() {
+		let mut raster = Raster::new(8, 2, BLACK);
+		let (positions, colors) = quad((-1., 1., 1., -1.), [1., 1., 1., 1.]);
+		// An 8 pixel wide line running from top to bottom, its edges on the left and right
+		let relative_positions = [-1., 1., -1., 1., 1., -1.];
+		raster.fill_lines(&positions, &colors, &relative_positions, 4.);
+
+		let row: Vec<u8> = (0..8).map(|x| pixel(&raster, x, 0)[0]).collect();
+		assert_eq!(row, [128, 255, 255, 255, 255, 255, 255, 128]);
+		assert_eq!(pixel(&raster, 0, 1), [128, 128, 128, 255]);
+	}
+
+	#[test]
+	fn encode_png_writes_the_header_and_chunks() {
+		let mut raster = Raster::new(2, 1, BLACK);
+		let (positions, colors) = quad((-1., 1., 0., -1.), [1., 0., 0., 1.]);
+		raster.fill_triangles(&positions, &colors);
+		let png = raster.encode_png();
+
+		assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+		// Every chunk is its length, kind, data and a CRC of the kind and data
+		let mut chunks = Vec::new();
+		let mut rest = &png[8..];
+		while !rest.is_empty() {
+			let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+			let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+			assert_eq!(crc, crc32(&rest[4..8 + len]));
+			chunks.push((&rest[4..8], &rest[8..8 + len]));
+			rest = &rest[12 + len..];
+		}
+
+		let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| *kind).collect();
+		assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+		assert_eq!(chunks[0].1, [0, 0, 0, 2, 0, 0, 0, 1, 8, 6, 0, 0, 0]);
+		// A single unfiltered scanline, stored as is
+		let scanline = [0, 255, 0, 0, 255, 0, 0, 0, 255];
+		assert_eq!(chunks[1].1, zlib_stored(&scanline));
+		assert_eq!(&png[png.len() - 4..], [0xae, 0x42, 0x60, 0x82]);
+	}
+
+	#[test]
+	fn crc32_matches_the_check_value() {
+		assert_eq!(crc32(b""), 0);
+		assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+	}
+
+	#[test]
+	fn adler32_matches_known_values() {
+		assert_eq!(adler32(b""), 1);
+		assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+		// Long enough for the sums to wrap around the modulus
+		assert_eq!(adler32(&[255; 100_000]), 0x149a_302c);
+	}
+
+	#[test]
+	fn stored_deflate_splits_into_blocks() {
+		assert_eq!(
+			zlib_stored(b"abc"),
+			[
+				0x78, 0x01, 1, 3, 0, 0xfc, 0xff, b'a', b'b', b'c', 0x02, 0x4d, 0x01, 0x27
+			]
+		);
+		assert_eq!(
+			zlib_stored(b""),
+			[0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]
+		);
+
+		let data = vec![7; 0xffff + 10];
+		let stream = zlib_stored(&data);
+		// The first block is full and not the last one, the second holds the rest
+		assert_eq!(&stream[2..7], [0, 0xff, 0xff, 0, 0]);
+		let second = 7 + 0xffff;
+		assert_eq!(&stream[second..second + 5], [1, 10, 0, 0xf5, 0xff]);
+		assert_eq!(stream.len(), 2 + 5 + 0xffff + 5 + 10 + 4);
+	}
+}
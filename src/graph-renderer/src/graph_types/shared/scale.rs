@@ -0,0 +1,216 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::graph_types::shared::ticks::{nice_step, ticks_in};
+use crate::graph_types::shared::types::{ScaleLineObject, ValueAxisDomain};
+use crate::log_warn;
+use crate::utils::{NumUtils, PreAllocatedCollection};
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum ScaleType {
+	Linear,
+	Log10,
+	SymLog,
+	Sqrt,
+}
+
+impl ScaleType {
+	// Maps a value into the space the axis is linear in
+	fn transform(&self, value: f32) -> f32 {
+		match self {
+			ScaleType::Linear => value,
+			ScaleType::Log10 => value.log10(),
+			ScaleType::SymLog => value.signum() * value.abs().ln_1p(),
+			ScaleType::Sqrt => value.signum() * value.abs().sqrt(),
+		}
+	}
+
+	// Powers of ten on logarithmic axes, nice steps elsewhere
+	fn nice_bounds(&self, min: f32, max: f32, ticks: u32) -> (f32, f32) {
+		match self {
+			ScaleType::Linear | ScaleType::Sqrt => {
+				let step = nice_step(max - min, ticks as usize);
+				((min / step).floor() * step, (max / step).ceil() * step)
+			}
+			ScaleType::Log10 => (min, 10f32.powf(max.log10().ceil())),
+			ScaleType::SymLog => {
+				let decade = |value: f32| match value.abs() {
+					0. => 0.,
+					abs if abs <= 1. => value.signum(),
+					abs => value.signum() * 10f32.powf(abs.log10().ceil()),
+				};
+				(decade(min), decade(max))
+			}
+		}
+	}
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Scale {
+	scale_type: ScaleType,
+	min: f32,
+	max: f32,
+	transformed_min: f32,
+	transformed_max: f32,
+	ticks: u32,
+}
+
+impl Scale {
+	// A logarithmic axis can't reach zero, so its domain starts at the decade below min_positive instead
+	pub fn new(domain: &ValueAxisDomain, min: f32, max: f32, min_positive: Option<f32>) -> Self {
+		let scale_type = domain.scale;
+		let (mut min, mut max) = match scale_type {
+			ScaleType::Log10 => {
+				let min = 10f32.powf(min_positive.unwrap_or(1.).log10().floor());
+				(min, max.max(min * 10.))
+			}
+			_ => (min, if max > min { max } else { min + 1. }),
+		};
+
+		if domain.nice_bounds {
+			(min, max) = scale_type.nice_bounds(min, max, domain.ticks);
+		}
+
+		if let Some(domain_min) = domain.min
+			&& (domain_min > 0. || !matches!(scale_type, ScaleType::Log10))
+		{
+			min = domain_min;
+		}
+		if let Some(domain_max) = domain.max {
+			max = domain_max;
+		}
+		if max <= min {
+			max = match scale_type {
+				ScaleType::Log10 => min * 10.,
+				_ => min + 1.,
+			};
+		}
+
+		Self {
+			scale_type,
+			min,
+			max,
+			transformed_min: scale_type.transform(min),
+			transformed_max: scale_type.transform(max),
+			ticks: domain.ticks.max(1),
+		}
+	}
+
+	// Where bars and areas grow from, zero when it is part of the domain
+	pub fn baseline(&self) -> f32 {
+		0f32.clamp(self.min, self.max)
+	}
+
+	// 0 at the bottom of the domain and 1 at the top
+	pub fn normalize(&self, value: f32) -> f32 {
+		let value = value.clamp(self.min, self.max);
+		(self.scale_type.transform(value) - self.transformed_min)
+			/ (self.transformed_max - self.transformed_min)
+	}
+
+	// Multiples of step on linear axes and powers of ten on logarithmic ones, along with their index from zero
+	fn ticks(&self, step: f32) -> Vec<(i32, f32)> {
+		match self.scale_type {
+			ScaleType::Linear | ScaleType::Sqrt => ticks_in(self.min, self.max, step)
+				.map(|value| ((value / step).round() as i32, value))
+				.collect(),
+			ScaleType::Log10 => {
+				let first = self.min.log10().ceil() as i32;
+				let last = self.max.log10().floor() as i32;
+				(first..=last).map(|i| (i, 10f32.powi(i))).collect()
+			}
+			// 0, ±1, ±10, ±100...
+			ScaleType::SymLog => {
+				let decade = |value: f32| {
+					if value.abs() < 1. {
+						0
+					} else {
+						value.signum() as i32 * (value.abs().log10().floor() as i32 + 1)
+					}
+				};
+				(decade(self.min)..=decade(self.max))
+					.map(|i| match i {
+						0 => (i, 0.),
+						_ => (i, i.signum() as f32 * 10f32.powi(i.abs() - 1)),
+					})
+					.collect()
+			}
+		}
+	}
+}
+
+// Lines at the edges of the graph, at the baseline and at readable values in between
+pub fn calculate_scale_lines(
+	scale_lines: &mut PreAllocatedCollection<ScaleLineObject>,
+	scale: &Scale,
+	(x_offset, width): (u32, u32),
+	(top, bottom): (f32, f32),
+	smallest_scale: f32,
+	min_pixel_dist: f32,
+) {
+	let thickness = 2;
+	let height = bottom - top;
+
+	let range = scale.max - scale.min;
+
+	// As many ticks as were asked for, as long as they stay min_pixel_dist apart
+	let max_ticks = scale.ticks.min((height / min_pixel_dist.max(1.)) as u32);
+	let step = nice_step(range, max_ticks as usize).max(smallest_scale);
+
+	if step / range * height < 1. {
+		log_warn!("pixel_distance < 1");
+		return;
+	}
+
+	let value_to_y = |value: f32| bottom - scale.normalize(value) * height;
+	let scale_line = |y: f32, intensity: u8, value: f32| ScaleLineObject {
+		x: x_offset,
+		y: (y - thickness as f32).to_u32(),
+		width: (width as i32 - x_offset as i32).to_u32(),
+		height: thickness,
+		intensity,
+		value,
+	};
+
+	scale_lines[0] = ScaleLineObject {
+		y: top.to_u32(),
+		..scale_line(top, 255, scale.max)
+	};
+	let mut count = 1;
+
+	let baseline = scale.baseline();
+	let baseline_y = value_to_y(baseline);
+	let has_bottom_line = baseline > scale.min;
+
+	// Reserve room for the baseline and bottom lines
+	let capacity = scale_lines.get_data().len() - 2;
+	let mut last_y = bottom;
+	for (i, value) in scale.ticks(step) {
+		let y = value_to_y(value);
+
+		// Keep clear of the edges, the baseline and the previous line, which nonlinear scales can bunch up
+		if count >= capacity
+			|| value == baseline
+			|| y - top < min_pixel_dist * (2.0 / 3.0)
+			|| (has_bottom_line && bottom - y < min_pixel_dist * (2.0 / 3.0))
+			|| (baseline_y - y).abs() < min_pixel_dist * (2.0 / 3.0)
+			|| (last_y - y).abs() < min_pixel_dist
+		{
+			continue;
+		}
+
+		scale_lines[count] = scale_line(y, if i % 2 == 0 { 100 } else { 50 }, value);
+		count += 1;
+		last_y = y;
+	}
+
+	scale_lines[count] = scale_line(baseline_y, 200, baseline);
+	count += 1;
+
+	if has_bottom_line {
+		scale_lines[count] = scale_line(bottom, 255, scale.min);
+		count += 1;
+	}
+
+	scale_lines.set_size(count);
+}
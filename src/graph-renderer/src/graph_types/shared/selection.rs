@@ -0,0 +1,147 @@
+use proc_macros::wasm_struct;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::graph_types::shared::consts::VERTICES_PER_QUAD;
+use crate::graph_types::shared::types::ClickingState;
+use crate::graph_types::utils::Color;
+use crate::utils::PreAllocatedCollection;
+
+pub const BRUSH_QUADS: usize = 1;
+// How far the pointer has to move while held down before it brushes instead of clicking
+const BRUSH_MIN_DISTANCE: f32 = 5.;
+
+#[wasm_struct]
+pub struct SelectionOptions {
+	// Clicking with a modifier held adds to the selection instead of replacing it
+	multi_select: bool,
+	// Dragging selects everything inside the rectangle it covers
+	brush: bool,
+	brush_color: Color,
+}
+
+impl SelectionOptions {
+	pub fn multi_select(&self) -> bool {
+		self.multi_select
+	}
+}
+
+// The rectangle dragged out while the pointer is held down
+#[derive(Debug, Clone)]
+pub struct Brush {
+	enabled: bool,
+	color: Color,
+
+	start: Option<(u32, u32)>,
+	// Only set once the pointer has moved far enough to tell the drag apart from a click
+	rect: Option<(u32, u32, u32, u32)>,
+}
+
+impl Brush {
+	pub fn new(options: &SelectionOptions) -> Self {
+		Self {
+			enabled: options.brush,
+			color: options.brush_color,
+			start: None,
+			rect: None,
+		}
+	}
+
+	// Follows the pointer while it is held down. Returns the brushed rectangle (x, y, width, height) when
+	// it is let go after dragging
+	pub fn update(
+		&mut self,
+		pointer_x: Option<u32>,
+		pointer_y: Option<u32>,
+		clicking_state: ClickingState,
+	) -> Option<(u32, u32, u32, u32)> {
+		if !self.enabled {
+			return None;
+		}
+
+		let pointer = pointer_x.zip(pointer_y);
+		match (clicking_state, pointer) {
+			(ClickingState::Holding | ClickingState::JustReleased, Some(pointer)) => {
+				let start = *self.start.get_or_insert(pointer);
+				let distance = (pointer.0 as f32 - start.0 as f32).hypot(pointer.1 as f32 - start.1 as f32);
+				if self.rect.is_some() || distance >= BRUSH_MIN_DISTANCE {
+					self.rect = Some((
+						start.0.min(pointer.0),
+						start.1.min(pointer.1),
+						start.0.abs_diff(pointer.0),
+						start.1.abs_diff(pointer.1),
+					));
+				}
+			}
+			_ => {
+				self.start = None;
+				self.rect = None;
+			}
+		}
+
+		if let ClickingState::JustReleased = clicking_state {
+			self.start = None;
+			return self.rect.take();
+		}
+
+		None
+	}
+
+	// A drag in progress, clicks are ignored while brushing
+	pub fn is_brushing(&self) -> bool {
+		self.rect.is_some()
+	}
+
+	pub fn quads_len(&self) -> usize {
+		if self.is_brushing() { BRUSH_QUADS } else { 0 }
+	}
+
+	pub fn write_vertex_positions(
+		&self,
+		positions: &mut PreAllocatedCollection<f32>,
+		first_quad: usize,
+		(width, height): (u32, u32),
+	) {
+		let Some((x, y, rect_width, rect_height)) = self.rect else {
+			return;
+		};
+		let vert_index = first_quad * VERTICES_PER_QUAD * 2;
+
+		let left = (x as f32 / width as f32) * 2. - 1.;
+		let right = ((x + rect_width) as f32 / width as f32) * 2. - 1.;
+		let top = -((y as f32 / height as f32) * 2. - 1.);
+		let bottom = -(((y + rect_height) as f32 / height as f32) * 2. - 1.);
+
+		positions[vert_index] = left;
+		positions[vert_index + 1] = bottom;
+		positions[vert_index + 2] = left;
+		positions[vert_index + 3] = top;
+		positions[vert_index + 4] = right;
+		positions[vert_index + 5] = top;
+
+		positions[vert_index + 6] = right;
+		positions[vert_index + 7] = bottom;
+		positions[vert_index + 8] = left;
+		positions[vert_index + 9] = bottom;
+		positions[vert_index + 10] = right;
+		positions[vert_index + 11] = top;
+	}
+
+	pub fn write_vertex_colors(&self, colors: &mut PreAllocatedCollection<f32>, first_quad: usize) {
+		for i in 0..self.quads_len() {
+			let vert_index = (first_quad + i) * VERTICES_PER_QUAD * 4;
+
+			for offset in 0..VERTICES_PER_QUAD {
+				let offset = offset * 4;
+				colors[vert_index + offset] = self.color.r as f32 / 255.;
+				colors[vert_index + offset + 1] = self.color.g as f32 / 255.;
+				colors[vert_index + offset + 2] = self.color.b as f32 / 255.;
+				colors[vert_index + offset + 3] = self.color.a as f32 / 255.;
+			}
+		}
+	}
+}
+
+// Whether two (x, y, width, height) rectangles overlap
+pub fn intersects(a: (u32, u32, u32, u32), b: (u32, u32, u32, u32)) -> bool {
+	a.0 <= b.0 + b.2 && b.0 <= a.0 + a.2 && a.1 <= b.1 + b.3 && b.1 <= a.1 + a.3
+}
@@ -0,0 +1,187 @@
+// Left, top, right and bottom in pixels
+pub type BoundingBox = (f32, f32, f32, f32);
+
+const MIN_CELL_SIZE: f32 = 16.;
+
+// Uniform grid over the graph where every item is listed in each cell its bounding box touches.
+// Cells are stored back to back in `items`, with `cell_starts[i]..cell_starts[i + 1]` belonging to cell i
+#[derive(Debug)]
+pub struct SpatialIndex {
+	cell_size: f32,
+	columns: usize,
+	rows: usize,
+	cell_starts: Vec<usize>,
+	items: Vec<usize>,
+	dirty: bool,
+}
+
+impl SpatialIndex {
+	pub fn new() -> Self {
+		Self {
+			cell_size: MIN_CELL_SIZE,
+			columns: 0,
+			rows: 0,
+			cell_starts: vec![0],
+			items: Vec::new(),
+			dirty: true,
+		}
+	}
+
+	// Marks the index as out of date, it is rebuilt the next time the items are laid out
+	pub fn invalidate(&mut self) {
+		self.dirty = true;
+	}
+
+	pub fn is_dirty(&self) -> bool {
+		self.dirty
+	}
+
+	fn cell_range(&self, (left, top, right, bottom): BoundingBox) -> (usize, usize, usize, usize) {
+		let cell = |value: f32, count: usize| {
+			((value / self.cell_size).floor().max(0.) as usize).min(count.saturating_sub(1))
+		};
+		(
+			cell(left, self.columns),
+			cell(top, self.rows),
+			cell(right, self.columns),
+			cell(bottom, self.rows),
+		)
+	}
+
+	pub fn rebuild(
+		&mut self,
+		(width, height): (u32, u32),
+		boxes: impl Iterator<Item = (usize, BoundingBox)> + Clone,
+	) {
+		// Roughly one item per cell, with a lower limit so tiny cells don't blow up the memory use
+		let count = boxes.clone().count().max(1);
+		let area = width.max(1) as f32 * height.max(1) as f32;
+		self.cell_size = (area / count as f32).sqrt().max(MIN_CELL_SIZE);
+		self.columns = (width as f32 / self.cell_size).ceil().max(1.) as usize;
+		self.rows = (height as f32 / self.cell_size).ceil().max(1.) as usize;
+
+		let cells = self.columns * self.rows;
+		self.cell_starts.clear();
+		self.cell_starts.resize(cells + 1, 0);
+
+		// Count the items in each cell, then turn the counts into start offsets and fill them in
+		for (_, bounding_box) in boxes.clone() {
+			let (left, top, right, bottom) = self.cell_range(bounding_box);
+			for row in top..=bottom {
+				for column in left..=right {
+					self.cell_starts[row * self.columns + column + 1] += 1;
+				}
+			}
+		}
+
+		for cell in 0..cells {
+			self.cell_starts[cell + 1] += self.cell_starts[cell];
+		}
+
+		self.items.clear();
+		self.items.resize(self.cell_starts[cells], 0);
+		let mut next = self.cell_starts.clone();
+		for (item, bounding_box) in boxes {
+			let (left, top, right, bottom) = self.cell_range(bounding_box);
+			for row in top..=bottom {
+				for column in left..=right {
+					let cell = row * self.columns + column;
+					self.items[next[cell]] = item;
+					next[cell] += 1;
+				}
+			}
+		}
+
+		self.dirty = false;
+	}
+
+	// Every item whose bounding box might be within radius of (x, y).
+	// Items spanning several cells can be returned more than once
+	pub fn query(&self, x: f32, y: f32, radius: f32) -> impl Iterator<Item = usize> + '_ {
+		let (left, top, right, bottom) = if self.columns == 0 || self.rows == 0 {
+			(1, 1, 0, 0)
+		} else {
+			self.cell_range((x - radius, y - radius, x + radius, y + radius))
+		};
+
+		(top..=bottom).flat_map(move |row| {
+			let start = self.cell_starts[row * self.columns + left];
+			let end = self.cell_starts[row * self.columns + right + 1];
+			self.items[start..end].iter().copied()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// 100 by 100 pixels with four items makes a two by two grid of 50 pixel cells
+	fn index() -> SpatialIndex {
+		let boxes = [
+			(10., 10., 20., 20.),
+			(80., 10., 90., 20.),
+			(10., 80., 20., 90.),
+			(40., 40., 60., 60.),
+		];
+		let mut index = SpatialIndex::new();
+		index.rebuild((100, 100), boxes.into_iter().enumerate());
+		index
+	}
+
+	fn query(index: &SpatialIndex, x: f32, y: f32, radius: f32) -> Vec<usize> {
+		let mut items: Vec<usize> = index.query(x, y, radius).collect();
+		items.sort();
+		items.dedup();
+		items
+	}
+
+	#[test]
+	fn rebuilding_clears_dirty() {
+		let mut index = index();
+		assert!(!index.is_dirty());
+
+		index.invalidate();
+		assert!(index.is_dirty());
+	}
+
+	#[test]
+	fn query_returns_the_items_in_the_cell() {
+		let index = index();
+		assert_eq!(query(&index, 15., 15., 0.), vec![0, 3]);
+		assert_eq!(query(&index, 85., 15., 0.), vec![1, 3]);
+		assert_eq!(query(&index, 15., 85., 0.), vec![2, 3]);
+		assert_eq!(query(&index, 85., 85., 0.), vec![3]);
+	}
+
+	#[test]
+	fn radius_reaches_into_neighbouring_cells() {
+		let index = index();
+		assert_eq!(query(&index, 15., 15., 40.), vec![0, 1, 2, 3]);
+		assert_eq!(query(&index, 30., 85., 30.), vec![2, 3]);
+	}
+
+	#[test]
+	fn items_are_listed_in_every_cell_they_touch() {
+		let index = index();
+		let hits = index.query(50., 50., 1.).filter(|&item| item == 3).count();
+		assert_eq!(hits, 4);
+	}
+
+	#[test]
+	fn points_outside_the_graph_use_the_nearest_cell() {
+		let index = index();
+		assert_eq!(query(&index, -100., -100., 0.), vec![0, 3]);
+		assert_eq!(query(&index, 500., 500., 0.), vec![3]);
+	}
+
+	#[test]
+	fn empty_index_returns_nothing() {
+		let index = SpatialIndex::new();
+		assert_eq!(index.query(0., 0., 100.).count(), 0);
+
+		let mut index = SpatialIndex::new();
+		index.rebuild((100, 100), std::iter::empty());
+		assert_eq!(index.query(50., 50., 100.).count(), 0);
+	}
+}
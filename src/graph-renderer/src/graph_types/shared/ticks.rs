@@ -0,0 +1,90 @@
+// Heckbert's nice numbers, 1, 2, 5 or 10 times a power of ten.
+// Rounds to the closest one when `round` is set, otherwise to the next one up.
+pub fn nice_number(value: f32, round: bool) -> f32 {
+	if value <= 0. || !value.is_finite() {
+		return 0.;
+	}
+
+	let power = 10f32.powf(value.log10().floor());
+	let fraction = value / power;
+
+	let nice_fraction = if round {
+		if fraction < 1.5 {
+			1.
+		} else if fraction < 3. {
+			2.
+		} else if fraction < 7. {
+			5.
+		} else {
+			10.
+		}
+	} else if fraction <= 1. {
+		1.
+	} else if fraction <= 2. {
+		2.
+	} else if fraction <= 5. {
+		5.
+	} else {
+		10.
+	};
+
+	nice_fraction * power
+}
+
+// Smallest nice step that splits the range into at most max_ticks intervals
+pub fn nice_step(range: f32, max_ticks: usize) -> f32 {
+	nice_number(range / max_ticks.max(1) as f32, false)
+}
+
+// Every multiple of step that lies within [min, max]
+pub fn ticks_in(min: f32, max: f32, step: f32) -> impl Iterator<Item = f32> {
+	let first = if step > 0. {
+		(min / step).ceil() as i64
+	} else {
+		1
+	};
+	let last = if step > 0. {
+		// Allow for a bit of float error so max itself still gets a tick
+		((max + step * 1e-3) / step).floor() as i64
+	} else {
+		0
+	};
+
+	(first..=last).map(move |i| i as f32 * step)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ticks_from_zero_to_150() {
+		let step = nice_step(150., 4);
+		assert_eq!(step, 50.);
+
+		let ticks: Vec<f32> = ticks_in(0., 150., step).collect();
+		assert_eq!(ticks, vec![0., 50., 100., 150.]);
+	}
+
+	#[test]
+	fn ticks_start_at_the_first_multiple_in_range() {
+		let ticks: Vec<f32> = ticks_in(-30., 95., 25.).collect();
+		assert_eq!(ticks, vec![-25., 0., 25., 50., 75.]);
+	}
+
+	#[test]
+	fn nice_numbers() {
+		assert_eq!(nice_number(0.37, false), 0.5);
+		assert_eq!(nice_number(37., false), 50.);
+		assert_eq!(nice_number(120., false), 200.);
+		assert_eq!(nice_number(120., true), 100.);
+		assert_eq!(nice_number(2.5, true), 2.);
+		assert_eq!(nice_number(800., true), 1000.);
+	}
+
+	#[test]
+	fn no_ticks_without_a_step() {
+		assert_eq!(nice_step(0., 4), 0.);
+		assert_eq!(ticks_in(0., 150., 0.).count(), 0);
+	}
+}
@@ -0,0 +1,143 @@
+use proc_macros::wasm_struct;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum TooltipMode {
+	// Only the hovered element
+	Hovered,
+	// Every series at the X closest to the pointer
+	NearestX,
+}
+
+#[wasm_struct]
+pub struct TooltipOptions {
+	mode: TooltipMode,
+	// Space between the anchor and the tooltip
+	offset: u32,
+}
+
+impl TooltipOptions {
+	pub fn mode(&self) -> TooltipMode {
+		self.mode
+	}
+
+	pub fn offset(&self) -> u32 {
+		self.offset
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct TooltipEntry {
+	pub series: usize,
+	pub index: usize,
+	pub name: String,
+	// The value as it was given, before scaling
+	pub value: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tooltip {
+	pub anchor_x: u32,
+	pub anchor_y: u32,
+	pub x: u32,
+	pub y: u32,
+	pub width: u32,
+	pub height: u32,
+	pub entries: Vec<TooltipEntry>,
+}
+
+impl Tooltip {
+	// Places a tooltip of the given size above and to the right of the anchor, flipping to the other side
+	// when it would leave the bounds (left, top, right, bottom) and clamping it inside them otherwise
+	pub fn new(
+		(anchor_x, anchor_y): (u32, u32),
+		(width, height): (u32, u32),
+		offset: u32,
+		(left, top, right, bottom): (u32, u32, u32, u32),
+		entries: Vec<TooltipEntry>,
+	) -> Self {
+		let x = if anchor_x.saturating_add(offset).saturating_add(width) <= right {
+			anchor_x + offset
+		} else {
+			anchor_x.saturating_sub(offset.saturating_add(width))
+		};
+		let y = if anchor_y >= top.saturating_add(offset).saturating_add(height) {
+			anchor_y.saturating_sub(offset.saturating_add(height))
+		} else {
+			anchor_y.saturating_add(offset)
+		};
+
+		// Too big to fit gets pinned to the top left
+		let x = x.min(right.saturating_sub(width)).max(left);
+		let y = y.min(bottom.saturating_sub(height)).max(top);
+
+		Self {
+			anchor_x,
+			anchor_y,
+			x,
+			y,
+			width,
+			height,
+			entries,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const BOUNDS: (u32, u32, u32, u32) = (10, 10, 110, 110);
+
+	fn place(anchor: (u32, u32)) -> (u32, u32) {
+		let tooltip = Tooltip::new(anchor, (30, 20), 5, BOUNDS, vec![]);
+		(tooltip.x, tooltip.y)
+	}
+
+	#[test]
+	fn goes_above_and_to_the_right_when_there_is_room() {
+		assert_eq!(place((50, 50)), (55, 25));
+	}
+
+	#[test]
+	fn flips_left_near_the_right_edge() {
+		assert_eq!(place((100, 50)), (65, 25));
+	}
+
+	#[test]
+	fn flips_below_near_the_top_edge() {
+		assert_eq!(place((50, 20)), (55, 25));
+	}
+
+	#[test]
+	fn flips_below_and_left_near_the_top_right_corner() {
+		assert_eq!(place((110, 10)), (75, 15));
+	}
+
+	#[test]
+	fn stays_inside_when_neither_side_fits() {
+		// Too wide for either side of the anchor, so it is clamped against the left edge
+		let tooltip = Tooltip::new((50, 50), (80, 20), 5, BOUNDS, vec![]);
+		assert_eq!((tooltip.x, tooltip.y), (10, 25));
+
+		// Too tall for either side of the anchor, so it is clamped against the bottom edge
+		let tooltip = Tooltip::new((50, 40), (30, 30), 5, (10, 10, 110, 60), vec![]);
+		assert_eq!((tooltip.x, tooltip.y), (55, 30));
+	}
+
+	#[test]
+	fn too_big_to_fit_is_pinned_to_the_top_left() {
+		let tooltip = Tooltip::new((50, 50), (200, 200), 5, BOUNDS, vec![]);
+		assert_eq!((tooltip.x, tooltip.y), (10, 10));
+	}
+
+	#[test]
+	fn large_anchors_and_offsets_do_not_overflow() {
+		let tooltip = Tooltip::new((u32::MAX, u32::MAX), (30, 20), u32::MAX, BOUNDS, vec![]);
+		assert_eq!((tooltip.x, tooltip.y), (10, 10));
+
+		let tooltip = Tooltip::new((50, 50), (30, 20), u32::MAX, BOUNDS, vec![]);
+		assert_eq!((tooltip.x, tooltip.y), (10, 90));
+	}
+}
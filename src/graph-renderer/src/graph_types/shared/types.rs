@@ -1,6 +1,8 @@
 use proc_macros::wasm_struct;
 use wasm_bindgen::prelude::wasm_bindgen;
 
+use crate::graph_types::shared::scale::ScaleType;
+
 #[wasm_bindgen]
 #[derive(Debug, Copy, Clone)]
 pub enum ClickingState {
@@ -53,4 +55,29 @@ pub struct ValueAxisLayout {
 	pub value_axis_width: u32,
 	pub value_axis_smallest_scale: f32,
 	pub value_axis_min_pixel_distance: u32,
+	value_axis_domain: ValueAxisDomain,
+}
+
+impl ValueAxisLayout {
+	pub fn domain(&self) -> &ValueAxisDomain {
+		&self.value_axis_domain
+	}
+}
+
+// How the value axis turns the range of the data into its domain.
+// Explicit bounds win over the data, nice bounds round the data out to the next tick
+#[wasm_struct]
+pub struct ValueAxisDomain {
+	pub scale: ScaleType,
+	pub ticks: u32,
+	pub min: Option<f32>,
+	pub max: Option<f32>,
+	pub nice_bounds: bool,
+}
+
+#[wasm_struct]
+pub struct XAxisLayout {
+	pub x_axis_tick_length: u32,
+	pub x_axis_tick_thickness: u32,
+	pub x_axis_min_tick_distance: u32,
 }
@@ -2,6 +2,7 @@
 
 use wasm_bindgen::prelude::*;
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
 	#[wasm_bindgen(js_namespace = console)]
@@ -24,6 +25,42 @@ extern "C" {
 	fn log_warn(s: &str);
 }
 
+// Off the web, like in the tests, there is no console so everything goes to stderr
+#[cfg(not(target_arch = "wasm32"))]
+fn log(s: &str) {
+	eprintln!("{s}");
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn log_info(s: &str) {
+	eprintln!("{s}");
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn log_debug(s: &str) {
+	eprintln!("{s}");
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn log_debug2(s: &str, s2: &str) {
+	eprintln!("{s} {s2}");
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn log_debug3(s: &str, _style: &str, s3: &str) {
+	eprintln!("{} {s3}", s.replace("%c", ""));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn log_error(s: &str) {
+	eprintln!("{s}");
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn log_warn(s: &str) {
+	eprintln!("{s}");
+}
+
 // Log
 pub trait Log {
 	fn log(&self);
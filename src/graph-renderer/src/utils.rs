@@ -11,7 +11,6 @@ use crate::wasm_assert;
 pub trait NumUtils {
 	fn to_u32(&self) -> u32;
 	fn to_u8(&self) -> u8;
-	fn ceil_nearest_power_2(&self) -> f32;
 }
 
 impl NumUtils for f32 {
@@ -21,13 +20,6 @@ impl NumUtils for f32 {
 	fn to_u8(&self) -> u8 {
 		self.max(0.0) as u8
 	}
-	fn ceil_nearest_power_2(&self) -> f32 {
-		let log_val = self.log2();
-
-		let rounded_log = log_val.ceil();
-
-		2.0f32.powf(rounded_log)
-	}
 }
 impl NumUtils for i32 {
 	fn to_u32(&self) -> u32 {
@@ -36,9 +28,6 @@ impl NumUtils for i32 {
 	fn to_u8(&self) -> u8 {
 		max(*self, 0) as u8
 	}
-	fn ceil_nearest_power_2(&self) -> f32 {
-		(*self as f32).ceil_nearest_power_2()
-	}
 }
 
 pub fn lerp(a: f32, b: f32, t: f32) -> f32 {
@@ -75,6 +64,10 @@ where
 		self.size = size;
 	}
 
+	pub fn as_slice(&self) -> &[T] {
+		&self.data[..self.size]
+	}
+
 	pub fn get_data(&self) -> &[T] {
 		&self.data
 	}
@@ -82,6 +75,49 @@ where
 	pub fn get_data_mut(&mut self) -> &mut [T] {
 		&mut self.data
 	}
+
+	// Keeps the items of the vec and leaves room for at least capacity items
+	pub fn from_vec(mut data: Vec<T>, filler: T, capacity: usize) -> Self {
+		let size = data.len();
+		data.resize(capacity.max(size), filler);
+		Self {
+			data: data.into_boxed_slice(),
+			size,
+		}
+	}
+
+	pub fn capacity(&self) -> usize {
+		self.data.len()
+	}
+
+	pub fn is_full(&self) -> bool {
+		self.size == self.data.len()
+	}
+
+	// Returns false without adding the value when there is no room left
+	pub fn push(&mut self, value: T) -> bool {
+		if self.is_full() {
+			return false;
+		}
+
+		self.data[self.size] = value;
+		self.size += 1;
+		true
+	}
+
+	// Drops the first count items and moves the rest to the front without reallocating
+	pub fn shift(&mut self, count: usize) {
+		let count = count.min(self.size);
+		self.data[..self.size].rotate_left(count);
+		self.size -= count;
+	}
+}
+
+// Only the items in use are printed, the rest of the capacity is filler
+impl<T: std::fmt::Debug> std::fmt::Debug for PreAllocatedCollection<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_list().entries(&self.data[..self.size]).finish()
+	}
 }
 
 impl<'a, T> IntoIterator for &'a PreAllocatedCollection<T> {
@@ -4,7 +4,9 @@ import type {
 	IGraphRenderer,
 	UnknownGraphRenderer,
 } from "./graphTypes/graphRenderer.js";
+import type { AnimationPolicy } from "./graphTypes/shared/types.js";
 import { sleepFor } from "./utils.js";
+import { isAdditivePointerEvent } from "./graphTypes/wasmUtils.js";
 
 export type ClickingState = "None" | "Holding" | "JustReleased";
 
@@ -12,6 +14,8 @@ export default class GraphManager {
 	private initOutput: InitOutput;
 	private renderers: Set<IGraphRenderer & UnknownGraphRenderer> = new Set();
 	private timestamp!: number;
+	// Overrides the animation policy of every graph, including ones added later
+	private animationPolicy: AnimationPolicy | null = null;
 
 	private pointerStart: {
 		touchId: number | undefined;
@@ -86,10 +90,15 @@ export default class GraphManager {
 	) {
 		trace();
 		renderer.init(this.initOutput.memory, this.timestamp);
+		if (this.animationPolicy) {
+			renderer.setAnimationPolicy(this.animationPolicy);
+		}
 		renderer.update(this.timestamp);
 		renderer.render(this.timestamp);
 
 		const canvas = renderer.getCanvas();
+		// Pinching and panning the chart rather than the page
+		canvas.style.touchAction = "none";
 		this.addInputHandling(canvas, renderer);
 
 		this.renderers.add(renderer);
@@ -97,6 +106,14 @@ export default class GraphManager {
 		return renderer;
 	}
 
+	public setAnimationPolicy(policy: AnimationPolicy) {
+		trace(policy);
+		this.animationPolicy = policy;
+		this.renderers.forEach((renderer) => {
+			renderer.setAnimationPolicy(policy);
+		});
+	}
+
 	public removeGraph<
 		TGraphRenderer extends UnknownGraphRenderer & IGraphRenderer,
 	>(renderer: TGraphRenderer) {
@@ -119,6 +136,8 @@ export default class GraphManager {
 			x: mouseX * devicePixelRatio,
 			y: mouseY * devicePixelRatio,
 			clickingState: "Holding",
+			additive: isAdditivePointerEvent(e),
+			touches: [],
 		};
 		this.pointerStart = { touchId };
 		renderer.update(this.timestamp);
@@ -137,6 +156,8 @@ export default class GraphManager {
 			x: mouseX * devicePixelRatio,
 			y: mouseY * devicePixelRatio,
 			clickingState: this.pointerStart ? "Holding" : "None",
+			additive: isAdditivePointerEvent(e),
+			touches: [],
 		};
 
 		renderer.update(this.timestamp);
@@ -148,6 +169,7 @@ export default class GraphManager {
 		renderer: IGraphRenderer & UnknownGraphRenderer,
 	) {
 		renderer.pointer.clickingState = "JustReleased";
+		renderer.pointer.additive = isAdditivePointerEvent(e);
 		renderer.update(this.timestamp);
 		renderer.onPointerUp(e.pointerType);
 		renderer.pointer.clickingState = "None";
@@ -155,11 +177,51 @@ export default class GraphManager {
 	}
 
 	private handleCancelInput(renderer: IGraphRenderer & UnknownGraphRenderer) {
-		renderer.pointer = { x: -1, y: -1, clickingState: "None" };
+		renderer.pointer = {
+			x: -1,
+			y: -1,
+			clickingState: "None",
+			additive: false,
+			touches: [],
+		};
 		renderer.update(this.timestamp);
 		renderer.onPointerLeave();
 	}
 
+	// Touches are handed to the gesture recognizer as they are, it decides what is a tap, a long-press, a
+	// pan or a pinch
+	private handleTouchInput(
+		e: PointerEvent,
+		canvas: HTMLCanvasElement,
+		renderer: IGraphRenderer & UnknownGraphRenderer,
+		lifted: boolean,
+	) {
+		const rect = canvas.getBoundingClientRect();
+		const x = (e.clientX - rect.left) * devicePixelRatio;
+		const y = (e.clientY - rect.top) * devicePixelRatio;
+		const touches = renderer.pointer.touches.filter(
+			(touch) => touch.id !== e.pointerId,
+		);
+		if (!lifted) {
+			const index = renderer.pointer.touches.findIndex(
+				(touch) => touch.id === e.pointerId,
+			);
+			touches.splice(index === -1 ? touches.length : index, 0, {
+				id: e.pointerId,
+				x,
+				y,
+			});
+		}
+		renderer.pointer = {
+			x,
+			y,
+			clickingState: "None",
+			additive: false,
+			touches,
+		};
+		renderer.update(this.timestamp);
+	}
+
 	private addInputHandling(
 		canvas: HTMLCanvasElement,
 		renderer: IGraphRenderer & UnknownGraphRenderer,
@@ -170,6 +232,11 @@ export default class GraphManager {
 				if (e.button !== 0) {
 					return;
 				}
+				if (e.pointerType === "touch") {
+					this.handleTouchInput(e, canvas, renderer, false);
+					renderer.onPointerDown(e.pointerType);
+					return;
+				}
 				this.handleClick(e, canvas, renderer);
 			},
 			canvas,
@@ -178,6 +245,11 @@ export default class GraphManager {
 		renderer.addInputEventHandler({
 			type: "pointermove",
 			handler: (e) => {
+				if (e.pointerType === "touch") {
+					this.handleTouchInput(e, canvas, renderer, false);
+					renderer.onPointerMove(e.pointerType);
+					return;
+				}
 				this.handleMoveInput(e, canvas, renderer);
 			},
 			canvas,
@@ -189,6 +261,11 @@ export default class GraphManager {
 				if (e.button !== 0) {
 					return;
 				}
+				if (e.pointerType === "touch") {
+					this.handleTouchInput(e, canvas, renderer, true);
+					renderer.onPointerUp(e.pointerType);
+					return;
+				}
 				this.handleEndInput(e, renderer);
 			},
 			canvas,
@@ -200,6 +277,13 @@ export default class GraphManager {
 				if (e.button > 1) {
 					return;
 				}
+				// A lifted finger goes out too, the others are still down
+				if (e.pointerType === "touch") {
+					this.handleTouchInput(e, canvas, renderer, true);
+					if (renderer.pointer.touches.length > 0) {
+						return;
+					}
+				}
 				this.handleCancelInput(renderer);
 			},
 			canvas,
@@ -4,11 +4,13 @@ import {
 	BarChartLayout as WasmBarChartLayout,
 	BarChartOptions as WasmBarChartOptions,
 	BarLayout as WasmBarLayout,
+	BarMode as WasmBarMode,
 	BarOptions as WasmBarOptions,
 	ClickingState as WasmClickingState,
 	BarChartDataPoint as WasmDataPoint,
 	Positioning as WasmPositioning,
 	ValueAxisLayout as WasmValueAxisLayout,
+	XAxisLayout as WasmXAxisLayout,
 } from "../../graph-renderer/pkg/graph_renderer.js";
 
 import {
@@ -22,37 +24,78 @@ import {
 	type PointerType,
 	type WasmGraphRendererInterop,
 } from "../graphRenderer.js";
-import { colorToWasmColor } from "../wasmUtils.js";
-import BarChartGL from "./barChartGL.js";
 import {
-	clamp,
-	roundToNearestMultiple,
-	type DeepRequired,
-} from "../../utils.js";
+	colorToWasmColor,
+	dataKeyToWasmDataKey,
+	focusRingOptionsToWasmFocusRingOptions,
+	focusRingOptionsWithDefaults,
+	gestureOptionsToWasmGestureOptions,
+	gestureOptionsWithDefaults,
+	selectionOptionsToWasmSelectionOptions,
+	selectionOptionsWithDefaults,
+	tooltipOptionsToWasmTooltipOptions,
+	tooltipOptionsWithDefaults,
+} from "../wasmUtils.js";
+import BarChartGL from "./barChartGL.js";
+import { clamp, type DeepRequired } from "../../utils.js";
 import type {
+	AnimationOptions,
+	AnimationPolicy,
+	FocusRingOptions,
+	GestureOptions,
+	SelectionOptions,
+	Tooltip,
+	TooltipOptions,
+	DataKey,
 	OnValueAxisLayout,
+	OnXAxisTicksLayout,
 	PointerCallback,
 	ValueAxisOptions,
+	XAxisOptions,
 } from "../shared/types.js";
+import {
+	scaleLineValue,
+	valueAxisOptionsToWasmValueAxisDomain,
+} from "../shared/scale.js";
+import {
+	animationOptionsToWasmAnimationOptions,
+	animationPolicyToWasmAnimationPolicy,
+	defaultAnimationPolicy,
+	animationOptionsWithDefaults,
+} from "../shared/animation.js";
 
 export type BarChartData<TLabel> = DataPoint<TLabel>[] & GraphData;
-type InternalBarChartData<TLabel> = Required<DataPoint<TLabel>>[] & GraphData;
+type InternalBarChartData<TLabel> = DataPoint<TLabel>[] & GraphData;
 
 interface BarOptions {
+	mode?: "grouped" | "stacked";
 	gap?: number;
+	seriesGap?: number;
 	hoverScale?: number;
 	color?: Color;
+	seriesColors?: Color[];
+	// Shown in tooltips
+	seriesNames?: string[];
 	hoverColor?: Color;
 	selectedColor?: Color;
 	cornerRadius?: number;
 	minWidth?: number;
 	minHeight?: number;
 	maxBars?: number;
+	// Categories kept when streaming with pushBar, 0 keeps every category up to maxBars
+	window?: number;
 }
 
 export interface BarChartOptions extends GraphRendererOptions {
 	barOptions?: BarOptions;
 	valueAxis?: ValueAxisOptions;
+	xAxis?: XAxisOptions;
+	focusRing?: FocusRingOptions;
+	selection?: SelectionOptions;
+	tooltip?: TooltipOptions;
+	gestures?: GestureOptions;
+	animations?: AnimationOptions;
+	animationPolicy?: AnimationPolicy;
 }
 
 class WasmBarChartInterop implements WasmGraphRendererInterop<WasmBarChart> {
@@ -85,7 +128,11 @@ class WasmBarChartInterop implements WasmGraphRendererInterop<WasmBarChart> {
 					options.positioning.right,
 				),
 				new WasmBarLayout(
+					options.barOptions.mode === "stacked"
+						? WasmBarMode.Stacked
+						: WasmBarMode.Grouped,
 					options.barOptions.gap,
+					options.barOptions.seriesGap,
 					options.barOptions.cornerRadius,
 					options.barOptions.minWidth,
 					options.barOptions.minHeight,
@@ -94,6 +141,12 @@ class WasmBarChartInterop implements WasmGraphRendererInterop<WasmBarChart> {
 					options.valueAxis.width,
 					options.valueAxis.smallestScale,
 					options.valueAxis.minPixelDistance,
+					valueAxisOptionsToWasmValueAxisDomain(options.valueAxis),
+				),
+				new WasmXAxisLayout(
+					options.xAxis.tickLength,
+					options.xAxis.tickThickness,
+					options.xAxis.minTickDistance,
 				),
 			),
 
@@ -102,19 +155,34 @@ class WasmBarChartInterop implements WasmGraphRendererInterop<WasmBarChart> {
 
 				new WasmBarOptions(
 					colorToWasmColor(options.barOptions.color),
+					options.barOptions.seriesColors.map(colorToWasmColor),
+					options.barOptions.seriesNames,
 					colorToWasmColor(options.barOptions.hoverColor),
 					colorToWasmColor(options.barOptions.selectedColor),
 					options.barOptions.hoverScale,
 					options.barOptions.maxBars,
+					options.barOptions.window,
 				),
 
 				colorToWasmColor(options.valueAxis.color),
+				focusRingOptionsToWasmFocusRingOptions(options.focusRing),
+				selectionOptionsToWasmSelectionOptions(options.selection),
+				tooltipOptionsToWasmTooltipOptions(options.tooltip),
+				gestureOptionsToWasmGestureOptions(options.gestures),
+				animationOptionsToWasmAnimationOptions(options.animations),
+				animationPolicyToWasmAnimationPolicy(options.animationPolicy),
 			),
 		);
 	}
 	public updateData(data: WasmDataPoint[], timestamp: number) {
 		this.wasmGraph.update_data(data, timestamp);
 	}
+	pushBar(dataPoint: WasmDataPoint, timestamp: number) {
+		return this.wasmGraph.push_bar(dataPoint, timestamp);
+	}
+	shiftBars(count: number) {
+		this.wasmGraph.shift_bars(count);
+	}
 
 	resize(width: number, height: number) {
 		this.wasmGraph.resize(width, height);
@@ -141,12 +209,45 @@ class WasmBarChartInterop implements WasmGraphRendererInterop<WasmBarChart> {
 			pointer.x,
 			pointer.y,
 			clickingState,
+			pointer.additive,
+			new Float32Array(
+				pointer.touches.flatMap((touch) => [touch.id, touch.x, touch.y]),
+			),
 		);
 	}
 
+	selectBar(index: number, additive: boolean, timestamp: number) {
+		this.wasmGraph.select_bar(index, additive, timestamp);
+	}
+	clearSelection(timestamp: number) {
+		this.wasmGraph.clear_selection(timestamp);
+	}
+	focusNext() {
+		this.wasmGraph.focus_next();
+	}
+	focusPrevious() {
+		this.wasmGraph.focus_previous();
+	}
+	clearFocus() {
+		this.wasmGraph.clear_focus();
+	}
+	getFocusedBarCategory() {
+		return this.wasmGraph.get_focused_bar_category();
+	}
+	getFocusedBarSeries() {
+		return this.wasmGraph.get_focused_bar_series();
+	}
+	setAnimationPolicy(policy: AnimationPolicy) {
+		this.wasmGraph.set_animation_policy(
+			animationPolicyToWasmAnimationPolicy(policy),
+		);
+	}
 	getIsAnimating() {
 		return this.wasmGraph.get_is_animating();
 	}
+	getMaxGeneralVertices() {
+		return this.wasmGraph.get_max_general_vertices();
+	}
 
 	getScaleLinesCount() {
 		return this.wasmGraph.get_scale_lines_count();
@@ -157,6 +258,18 @@ class WasmBarChartInterop implements WasmGraphRendererInterop<WasmBarChart> {
 	getScaleLineYAt(i: number) {
 		return this.wasmGraph.get_scale_line_y_at(i);
 	}
+	getXAxisTicksCount() {
+		return this.wasmGraph.get_x_axis_ticks_count();
+	}
+	getXAxisTickXAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_x_at(i);
+	}
+	getXAxisTickYAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_y_at(i);
+	}
+	getXAxisTickValueAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_value_at(i);
+	}
 	getScaleLineXAt(i: number) {
 		return this.wasmGraph.get_scale_line_x_at(i);
 	}
@@ -178,16 +291,81 @@ class WasmBarChartInterop implements WasmGraphRendererInterop<WasmBarChart> {
 	getBarYAt(i: number) {
 		return this.wasmGraph.get_bar_y_at(i);
 	}
-	getSelectedBarIndex() {
-		return this.wasmGraph.get_selected_bar_index();
+	getSeriesCount() {
+		return this.wasmGraph.get_series_count();
+	}
+	getCategoriesLen() {
+		return this.wasmGraph.get_categories_len();
+	}
+	getCategoryXAt(i: number) {
+		return this.wasmGraph.get_category_x_at(i);
+	}
+	getCategoryWidthAt(i: number) {
+		return this.wasmGraph.get_category_width_at(i);
+	}
+	getSelectedBarCategory() {
+		return this.wasmGraph.get_selected_bar_category();
+	}
+	calculateTooltip(width: number, height: number) {
+		return this.wasmGraph.calculate_tooltip(width, height);
+	}
+	getTooltipAnchorX() {
+		return this.wasmGraph.get_tooltip_anchor_x();
+	}
+	getTooltipAnchorY() {
+		return this.wasmGraph.get_tooltip_anchor_y();
+	}
+	getTooltipX() {
+		return this.wasmGraph.get_tooltip_x();
+	}
+	getTooltipY() {
+		return this.wasmGraph.get_tooltip_y();
 	}
-	getHoveredBarIndex() {
-		return this.wasmGraph.get_hovered_bar_index();
+	getTooltipWidth() {
+		return this.wasmGraph.get_tooltip_width();
+	}
+	getTooltipHeight() {
+		return this.wasmGraph.get_tooltip_height();
+	}
+	getTooltipEntriesLen() {
+		return this.wasmGraph.get_tooltip_entries_len();
+	}
+	getTooltipEntrySeriesAt(i: number) {
+		return this.wasmGraph.get_tooltip_entry_series_at(i);
+	}
+	getTooltipEntryNameAt(i: number) {
+		return this.wasmGraph.get_tooltip_entry_name_at(i);
+	}
+	getTooltipEntryValueAt(i: number) {
+		return this.wasmGraph.get_tooltip_entry_value_at(i);
+	}
+	getTooltipEntryCategoryAt(i: number) {
+		return this.wasmGraph.get_tooltip_entry_category_at(i);
+	}
+	getSelectedBarIndices() {
+		return this.wasmGraph.get_selected_bar_indices();
+	}
+	getSelectedBarSeries() {
+		return this.wasmGraph.get_selected_bar_series();
+	}
+	getHoveredBarCategory() {
+		return this.wasmGraph.get_hovered_bar_category();
+	}
+	getHoveredBarSeries() {
+		return this.wasmGraph.get_hovered_bar_series();
 	}
 }
 
 function dataToWasmData<TLabel>(data: BarChartData<TLabel>) {
-	return data.map((item) => new WasmDataPoint(item.value));
+	return data.map(
+		(item) =>
+			new WasmDataPoint(
+				new Float32Array(
+					typeof item.value === "number" ? [item.value] : item.value,
+				),
+				dataKeyToWasmDataKey(item.key),
+			),
+	);
 }
 
 function dataToInternalData<TLabel>(data: BarChartData<TLabel>) {
@@ -198,7 +376,8 @@ function dataToInternalData<TLabel>(data: BarChartData<TLabel>) {
 
 export interface DataPoint<TLabel> {
 	label: TLabel;
-	value: number;
+	value: number | number[];
+	key?: DataKey;
 }
 
 type PositionInfo = {
@@ -208,10 +387,20 @@ type PositionInfo = {
 	height: number;
 } | null;
 
+export type TooltipEntry<TLabel> = {
+	data: DataPoint<TLabel>;
+	series: number;
+	index: number;
+	name: string;
+	// The value as it was given, before scaling
+	value: number;
+};
+
 export type OnSelectionChangeArgs<TLabel> = {
 	data: DataPoint<TLabel>;
 	positionInfo?: PositionInfo;
 	index: number;
+	series: number;
 } | null;
 type OnSelectionChange<TLabel> =
 	| ((args: OnSelectionChangeArgs<TLabel>) => void)
@@ -222,6 +411,7 @@ export type OnHoverArgs<TLabel> = {
 	positionInfo?: PositionInfo;
 	pointer: { x: number; y: number; type: string };
 	index: number;
+	series: number;
 } | null;
 type OnHover<TLabel> = ((args: OnHoverArgs<TLabel>) => void) | undefined;
 
@@ -241,6 +431,7 @@ export type BarChartCallbacks<TLabel> = {
 	onHover?: PointerCallback<OnHover<TLabel>>;
 	onLabelsLayout?: OnLabelsLayout<TLabel>;
 	onValueAxisLayout?: OnValueAxisLayout;
+	onXAxisTicksLayout?: OnXAxisTicksLayout;
 };
 
 type InternalBarChartOptions = InternalGraphRendererOptions &
@@ -261,6 +452,7 @@ export default class BarChart<TLabel>
 	private onSelectionChange: OnSelectionChange<TLabel>;
 	private onSelectionChangeIncludePositionInfo?: boolean;
 	private selectedBarIndex: number | undefined;
+	private selectedBarSeries: number | undefined;
 
 	private onHover: OnHover<TLabel>;
 	private onHoverIncludePositionInfo?: boolean;
@@ -268,6 +460,7 @@ export default class BarChart<TLabel>
 
 	private onLabelsLayout?: OnLabelsLayout<TLabel>;
 	private onValueAxisLayout?: OnValueAxisLayout;
+	private onXAxisTicksLayout?: OnXAxisTicksLayout;
 
 	constructor(
 		canvas: HTMLCanvasElement,
@@ -280,6 +473,7 @@ export default class BarChart<TLabel>
 			onHover,
 			onLabelsLayout,
 			onValueAxisLayout,
+			onXAxisTicksLayout,
 		}: {
 			options?: BarChartOptions;
 		} & BarChartCallbacks<TLabel>,
@@ -310,11 +504,15 @@ export default class BarChart<TLabel>
 							bottom: options.positioning * devicePixelRatio,
 						},
 			barOptions: {
+				mode: options.barOptions?.mode ?? "grouped",
 				gap: (options.barOptions?.gap ?? 10) * devicePixelRatio,
+				seriesGap: (options.barOptions?.seriesGap ?? 2) * devicePixelRatio,
 				cornerRadius:
 					(options.barOptions?.cornerRadius ?? 10) * devicePixelRatio,
 				hoverScale: options.barOptions?.hoverScale ?? 1.1,
 				color: options.barOptions?.color ?? { r: 255, g: 255, b: 255 },
+				seriesColors: options.barOptions?.seriesColors ?? [],
+				seriesNames: options.barOptions?.seriesNames ?? [],
 				hoverColor: options.barOptions?.hoverColor ?? {
 					r: 150,
 					g: 150,
@@ -329,6 +527,7 @@ export default class BarChart<TLabel>
 				minWidth: (options.barOptions?.minWidth ?? 1) * devicePixelRatio,
 				minHeight: (options.barOptions?.minHeight ?? 1) * devicePixelRatio,
 				maxBars: options.barOptions?.maxBars ?? 1000,
+				window: options.barOptions?.window ?? 0,
 			},
 			valueAxis: {
 				width: (options.valueAxis?.width ?? 0) * devicePixelRatio,
@@ -337,7 +536,34 @@ export default class BarChart<TLabel>
 					(options.valueAxis?.smallestScale ?? 1) * devicePixelRatio,
 				minPixelDistance:
 					(options.valueAxis?.minPixelDistance ?? 20) * devicePixelRatio,
+				scale: options.valueAxis?.scale ?? "linear",
+				ticks: options.valueAxis?.ticks ?? 10,
+				min: options.valueAxis?.min ?? null,
+				max: options.valueAxis?.max ?? null,
+				niceBounds: options.valueAxis?.niceBounds ?? false,
+			},
+			xAxis: {
+				tickLength: (options.xAxis?.tickLength ?? 5) * devicePixelRatio,
+				tickThickness: (options.xAxis?.tickThickness ?? 2) * devicePixelRatio,
+				minTickDistance:
+					(options.xAxis?.minTickDistance ?? 50) * devicePixelRatio,
 			},
+			focusRing: focusRingOptionsWithDefaults(
+				options.focusRing,
+				devicePixelRatio,
+			),
+			selection: selectionOptionsWithDefaults(options.selection),
+			tooltip: tooltipOptionsWithDefaults(options.tooltip, devicePixelRatio),
+			gestures: gestureOptionsWithDefaults(options.gestures, devicePixelRatio),
+			animations: animationOptionsWithDefaults(options.animations, {
+				enter: { duration: 500, stagger: 800 },
+				hover: { duration: 200 },
+				select: { duration: 200 },
+				click: { duration: 200 },
+				exit: { duration: 500 },
+				viewport: { duration: 300 },
+			}),
+			animationPolicy: options.animationPolicy ?? defaultAnimationPolicy(),
 		};
 
 		super(
@@ -360,9 +586,11 @@ export default class BarChart<TLabel>
 		this.onHoverIncludePositionInfo = onHover?.includePositionInfo;
 		this.onLabelsLayout = onLabelsLayout;
 		this.onValueAxisLayout = onValueAxisLayout;
+		this.onXAxisTicksLayout = onXAxisTicksLayout;
 	}
 
-	public getPositionInfoForBarAt(index: number) {
+	public getPositionInfoForBarAt(category: number, series = 0) {
+		const index = category * this.wasmGraphRenderer.getSeriesCount() + series;
 		return {
 			x: this.wasmGraphRenderer.getBarXAt(index) / devicePixelRatio,
 			y: this.wasmGraphRenderer.getBarYAt(index) / devicePixelRatio,
@@ -371,15 +599,72 @@ export default class BarChart<TLabel>
 		};
 	}
 
+	// Where to put a tooltip of the given size (in CSS pixels) for what the pointer is over, clamped to the
+	// plot area, along with the original values it should show
+	public getTooltip(
+		width: number,
+		height: number,
+	): Tooltip<TooltipEntry<TLabel>> | null {
+		const renderer = this.wasmGraphRenderer;
+		if (
+			!renderer.calculateTooltip(
+				Math.round(width * devicePixelRatio),
+				Math.round(height * devicePixelRatio),
+			)
+		) {
+			return null;
+		}
+
+		const entries: TooltipEntry<TLabel>[] = [];
+		for (let i = 0; i < renderer.getTooltipEntriesLen(); i++) {
+			const series = renderer.getTooltipEntrySeriesAt(i);
+			const index = renderer.getTooltipEntryCategoryAt(i);
+			if (series == null || index == null) {
+				continue;
+			}
+			const data = this.data[index];
+			if (data == null) {
+				continue;
+			}
+
+			entries.push({
+				series,
+				index,
+				name: renderer.getTooltipEntryNameAt(i) ?? "",
+				value: renderer.getTooltipEntryValueAt(i) ?? 0,
+				data,
+			});
+		}
+
+		return {
+			anchor: {
+				x: (renderer.getTooltipAnchorX() ?? 0) / devicePixelRatio,
+				y: (renderer.getTooltipAnchorY() ?? 0) / devicePixelRatio,
+			},
+			x: (renderer.getTooltipX() ?? 0) / devicePixelRatio,
+			y: (renderer.getTooltipY() ?? 0) / devicePixelRatio,
+			width: (renderer.getTooltipWidth() ?? 0) / devicePixelRatio,
+			height: (renderer.getTooltipHeight() ?? 0) / devicePixelRatio,
+			entries,
+		};
+	}
+
 	public updateData(data: BarChartData<TLabel>, timestamp: number) {
 		trace(data);
 		if (data === this.data) {
 			return;
 		}
 
-		if (data.length > this.options.barOptions.maxBars) {
+		// Every category has a bar for every series
+		const seriesCount = Math.max(
+			1,
+			...data.map((item) =>
+				typeof item.value === "number" ? 1 : item.value.length,
+			),
+		);
+		if (data.length * seriesCount > this.options.barOptions.maxBars) {
 			logWarn(
-				`Cannot update data to length greater than maxBars, {${data.length}}, {${this.options.barOptions.maxBars}}`,
+				`Cannot update data to more bars than maxBars, {${data.length * seriesCount}}, {${this.options.barOptions.maxBars}}`,
 			);
 			return;
 		}
@@ -393,6 +678,7 @@ export default class BarChart<TLabel>
 				if (
 					!oldDataPoint ||
 					newDataPoint.value !== oldDataPoint.value ||
+					newDataPoint.key !== oldDataPoint.key ||
 					newDataPoint.label !== oldDataPoint.label
 				) {
 					hasDifference = true;
@@ -411,6 +697,39 @@ export default class BarChart<TLabel>
 		super.updateData(data, timestamp);
 	}
 
+	// Streams a category onto the end, dropping the oldest once the window is full
+	public pushBar(dataPoint: DataPoint<TLabel>, timestamp: number) {
+		trace(dataPoint);
+		const pushed = this.wasmGraphRenderer.pushBar(
+			dataToWasmData([dataPoint])[0],
+			timestamp,
+		);
+		if (!pushed) {
+			return;
+		}
+
+		this.data.push({ ...dataPoint });
+		this.data.splice(
+			0,
+			this.data.length - this.wasmGraphRenderer.getCategoriesLen(),
+		);
+		this.wasmGraphRenderer.update(timestamp, this.pointer);
+
+		super.updateData(this.data, timestamp);
+	}
+
+	public shiftBars(count: number, timestamp: number) {
+		if (count <= 0) {
+			return;
+		}
+
+		this.data.splice(0, count);
+		this.wasmGraphRenderer.shiftBars(count);
+		this.wasmGraphRenderer.update(timestamp, this.pointer);
+
+		super.updateData(this.data, timestamp);
+	}
+
 	public init(memory: WebAssembly.Memory, startTimestamp: number): void {
 		const wasmGraphRenderer = new WasmBarChartInterop({
 			data: dataToWasmData(this.data),
@@ -421,6 +740,9 @@ export default class BarChart<TLabel>
 		});
 
 		super._init(memory, wasmGraphRenderer);
+		this.glRenderer.setMaxGeneralVertices(
+			wasmGraphRenderer.getMaxGeneralVertices(),
+		);
 	}
 
 	public dispose() {
@@ -437,8 +759,9 @@ export default class BarChart<TLabel>
 
 		for (let i = 0; i < scaleLinesLen; i++) {
 			valueAxisLayout.push({
-				value: roundToNearestMultiple(
+				value: scaleLineValue(
 					this.wasmGraphRenderer.getScaleLineValueAt(i),
+					this.options.valueAxis.scale,
 					this.options.valueAxis.smallestScale,
 				),
 				x: 0,
@@ -454,10 +777,10 @@ export default class BarChart<TLabel>
 
 		const labelsLayout = [];
 
-		const barsLen = this.wasmGraphRenderer.getBarsLen();
-		for (let i = 0; i < barsLen; i++) {
-			const barX = this.wasmGraphRenderer.getBarXAt(i);
-			const barWidth = this.wasmGraphRenderer.getBarWidthAt(i);
+		const categoriesLen = this.wasmGraphRenderer.getCategoriesLen();
+		for (let i = 0; i < categoriesLen; i++) {
+			const barX = this.wasmGraphRenderer.getCategoryXAt(i);
+			const barWidth = this.wasmGraphRenderer.getCategoryWidthAt(i);
 			let x = barX - this.options.barOptions.gap * 0.5;
 			const diff = x - this.options.valueAxis.width;
 			let width =
@@ -477,6 +800,17 @@ export default class BarChart<TLabel>
 		}
 
 		this.options.positioning.bottom > 0 && this.onLabelsLayout?.(labelsLayout);
+
+		const xAxisTicksCount = this.wasmGraphRenderer.getXAxisTicksCount();
+		const xAxisTicksLayout = [];
+		for (let i = 0; i < xAxisTicksCount; i++) {
+			xAxisTicksLayout.push({
+				value: this.wasmGraphRenderer.getXAxisTickValueAt(i),
+				x: this.wasmGraphRenderer.getXAxisTickXAt(i) / devicePixelRatio,
+				y: this.wasmGraphRenderer.getXAxisTickYAt(i) / devicePixelRatio,
+			});
+		}
+		this.onXAxisTicksLayout?.(xAxisTicksLayout);
 	}
 
 	public onPointerDown(pointerType: string) {
@@ -486,12 +820,89 @@ export default class BarChart<TLabel>
 	}
 
 	public onPointerUp(_pointerType: string) {
-		const selectedBarIndex = this.wasmGraphRenderer.getSelectedBarIndex();
+		this.handleSelectionChange();
+	}
 
-		if (selectedBarIndex === this.selectedBarIndex || !this.onSelectionChange) {
+	// Selects the bar like clicking it would, for keyboard users and controls outside the chart.
+	// With additive set it joins the current selection instead of replacing it
+	public selectBar(
+		index: number,
+		series: number,
+		timestamp: number,
+		additive = false,
+	) {
+		this.wasmGraphRenderer.selectBar(
+			index * this.wasmGraphRenderer.getSeriesCount() + series,
+			additive,
+			timestamp,
+		);
+		this.handleSelectionChange();
+	}
+
+	// Every selected bar, in the order they were selected
+	public getSelectedBars() {
+		const seriesCount = this.wasmGraphRenderer.getSeriesCount();
+		const indices = this.wasmGraphRenderer.getSelectedBarIndices();
+		return Array.from(
+			new Float32Array(this.wasmMemory.buffer, indices.pointer, indices.size),
+			(flatIndex) => ({
+				index: Math.floor(flatIndex / seriesCount),
+				series: flatIndex % seriesCount,
+			}),
+		);
+	}
+
+	public selectFocusedBar(timestamp: number) {
+		const index = this.wasmGraphRenderer.getFocusedBarCategory();
+		const series = this.wasmGraphRenderer.getFocusedBarSeries();
+		if (index == null || series == null) {
+			return;
+		}
+
+		this.selectBar(index, series, timestamp);
+	}
+
+	public clearSelection(timestamp: number) {
+		this.wasmGraphRenderer.clearSelection(timestamp);
+		this.handleSelectionChange();
+	}
+
+	// Moves the focus ring, which is kept apart from hover, through the bars in order
+	public focusNext() {
+		this.wasmGraphRenderer.focusNext();
+	}
+
+	public focusPrevious() {
+		this.wasmGraphRenderer.focusPrevious();
+	}
+
+	public clearFocus() {
+		this.wasmGraphRenderer.clearFocus();
+	}
+
+	public getFocusedBar() {
+		const index = this.wasmGraphRenderer.getFocusedBarCategory();
+		const series = this.wasmGraphRenderer.getFocusedBarSeries();
+		if (index == null || series == null) {
+			return null;
+		}
+
+		return { index, series };
+	}
+
+	private handleSelectionChange() {
+		const selectedBarIndex = this.wasmGraphRenderer.getSelectedBarCategory();
+		const selectedBarSeries = this.wasmGraphRenderer.getSelectedBarSeries() ?? 0;
+
+		if (
+			(selectedBarIndex === this.selectedBarIndex &&
+				selectedBarSeries === this.selectedBarSeries) ||
+			!this.onSelectionChange
+		) {
 			return;
 		}
 		this.selectedBarIndex = selectedBarIndex;
+		this.selectedBarSeries = selectedBarSeries;
 
 		if (selectedBarIndex == null || selectedBarIndex >= this.data.length) {
 			this.onSelectionChange(null);
@@ -501,14 +912,16 @@ export default class BarChart<TLabel>
 		this.onSelectionChange({
 			data: this.data[selectedBarIndex],
 			positionInfo: this.onSelectionChangeIncludePositionInfo
-				? this.getPositionInfoForBarAt(selectedBarIndex)
+				? this.getPositionInfoForBarAt(selectedBarIndex, selectedBarSeries)
 				: null,
 			index: selectedBarIndex,
+			series: selectedBarSeries,
 		});
 	}
 
 	public onPointerMove(pointerType: string) {
-		const hoveredBarIndex = this.wasmGraphRenderer.getHoveredBarIndex();
+		const hoveredBarIndex = this.wasmGraphRenderer.getHoveredBarCategory();
+		const hoveredBarSeries = this.wasmGraphRenderer.getHoveredBarSeries() ?? 0;
 		if (!this.onHover) {
 			return;
 		}
@@ -528,9 +941,10 @@ export default class BarChart<TLabel>
 		this.onHover({
 			data: this.data[hoveredBarIndex],
 			positionInfo: this.onHoverIncludePositionInfo
-				? this.getPositionInfoForBarAt(hoveredBarIndex)
+				? this.getPositionInfoForBarAt(hoveredBarIndex, hoveredBarSeries)
 				: null,
 			index: hoveredBarIndex,
+			series: hoveredBarSeries,
 			pointer: {
 				x: this.pointer.x / devicePixelRatio,
 				y: this.pointer.y / devicePixelRatio,
@@ -547,6 +961,10 @@ export default class BarChart<TLabel>
 	public update(timestamp: number) {
 		trace();
 		const barChartData = this.wasmGraphRenderer.update(timestamp, this.pointer);
+		// A long-press selects while the finger is still down
+		if (this.pointer.touches.length > 0) {
+			this.handleSelectionChange();
+		}
 
 		this.glRenderer.updateGeneralBuffers(
 			barChartData.vertex_array_general,
@@ -567,6 +985,11 @@ export default class BarChart<TLabel>
 		super.render(timestamp);
 	}
 
+	public setAnimationPolicy(policy: AnimationPolicy) {
+		this.options.animationPolicy = policy;
+		this.wasmGraphRenderer.setAnimationPolicy(policy);
+	}
+
 	public isAnimating() {
 		return this.wasmGraphRenderer.getIsAnimating();
 	}
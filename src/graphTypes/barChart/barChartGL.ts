@@ -24,6 +24,7 @@ export default class BarChartGL
 			backgroundColor,
 		});
 
+		// Resized to what the wasm side allows once it exists
 		this.program_general = new GeneralProgram(this.gl);
 		this.program_bars = new BarsProgram(this.gl, { maxBars });
 	}
@@ -33,6 +34,10 @@ export default class BarChartGL
 		this.program_bars.init(memory);
 	}
 
+	public setMaxGeneralVertices(maxVertices: number) {
+		this.program_general.setMaxVertices(maxVertices);
+	}
+
 	public updateGeneralBuffers(
 		positions: WasmFloat32Array,
 		colors: WasmFloat32Array,
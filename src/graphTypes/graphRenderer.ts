@@ -3,6 +3,7 @@ import type { ClickingState } from "../graphManager";
 import type WebGLRenderer from "./webGLRenderer";
 import type { IWebGLRenderer } from "./webGLRenderer";
 import type { DeepRequired } from "../utils";
+import type { AnimationPolicy } from "./shared/types";
 
 export interface Color {
 	r: number;
@@ -50,6 +51,16 @@ export type PointerType = {
 	x: number;
 	y: number;
 	clickingState: ClickingState;
+	// A modifier key is held, so clicks add to the selection
+	additive: boolean;
+	// Fingers down on the canvas, in the order they went down. Gestures read these rather than the pointer
+	touches: TouchType[];
+};
+
+export type TouchType = {
+	id: number;
+	x: number;
+	y: number;
 };
 
 type InputEventType = {
@@ -152,7 +163,13 @@ export class GraphRenderer<
 			this_width: this.width,
 			this_height: this.height,
 		});
-		this.pointer = { x: -1, y: -1, clickingState: "None" };
+		this.pointer = {
+			x: -1,
+			y: -1,
+			clickingState: "None",
+			additive: false,
+			touches: [],
+		};
 		this.options = options;
 	}
 
@@ -229,6 +246,7 @@ export interface IGraphRenderer {
 	handleLayout(): void;
 	updateData(data: GraphData, timestamp: number): void;
 	isAnimating(): boolean;
+	setAnimationPolicy(policy: AnimationPolicy): void;
 	onPointerDown(pointerType: string): void;
 	onPointerUp(pointerType: string): void;
 	onPointerMove(pointerType: string): void;
@@ -3,20 +3,50 @@ import {
 	LineChartLayout as WasmLineChartLayout,
 	ClickingState as WasmClickingState,
 	LineChartDataPoint as WasmDataPoint,
+	LineChartSeries as WasmLineChartSeries,
+	PointStyle as WasmPointStyle,
+	LegendLayout as WasmLegendLayout,
 	LineChart as WasmLineChart,
 	Positioning as WasmPositioning,
 	PointLayout as WasmPointLayout,
 	ValueAxisLayout as WasmValueAxisLayout,
+	XAxisLayout as WasmXAxisLayout,
 	LineChartOptions as WasmLineChartOptions,
+	LineJoin as WasmLineJoin,
+	LineOptions as WasmLineOptions,
+	Interpolation as WasmInterpolation,
+	Downsampling as WasmDownsampling,
+	AreaOptions as WasmAreaOptions,
 	PointOptions as WasmPointOptions,
+	CrosshairOptions as WasmCrosshairOptions,
 } from "../../graph-renderer/pkg/graph_renderer";
 // import type { ClickingState } from "../../graphManager";
-import { clamp, roundToNearestMultiple, type DeepRequired } from "../../utils";
+import { clamp, type DeepRequired } from "../../utils";
 import type {
+	AnimationOptions,
+	AnimationPolicy,
+	FocusRingOptions,
+	GestureOptions,
+	SelectionOptions,
+	Tooltip,
+	TooltipOptions,
+	DataKey,
 	OnValueAxisLayout,
+	OnXAxisTicksLayout,
 	PointerCallback,
 	ValueAxisOptions,
+	XAxisOptions,
 } from "../shared/types";
+import {
+	scaleLineValue,
+	valueAxisOptionsToWasmValueAxisDomain,
+} from "../shared/scale";
+import {
+	animationOptionsToWasmAnimationOptions,
+	animationPolicyToWasmAnimationPolicy,
+	defaultAnimationPolicy,
+	animationOptionsWithDefaults,
+} from "../shared/animation";
 import {
 	GraphRenderer,
 	type Color,
@@ -28,39 +58,197 @@ import {
 	type WasmGraphRendererInterop,
 } from "../graphRenderer";
 import LineChartGL from "./lineChartGL";
-import { colorToWasmColor } from "../wasmUtils";
+import {
+	colorToWasmColor,
+	dataKeyToWasmDataKey,
+	focusRingOptionsToWasmFocusRingOptions,
+	focusRingOptionsWithDefaults,
+	gestureOptionsToWasmGestureOptions,
+	gestureOptionsWithDefaults,
+	selectionOptionsToWasmSelectionOptions,
+	selectionOptionsWithDefaults,
+	tooltipOptionsToWasmTooltipOptions,
+	tooltipOptionsWithDefaults,
+} from "../wasmUtils";
+
+function pointStyleToWasmPointStyle(pointStyle: PointStyle) {
+	switch (pointStyle) {
+		case "square":
+			return WasmPointStyle.Square;
+		case "diamond":
+			return WasmPointStyle.Diamond;
+		case "hidden":
+			return WasmPointStyle.Hidden;
+	}
+}
 
-function dataToWasmData<TLabel>(data: LineChartData<TLabel>) {
-	return data.map((item) => new WasmDataPoint(item.x, item.y));
+function dataToWasmData<TLabel>(data: InternalLineChartData<TLabel>) {
+	return data.map(
+		(series) =>
+			new WasmLineChartSeries(
+				series.name,
+				colorToWasmColor(series.color),
+				pointStyleToWasmPointStyle(series.pointStyle),
+				series.visible,
+				series.data.map(
+					(item) =>
+						new WasmDataPoint(item.x, item.y, dataKeyToWasmDataKey(item.key)),
+				),
+			),
+	);
 }
 
-function dataToInternalData<TLabel>(data: LineChartData<TLabel>) {
-	return data.map((data) => {
-		return { ...data };
+function isSeriesData<TLabel>(
+	data: LineChartData<TLabel>,
+): data is LineChartSeries<TLabel>[] & GraphData {
+	return data.length > 0 && "data" in data[0];
+}
+
+// A plain list of points is treated as a single unnamed series
+function dataToInternalData<TLabel>(
+	data: LineChartData<TLabel>,
+	defaultColor: Color,
+): InternalLineChartData<TLabel> {
+	const series: LineChartSeries<TLabel>[] = isSeriesData(data)
+		? data
+		: [{ name: "", data: data as DataPoint<TLabel>[] }];
+
+	return series.map((series) => {
+		const color = series.color ?? defaultColor;
+		return {
+			name: series.name,
+			color: { ...color, a: color.a ?? 255 },
+			pointStyle: series.pointStyle ?? "square",
+			visible: series.visible ?? true,
+			data: series.data.map((data) => {
+				return { ...data };
+			}),
+		};
 	});
 }
 
+function pointsCount<TLabel>(data: InternalLineChartData<TLabel>) {
+	return data.reduce((count, series) => count + series.data.length, 0);
+}
+
 export interface DataPoint<TLabel> {
 	label: TLabel;
 	x: number;
 	y: number;
+	key?: DataKey;
+}
+
+type PointStyle = "square" | "diamond" | "hidden";
+
+export interface LineChartSeries<TLabel> {
+	name: string;
+	color?: Color;
+	pointStyle?: PointStyle;
+	visible?: boolean;
+	data: DataPoint<TLabel>[];
 }
 
-export type LineChartData<TLabel> = DataPoint<TLabel>[] & GraphData;
-type InternalLineChartData<TLabel> = Required<DataPoint<TLabel>>[] & GraphData;
+export type LineChartData<TLabel> = (
+	| DataPoint<TLabel>[]
+	| LineChartSeries<TLabel>[]
+) &
+	GraphData;
+type InternalLineChartData<TLabel> = Required<LineChartSeries<TLabel>>[] &
+	GraphData;
+
+type Interpolation =
+	| "linear"
+	| "monotoneCubic"
+	| "catmullRom"
+	| "stepBefore"
+	| "stepAfter"
+	| "stepMiddle";
+
+type Downsampling = "none" | "lttb" | "minMax";
+
+interface LineOptions {
+	color?: Color;
+	width?: number;
+	join?: "miter" | "round";
+	interpolation?: Interpolation;
+	curveSegments?: number;
+	downsampling?: Downsampling;
+}
+
+function interpolationToWasmInterpolation(interpolation: Interpolation) {
+	switch (interpolation) {
+		case "linear":
+			return WasmInterpolation.Linear;
+		case "monotoneCubic":
+			return WasmInterpolation.MonotoneCubic;
+		case "catmullRom":
+			return WasmInterpolation.CatmullRom;
+		case "stepBefore":
+			return WasmInterpolation.StepBefore;
+		case "stepAfter":
+			return WasmInterpolation.StepAfter;
+		case "stepMiddle":
+			return WasmInterpolation.StepMiddle;
+	}
+}
+
+function downsamplingToWasmDownsampling(downsampling: Downsampling) {
+	switch (downsampling) {
+		case "none":
+			return WasmDownsampling.None;
+		case "lttb":
+			return WasmDownsampling.Lttb;
+		case "minMax":
+			return WasmDownsampling.MinMax;
+	}
+}
+
+interface AreaOptions {
+	enabled?: boolean;
+	opacity?: number;
+	gradient?: boolean;
+}
 
 interface PointOptions {
 	hoverScale?: number;
-	color?: Color;
 	hoverColor?: Color;
 	selectedColor?: Color;
 	radius?: number;
 	maxPoints?: number;
+	// The most series updateData accepts
+	maxSeries?: number;
+	// Points kept per series when streaming with appendPoints, 0 keeps every point up to maxPoints
+	window?: number;
+}
+
+// Guide lines snapped to the data X closest to the pointer, with a marker on each series
+interface CrosshairOptions {
+	enabled?: boolean;
+	color?: Color;
+	thickness?: number;
+	markerSize?: number;
+}
+
+interface LegendOptions {
+	swatchSize?: number;
+	itemWidth?: number;
+	gap?: number;
 }
 
 export interface LineChartOptions extends GraphRendererOptions {
+	lineOptions?: LineOptions;
+	areaOptions?: AreaOptions;
 	pointOptions?: PointOptions;
 	valueAxis?: ValueAxisOptions;
+	xAxis?: XAxisOptions;
+	legend?: LegendOptions;
+	focusRing?: FocusRingOptions;
+	crosshair?: CrosshairOptions;
+	selection?: SelectionOptions;
+	tooltip?: TooltipOptions;
+	gestures?: GestureOptions;
+	animations?: AnimationOptions;
+	animationPolicy?: AnimationPolicy;
 }
 type InternalLineChartOptions = InternalGraphRendererOptions &
 	DeepRequired<LineChartOptions>;
@@ -70,9 +258,19 @@ type PositionInfo = {
 	y: number;
 } | null;
 
+export type TooltipEntry<TLabel> = {
+	data: DataPoint<TLabel>;
+	series: number;
+	index: number;
+	name: string;
+	// The value as it was given, before scaling
+	value: number;
+};
+
 export type OnSelectionChangeArgs<TLabel> = {
 	data: DataPoint<TLabel>;
 	positionInfo?: PositionInfo;
+	series: number;
 	index: number;
 } | null;
 type OnSelectionChange<TLabel> =
@@ -83,6 +281,7 @@ export type OnHoverArgs<TLabel> = {
 	data: DataPoint<TLabel>;
 	positionInfo?: PositionInfo;
 	pointer: { x: number; y: number; type: string };
+	series: number;
 	index: number;
 } | null;
 type OnHover<TLabel> = ((args: OnHoverArgs<TLabel>) => void) | undefined;
@@ -98,11 +297,25 @@ export type OnXAxisLayoutParams<TLabel> = {
 	centerPoint: number;
 }[];
 
+type OnLegendLayout = (args: OnLegendLayoutParams) => void;
+
+export type OnLegendLayoutParams = {
+	name: string;
+	color: Color;
+	visible: boolean;
+	x: number;
+	y: number;
+	swatchSize: number;
+	width: number;
+}[];
+
 export type LineChartCallbacks<TLabel> = {
 	onSelectionChange?: PointerCallback<OnSelectionChange<TLabel>>;
 	onHover?: PointerCallback<OnHover<TLabel>>;
 	onXAxisLayout?: OnXAxisLayout<TLabel>;
 	onValueAxisLayout?: OnValueAxisLayout;
+	onXAxisTicksLayout?: OnXAxisTicksLayout;
+	onLegendLayout?: OnLegendLayout;
 };
 
 class WasmLineChartInterop implements WasmGraphRendererInterop<WasmLineChart> {
@@ -114,7 +327,7 @@ class WasmLineChartInterop implements WasmGraphRendererInterop<WasmLineChart> {
 		height,
 		options,
 	}: {
-		data: WasmDataPoint[];
+		data: WasmLineChartSeries[];
 		startTimestamp: number;
 		width: number;
 		height: number;
@@ -137,32 +350,106 @@ class WasmLineChartInterop implements WasmGraphRendererInterop<WasmLineChart> {
 					options.valueAxis.width,
 					options.valueAxis.smallestScale,
 					options.valueAxis.minPixelDistance,
+					valueAxisOptionsToWasmValueAxisDomain(options.valueAxis),
+				),
+				new WasmXAxisLayout(
+					options.xAxis.tickLength,
+					options.xAxis.tickThickness,
+					options.xAxis.minTickDistance,
+				),
+				new WasmLegendLayout(
+					options.legend.swatchSize,
+					options.legend.itemWidth,
+					options.legend.gap,
 				),
 			),
 
 			new WasmLineChartOptions(
 				colorToWasmColor(options.backgroundColor),
 
+				new WasmLineOptions(
+					options.lineOptions.width,
+					options.lineOptions.join === "round"
+						? WasmLineJoin.Round
+						: WasmLineJoin.Miter,
+					interpolationToWasmInterpolation(options.lineOptions.interpolation),
+					options.lineOptions.curveSegments,
+					downsamplingToWasmDownsampling(options.lineOptions.downsampling),
+				),
+
+				new WasmAreaOptions(
+					options.areaOptions.enabled,
+					options.areaOptions.opacity,
+					options.areaOptions.gradient,
+				),
+
 				new WasmPointOptions(
-					colorToWasmColor(options.pointOptions.color),
 					colorToWasmColor(options.pointOptions.hoverColor),
 					colorToWasmColor(options.pointOptions.selectedColor),
 					options.pointOptions.hoverScale,
 					options.pointOptions.maxPoints,
+					options.pointOptions.maxSeries,
+					options.pointOptions.window,
 				),
 
 				colorToWasmColor(options.valueAxis.color),
+				focusRingOptionsToWasmFocusRingOptions(options.focusRing),
+				new WasmCrosshairOptions(
+					options.crosshair.enabled,
+					colorToWasmColor(options.crosshair.color),
+					options.crosshair.thickness,
+					options.crosshair.markerSize,
+				),
+				selectionOptionsToWasmSelectionOptions(options.selection),
+				tooltipOptionsToWasmTooltipOptions(options.tooltip),
+				gestureOptionsToWasmGestureOptions(options.gestures),
+				animationOptionsToWasmAnimationOptions(options.animations),
+				animationPolicyToWasmAnimationPolicy(options.animationPolicy),
 			),
 		);
 	}
-	getPointsLen() {
-		return this.wasmGraph.get_points_len();
+	getSeriesCount() {
+		return this.wasmGraph.get_series_count();
+	}
+	getSeriesNameAt(index: number) {
+		return this.wasmGraph.get_series_name_at(index);
+	}
+	getSeriesColorAt(index: number) {
+		const color = this.wasmGraph.get_series_color_at(index);
+		return { r: color.r, g: color.g, b: color.b, a: color.a };
+	}
+	getSeriesVisibleAt(index: number) {
+		return this.wasmGraph.get_series_visible_at(index);
 	}
-	getPointXAt(index: number) {
-		return this.wasmGraph.get_point_x_at(index);
+	setSeriesVisible(index: number, visible: boolean, timestamp: number) {
+		this.wasmGraph.set_series_visible(index, visible, timestamp);
 	}
-	getPointYAt(index: number) {
-		return this.wasmGraph.get_point_y_at(index);
+	appendPoints(series: number, points: WasmDataPoint[], timestamp: number) {
+		return this.wasmGraph.append_points(series, points, timestamp);
+	}
+	shiftPoints(series: number, count: number) {
+		this.wasmGraph.shift_points(series, count);
+	}
+	getLegendSwatchXAt(index: number) {
+		return this.wasmGraph.get_legend_swatch_x_at(index);
+	}
+	getLegendSwatchYAt(index: number) {
+		return this.wasmGraph.get_legend_swatch_y_at(index);
+	}
+	getLegendSwatchSize() {
+		return this.wasmGraph.get_legend_swatch_size();
+	}
+	getLegendItemWidth() {
+		return this.wasmGraph.get_legend_item_width();
+	}
+	getPointsLen(series: number) {
+		return this.wasmGraph.get_points_len(series);
+	}
+	getPointXAt(series: number, index: number) {
+		return this.wasmGraph.get_point_x_at(series, index);
+	}
+	getPointYAt(series: number, index: number) {
+		return this.wasmGraph.get_point_y_at(series, index);
 	}
 	getScaleLinesCount() {
 		return this.wasmGraph.get_scale_lines_count();
@@ -173,12 +460,69 @@ class WasmLineChartInterop implements WasmGraphRendererInterop<WasmLineChart> {
 	getScaleLineYAt(i: number) {
 		return this.wasmGraph.get_scale_line_y_at(i);
 	}
+	getXAxisTicksCount() {
+		return this.wasmGraph.get_x_axis_ticks_count();
+	}
+	getXAxisTickXAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_x_at(i);
+	}
+	getXAxisTickYAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_y_at(i);
+	}
+	getXAxisTickValueAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_value_at(i);
+	}
 	getScaleLineXAt(i: number) {
 		return this.wasmGraph.get_scale_line_x_at(i);
 	}
+	getSelectedPointSeries() {
+		return this.wasmGraph.get_selected_point_series();
+	}
 	getSelectedPointIndex() {
 		return this.wasmGraph.get_selected_point_index();
 	}
+	calculateTooltip(width: number, height: number) {
+		return this.wasmGraph.calculate_tooltip(width, height);
+	}
+	getTooltipAnchorX() {
+		return this.wasmGraph.get_tooltip_anchor_x();
+	}
+	getTooltipAnchorY() {
+		return this.wasmGraph.get_tooltip_anchor_y();
+	}
+	getTooltipX() {
+		return this.wasmGraph.get_tooltip_x();
+	}
+	getTooltipY() {
+		return this.wasmGraph.get_tooltip_y();
+	}
+	getTooltipWidth() {
+		return this.wasmGraph.get_tooltip_width();
+	}
+	getTooltipHeight() {
+		return this.wasmGraph.get_tooltip_height();
+	}
+	getTooltipEntriesLen() {
+		return this.wasmGraph.get_tooltip_entries_len();
+	}
+	getTooltipEntrySeriesAt(i: number) {
+		return this.wasmGraph.get_tooltip_entry_series_at(i);
+	}
+	getTooltipEntryNameAt(i: number) {
+		return this.wasmGraph.get_tooltip_entry_name_at(i);
+	}
+	getTooltipEntryValueAt(i: number) {
+		return this.wasmGraph.get_tooltip_entry_value_at(i);
+	}
+	getTooltipEntryIndexAt(i: number) {
+		return this.wasmGraph.get_tooltip_entry_index_at(i);
+	}
+	getSelectedPointIndices() {
+		return this.wasmGraph.get_selected_point_indices();
+	}
+	getHoveredPointSeries() {
+		return this.wasmGraph.get_hovered_point_series();
+	}
 	getHoveredPointIndex() {
 		return this.wasmGraph.get_hovered_point_index();
 	}
@@ -207,14 +551,82 @@ class WasmLineChartInterop implements WasmGraphRendererInterop<WasmLineChart> {
 			pointer.x,
 			pointer.y,
 			clickingState,
+			pointer.additive,
+			new Float32Array(
+				pointer.touches.flatMap((touch) => [touch.id, touch.x, touch.y]),
+			),
 		);
 	}
-	updateData(data: WasmDataPoint[], timestamp: number) {
+	updateData(data: WasmLineChartSeries[], timestamp: number) {
 		this.wasmGraph.update_data(data, timestamp);
 	}
+	selectPoint(
+		series: number,
+		index: number,
+		additive: boolean,
+		timestamp: number,
+	) {
+		this.wasmGraph.select_point(series, index, additive, timestamp);
+	}
+	clearSelection(timestamp: number) {
+		this.wasmGraph.clear_selection(timestamp);
+	}
+	focusNext() {
+		this.wasmGraph.focus_next();
+	}
+	focusPrevious() {
+		this.wasmGraph.focus_previous();
+	}
+	clearFocus() {
+		this.wasmGraph.clear_focus();
+	}
+	zoomAt(pointerX: number, factor: number, timestamp: number) {
+		this.wasmGraph.zoom_at(pointerX, factor, timestamp);
+	}
+	panBy(dx: number, timestamp: number) {
+		this.wasmGraph.pan_by(dx, timestamp);
+	}
+	setXDomain(min: number, max: number, timestamp: number) {
+		this.wasmGraph.set_x_domain(min, max, timestamp);
+	}
+	resetZoom(timestamp: number) {
+		this.wasmGraph.reset_zoom(timestamp);
+	}
+	getXDomainMin() {
+		return this.wasmGraph.get_x_domain_min();
+	}
+	getXDomainMax() {
+		return this.wasmGraph.get_x_domain_max();
+	}
+	getFocusedPointSeries() {
+		return this.wasmGraph.get_focused_point_series();
+	}
+	getFocusedPointIndex() {
+		return this.wasmGraph.get_focused_point_index();
+	}
+	setAnimationPolicy(policy: AnimationPolicy) {
+		this.wasmGraph.set_animation_policy(
+			animationPolicyToWasmAnimationPolicy(policy),
+		);
+	}
 	getIsAnimating() {
 		return this.wasmGraph.get_is_animating();
 	}
+	getMaxGeneralVertices() {
+		return this.wasmGraph.get_max_general_vertices();
+	}
+	getMaxAreaVertices() {
+		return this.wasmGraph.get_max_area_vertices();
+	}
+	getMaxLineVertices() {
+		return this.wasmGraph.get_max_line_vertices();
+	}
+	getMaxPointsVertices() {
+		return this.wasmGraph.get_max_points_vertices();
+	}
+	getLineHalfWidth() {
+		return this.wasmGraph.get_line_half_width();
+	}
 }
 
 export default class LineChart<TLabel>
@@ -231,14 +643,18 @@ export default class LineChart<TLabel>
 
 	private onSelectionChange: OnSelectionChange<TLabel>;
 	private onSelectionChangeIncludePositionInfo?: boolean;
+	private selectedPointSeries: number | undefined;
 	private selectedPointIndex: number | undefined;
 
 	private onHover: OnHover<TLabel>;
 	private onHoverIncludePositionInfo?: boolean;
+	private hoveredPointSeries?: number;
 	private hoveredPointIndex?: number;
 
 	private onXAxisLayout?: OnXAxisLayout<TLabel>;
 	private onYAxisLayout?: OnValueAxisLayout;
+	private onXAxisTicksLayout?: OnXAxisTicksLayout;
+	private onLegendLayout?: OnLegendLayout;
 
 	constructor(
 		canvas: HTMLCanvasElement,
@@ -251,6 +667,8 @@ export default class LineChart<TLabel>
 			onHover,
 			onXAxisLayout,
 			onValueAxisLayout: onYAxisLayout,
+			onXAxisTicksLayout,
+			onLegendLayout,
 		}: {
 			options?: LineChartOptions;
 		} & LineChartCallbacks<TLabel>,
@@ -280,10 +698,22 @@ export default class LineChart<TLabel>
 							right: options.positioning * devicePixelRatio,
 							bottom: options.positioning * devicePixelRatio,
 						},
+			lineOptions: {
+				color: options.lineOptions?.color ?? { r: 255, g: 255, b: 255 },
+				width: (options.lineOptions?.width ?? 2) * devicePixelRatio,
+				join: options.lineOptions?.join ?? "miter",
+				interpolation: options.lineOptions?.interpolation ?? "linear",
+				curveSegments: options.lineOptions?.curveSegments ?? 16,
+				downsampling: options.lineOptions?.downsampling ?? "none",
+			},
+			areaOptions: {
+				enabled: options.areaOptions?.enabled ?? false,
+				opacity: options.areaOptions?.opacity ?? 100 / 255,
+				gradient: options.areaOptions?.gradient ?? true,
+			},
 			pointOptions: {
 				radius: (options.pointOptions?.radius ?? 10) * devicePixelRatio,
 				hoverScale: options.pointOptions?.hoverScale ?? 1.1,
-				color: options.pointOptions?.color ?? { r: 255, g: 255, b: 255 },
 				hoverColor: options.pointOptions?.hoverColor ?? {
 					r: 150,
 					g: 150,
@@ -296,6 +726,8 @@ export default class LineChart<TLabel>
 					b: 255,
 				},
 				maxPoints: options.pointOptions?.maxPoints ?? 1000,
+				maxSeries: options.pointOptions?.maxSeries ?? 10,
+				window: options.pointOptions?.window ?? 0,
 			},
 			valueAxis: {
 				width: (options.valueAxis?.width ?? 0) * devicePixelRatio,
@@ -304,7 +736,51 @@ export default class LineChart<TLabel>
 					(options.valueAxis?.smallestScale ?? 1) * devicePixelRatio,
 				minPixelDistance:
 					(options.valueAxis?.minPixelDistance ?? 20) * devicePixelRatio,
+				scale: options.valueAxis?.scale ?? "linear",
+				ticks: options.valueAxis?.ticks ?? 10,
+				min: options.valueAxis?.min ?? null,
+				max: options.valueAxis?.max ?? null,
+				niceBounds: options.valueAxis?.niceBounds ?? false,
 			},
+			xAxis: {
+				tickLength: (options.xAxis?.tickLength ?? 5) * devicePixelRatio,
+				tickThickness: (options.xAxis?.tickThickness ?? 2) * devicePixelRatio,
+				minTickDistance:
+					(options.xAxis?.minTickDistance ?? 50) * devicePixelRatio,
+			},
+			legend: {
+				swatchSize: (options.legend?.swatchSize ?? 12) * devicePixelRatio,
+				itemWidth: (options.legend?.itemWidth ?? 100) * devicePixelRatio,
+				gap: (options.legend?.gap ?? 8) * devicePixelRatio,
+			},
+			focusRing: focusRingOptionsWithDefaults(
+				options.focusRing,
+				devicePixelRatio,
+			),
+			crosshair: {
+				enabled: options.crosshair?.enabled ?? false,
+				color: {
+					r: 255,
+					g: 255,
+					b: 255,
+					a: 100,
+					...options.crosshair?.color,
+				},
+				thickness: (options.crosshair?.thickness ?? 1) * devicePixelRatio,
+				markerSize: (options.crosshair?.markerSize ?? 8) * devicePixelRatio,
+			},
+			selection: selectionOptionsWithDefaults(options.selection),
+			tooltip: tooltipOptionsWithDefaults(options.tooltip, devicePixelRatio),
+			gestures: gestureOptionsWithDefaults(options.gestures, devicePixelRatio),
+			animations: animationOptionsWithDefaults(options.animations, {
+				enter: { duration: 500 },
+				hover: { duration: 200 },
+				select: { duration: 200 },
+				click: { duration: 200 },
+				exit: { duration: 200 },
+				viewport: { duration: 300 },
+			}),
+			animationPolicy: options.animationPolicy ?? defaultAnimationPolicy(),
 		};
 
 		super(
@@ -318,7 +794,7 @@ export default class LineChart<TLabel>
 			internalOptions,
 		);
 
-		this.data = dataToInternalData(data);
+		this.data = dataToInternalData(data, internalOptions.lineOptions.color);
 		this.onSelectionChange = onSelectionChange?.func;
 		this.onSelectionChangeIncludePositionInfo =
 			onSelectionChange?.includePositionInfo;
@@ -326,6 +802,8 @@ export default class LineChart<TLabel>
 		this.onHoverIncludePositionInfo = onHover?.includePositionInfo;
 		this.onXAxisLayout = onXAxisLayout;
 		this.onYAxisLayout = onYAxisLayout;
+		this.onXAxisTicksLayout = onXAxisTicksLayout;
+		this.onLegendLayout = onLegendLayout;
 	}
 
 	public updateData(data: LineChartData<TLabel>, timestamp: number) {
@@ -334,41 +812,139 @@ export default class LineChart<TLabel>
 			return;
 		}
 
-		if (data.length > this.options.pointOptions.maxPoints) {
+		const newData = dataToInternalData(data, this.options.lineOptions.color);
+
+		if (newData.length > this.options.pointOptions.maxSeries) {
+			logWarn(
+				`Cannot update data to more series than maxSeries, {${newData.length}}, {${this.options.pointOptions.maxSeries}}`,
+			);
+			return;
+		}
+
+		// Downsampled series are reduced to fit within maxPoints when they are drawn
+		const newPointsCount = pointsCount(newData);
+		if (
+			this.options.lineOptions.downsampling === "none" &&
+			newPointsCount > this.options.pointOptions.maxPoints
+		) {
 			logWarn(
-				`Cannot update data to length greater than maxPoints, {${data.length}}, {${this.options.pointOptions.maxPoints}}`,
+				`Cannot update data to length greater than maxPoints, {${newPointsCount}}, {${this.options.pointOptions.maxPoints}}`,
 			);
 			return;
 		}
 
-		if (data.length === this.data.length) {
+		if (newData.length === this.data.length) {
 			let hasDifference = false;
-			for (let i = 0; i < data.length; i++) {
-				const newDataPoint = data[i];
-				const oldDataPoint = this.data[i];
+			for (let s = 0; s < newData.length && !hasDifference; s++) {
+				const newSeries = newData[s];
+				const oldSeries = this.data[s];
 
 				if (
-					!oldDataPoint ||
-					newDataPoint.y !== oldDataPoint.y ||
-					newDataPoint.x !== oldDataPoint.x ||
-					newDataPoint.label !== oldDataPoint.label
+					newSeries.name !== oldSeries.name ||
+					newSeries.visible !== oldSeries.visible ||
+					newSeries.pointStyle !== oldSeries.pointStyle ||
+					newSeries.color.r !== oldSeries.color.r ||
+					newSeries.color.g !== oldSeries.color.g ||
+					newSeries.color.b !== oldSeries.color.b ||
+					newSeries.color.a !== oldSeries.color.a ||
+					newSeries.data.length !== oldSeries.data.length
 				) {
 					hasDifference = true;
 					break;
 				}
+
+				for (let i = 0; i < newSeries.data.length; i++) {
+					const newDataPoint = newSeries.data[i];
+					const oldDataPoint = oldSeries.data[i];
+
+					if (
+						!oldDataPoint ||
+						newDataPoint.y !== oldDataPoint.y ||
+						newDataPoint.x !== oldDataPoint.x ||
+						newDataPoint.key !== oldDataPoint.key ||
+						newDataPoint.label !== oldDataPoint.label
+					) {
+						hasDifference = true;
+						break;
+					}
+				}
 			}
 			if (!hasDifference) {
 				return;
 			}
 		}
 
-		this.data = dataToInternalData(data);
-		this.wasmGraphRenderer.updateData(dataToWasmData(data), timestamp);
+		this.data = newData;
+		this.wasmGraphRenderer.updateData(dataToWasmData(newData), timestamp);
 		this.wasmGraphRenderer.update(timestamp, this.pointer);
 
 		super.updateData(data, timestamp);
 	}
 
+	// Streams points onto the end of a series, dropping the oldest once the window is full
+	public appendPoints(
+		seriesIndex: number,
+		points: DataPoint<TLabel>[],
+		timestamp: number,
+	) {
+		trace(points);
+		const series = this.data[seriesIndex];
+		if (!series || points.length === 0) {
+			return;
+		}
+
+		const appended = this.wasmGraphRenderer.appendPoints(
+			seriesIndex,
+			points.map(
+				(point) =>
+					new WasmDataPoint(
+						point.x,
+						point.y,
+						dataKeyToWasmDataKey(point.key),
+					),
+			),
+			timestamp,
+		);
+		if (!appended) {
+			return;
+		}
+
+		series.data.push(...points.map((point) => ({ ...point })));
+		series.data.splice(
+			0,
+			series.data.length - this.wasmGraphRenderer.getPointsLen(seriesIndex),
+		);
+		this.wasmGraphRenderer.update(timestamp, this.pointer);
+
+		super.updateData(this.data, timestamp);
+	}
+
+	public shiftPoints(seriesIndex: number, count: number, timestamp: number) {
+		const series = this.data[seriesIndex];
+		if (!series || count <= 0) {
+			return;
+		}
+
+		series.data.splice(0, count);
+		this.wasmGraphRenderer.shiftPoints(seriesIndex, count);
+		this.wasmGraphRenderer.update(timestamp, this.pointer);
+
+		super.updateData(this.data, timestamp);
+	}
+
+	public setSeriesVisible(index: number, visible: boolean, timestamp: number) {
+		const series = this.data[index];
+		if (!series || series.visible === visible) {
+			return;
+		}
+
+		series.visible = visible;
+		this.wasmGraphRenderer.setSeriesVisible(index, visible, timestamp);
+		this.wasmGraphRenderer.update(timestamp, this.pointer);
+
+		super.updateData(this.data, timestamp);
+	}
+
 	public init(memory: WebAssembly.Memory, startTimestamp: number): void {
 		const wasmGraphRenderer = new WasmLineChartInterop({
 			data: dataToWasmData(this.data),
@@ -379,6 +955,13 @@ export default class LineChart<TLabel>
 		});
 
 		super._init(memory, wasmGraphRenderer);
+		this.glRenderer.setMaxVertices({
+			general: wasmGraphRenderer.getMaxGeneralVertices(),
+			area: wasmGraphRenderer.getMaxAreaVertices(),
+			line: wasmGraphRenderer.getMaxLineVertices(),
+			points: wasmGraphRenderer.getMaxPointsVertices(),
+		});
+		this.glRenderer.setLineHalfWidth(wasmGraphRenderer.getLineHalfWidth());
 	}
 
 	public dispose() {
@@ -387,10 +970,60 @@ export default class LineChart<TLabel>
 		this.removeInputEventHandlers();
 	}
 
-	public getPositionInfoForBarAt(index: number) {
+	// Where to put a tooltip of the given size (in CSS pixels) for what the pointer is over, clamped to the
+	// plot area, along with the original values it should show
+	public getTooltip(
+		width: number,
+		height: number,
+	): Tooltip<TooltipEntry<TLabel>> | null {
+		const renderer = this.wasmGraphRenderer;
+		if (
+			!renderer.calculateTooltip(
+				Math.round(width * devicePixelRatio),
+				Math.round(height * devicePixelRatio),
+			)
+		) {
+			return null;
+		}
+
+		const entries: TooltipEntry<TLabel>[] = [];
+		for (let i = 0; i < renderer.getTooltipEntriesLen(); i++) {
+			const series = renderer.getTooltipEntrySeriesAt(i);
+			const index = renderer.getTooltipEntryIndexAt(i);
+			if (series == null || index == null) {
+				continue;
+			}
+			const data = this.data[series]?.data[index];
+			if (data == null) {
+				continue;
+			}
+
+			entries.push({
+				series,
+				index,
+				name: renderer.getTooltipEntryNameAt(i) ?? "",
+				value: renderer.getTooltipEntryValueAt(i) ?? 0,
+				data,
+			});
+		}
+
+		return {
+			anchor: {
+				x: (renderer.getTooltipAnchorX() ?? 0) / devicePixelRatio,
+				y: (renderer.getTooltipAnchorY() ?? 0) / devicePixelRatio,
+			},
+			x: (renderer.getTooltipX() ?? 0) / devicePixelRatio,
+			y: (renderer.getTooltipY() ?? 0) / devicePixelRatio,
+			width: (renderer.getTooltipWidth() ?? 0) / devicePixelRatio,
+			height: (renderer.getTooltipHeight() ?? 0) / devicePixelRatio,
+			entries,
+		};
+	}
+
+	public getPositionInfoForPointAt(series: number, index: number) {
 		return {
-			x: this.wasmGraphRenderer.getPointXAt(index) / devicePixelRatio,
-			y: this.wasmGraphRenderer.getPointYAt(index) / devicePixelRatio,
+			x: this.wasmGraphRenderer.getPointXAt(series, index) / devicePixelRatio,
+			y: this.wasmGraphRenderer.getPointYAt(series, index) / devicePixelRatio,
 		};
 	}
 
@@ -400,8 +1033,9 @@ export default class LineChart<TLabel>
 		const valueAxisLayout = [];
 		for (let i = 0; i < scaleLinesLen; i++) {
 			valueAxisLayout.push({
-				value: roundToNearestMultiple(
+				value: scaleLineValue(
 					this.wasmGraphRenderer.getScaleLineValueAt(i),
+					this.options.valueAxis.scale,
 					this.options.valueAxis.smallestScale,
 				),
 				x: 0,
@@ -412,26 +1046,61 @@ export default class LineChart<TLabel>
 		this.options.valueAxis.width > 0 && this.onYAxisLayout?.(valueAxisLayout);
 		//
 		const labelsLayout = [];
-		const barsLen = this.wasmGraphRenderer.getPointsLen();
-		for (let i = 0; i < barsLen; i++) {
-			const barX = this.wasmGraphRenderer.getPointXAt(i);
-			const barWidth = 10; // TODO
-			let x = barX;
+		// Labels follow the first series, every series shares the same x axis
+		const pointsLen =
+			this.wasmGraphRenderer.getSeriesCount() > 0
+				? this.wasmGraphRenderer.getPointsLen(0)
+				: 0;
+		for (let i = 0; i < pointsLen; i++) {
+			const pointX = this.wasmGraphRenderer.getPointXAt(0, i);
+			const pointWidth = 10; // TODO
+			// Points are centered on their value, so the label is too
+			let x = pointX - pointWidth / 2;
 			const diff = x - this.options.valueAxis.width;
-			let width = barWidth + (diff < 0 ? diff : 0);
+			let width = pointWidth + (diff < 0 ? diff : 0);
 			x = clamp(x, { min: this.options.valueAxis.width });
 			width = clamp(width, { max: this.width - x });
 			const y = this.height - this.options.positioning.bottom;
 			labelsLayout.push({
-				label: this.data[i].label,
+				label: this.data[0].data[i].label,
 				x: x / devicePixelRatio,
 				y: y / devicePixelRatio,
 				width: width / devicePixelRatio,
 				height: this.options.positioning.bottom,
-				centerPoint: (barX + barWidth / 2 - x) / devicePixelRatio,
+				centerPoint: (pointX - x) / devicePixelRatio,
 			});
 		}
 		this.options.positioning.bottom > 0 && this.onXAxisLayout?.(labelsLayout);
+
+		const xAxisTicksCount = this.wasmGraphRenderer.getXAxisTicksCount();
+		const xAxisTicksLayout = [];
+		for (let i = 0; i < xAxisTicksCount; i++) {
+			xAxisTicksLayout.push({
+				value: this.wasmGraphRenderer.getXAxisTickValueAt(i),
+				x: this.wasmGraphRenderer.getXAxisTickXAt(i) / devicePixelRatio,
+				y: this.wasmGraphRenderer.getXAxisTickYAt(i) / devicePixelRatio,
+			});
+		}
+		this.onXAxisTicksLayout?.(xAxisTicksLayout);
+
+		const seriesCount = this.wasmGraphRenderer.getSeriesCount();
+		const swatchSize =
+			this.wasmGraphRenderer.getLegendSwatchSize() / devicePixelRatio;
+		const itemWidth =
+			this.wasmGraphRenderer.getLegendItemWidth() / devicePixelRatio;
+		const legendLayout = [];
+		for (let i = 0; i < seriesCount; i++) {
+			legendLayout.push({
+				name: this.wasmGraphRenderer.getSeriesNameAt(i),
+				color: this.wasmGraphRenderer.getSeriesColorAt(i),
+				visible: this.wasmGraphRenderer.getSeriesVisibleAt(i),
+				x: this.wasmGraphRenderer.getLegendSwatchXAt(i) / devicePixelRatio,
+				y: this.wasmGraphRenderer.getLegendSwatchYAt(i) / devicePixelRatio,
+				swatchSize,
+				width: itemWidth,
+			});
+		}
+		this.onLegendLayout?.(legendLayout);
 	}
 
 	public onPointerDown(pointerType: string) {
@@ -441,49 +1110,169 @@ export default class LineChart<TLabel>
 	}
 
 	public onPointerUp(_pointerType: string) {
-		const selectedBarIndex = this.wasmGraphRenderer.getSelectedPointIndex();
+		this.handleSelectionChange();
+	}
+
+	// Selects the point like clicking it would, for keyboard users and controls outside the chart.
+	// With additive set it joins the current selection instead of replacing it
+	public selectPoint(
+		series: number,
+		index: number,
+		timestamp: number,
+		additive = false,
+	) {
+		this.wasmGraphRenderer.selectPoint(series, index, additive, timestamp);
+		this.handleSelectionChange();
+	}
+
+	// Every selected point, in the order they were selected
+	public getSelectedPoints() {
+		const indices = this.wasmGraphRenderer.getSelectedPointIndices();
+		const pairs = new Float32Array(
+			this.wasmMemory.buffer,
+			indices.pointer,
+			indices.size,
+		);
+		const points: { series: number; index: number }[] = [];
+		for (let i = 0; i < pairs.length; i += 2) {
+			points.push({ series: pairs[i], index: pairs[i + 1] });
+		}
+
+		return points;
+	}
+
+	public selectFocusedPoint(timestamp: number) {
+		const series = this.wasmGraphRenderer.getFocusedPointSeries();
+		const index = this.wasmGraphRenderer.getFocusedPointIndex();
+		if (series == null || index == null) {
+			return;
+		}
+
+		this.selectPoint(series, index, timestamp);
+	}
+
+	public clearSelection(timestamp: number) {
+		this.wasmGraphRenderer.clearSelection(timestamp);
+		this.handleSelectionChange();
+	}
+
+	// Moves the focus ring, which is kept apart from hover, through the drawn points in order
+	public focusNext() {
+		this.wasmGraphRenderer.focusNext();
+	}
+
+	public focusPrevious() {
+		this.wasmGraphRenderer.focusPrevious();
+	}
+
+	public clearFocus() {
+		this.wasmGraphRenderer.clearFocus();
+	}
+
+	// Zooms in by factor (out when it is below 1) around pointerX, in CSS pixels from the left of the canvas
+	public zoomAt(pointerX: number, factor: number, timestamp: number) {
+		this.wasmGraphRenderer.zoomAt(
+			Math.max(0, Math.round(pointerX * devicePixelRatio)),
+			factor,
+			timestamp,
+		);
+	}
+
+	// Pans a zoomed in chart by dx CSS pixels, positive dx drags the data to the right
+	public panBy(dx: number, timestamp: number) {
+		this.wasmGraphRenderer.panBy(dx * devicePixelRatio, timestamp);
+	}
+
+	// Shows only the data with an X between min and max
+	public setXDomain(min: number, max: number, timestamp: number) {
+		this.wasmGraphRenderer.setXDomain(min, max, timestamp);
+	}
+
+	public resetZoom(timestamp: number) {
+		this.wasmGraphRenderer.resetZoom(timestamp);
+	}
+
+	public getXDomain() {
+		return {
+			min: this.wasmGraphRenderer.getXDomainMin(),
+			max: this.wasmGraphRenderer.getXDomainMax(),
+		};
+	}
+
+	public getFocusedPoint() {
+		const series = this.wasmGraphRenderer.getFocusedPointSeries();
+		const index = this.wasmGraphRenderer.getFocusedPointIndex();
+		if (series == null || index == null) {
+			return null;
+		}
+
+		return { series, index };
+	}
+
+	private handleSelectionChange() {
+		const selectedPointSeries = this.wasmGraphRenderer.getSelectedPointSeries();
+		const selectedPointIndex = this.wasmGraphRenderer.getSelectedPointIndex();
 		if (
-			selectedBarIndex === this.selectedPointIndex ||
+			(selectedPointSeries === this.selectedPointSeries &&
+				selectedPointIndex === this.selectedPointIndex) ||
 			!this.onSelectionChange
 		) {
 			return;
 		}
-		this.selectedPointIndex = selectedBarIndex;
-		if (selectedBarIndex == null || selectedBarIndex >= this.data.length) {
+		this.selectedPointSeries = selectedPointSeries;
+		this.selectedPointIndex = selectedPointIndex;
+		const data =
+			selectedPointSeries != null && selectedPointIndex != null
+				? this.data[selectedPointSeries]?.data[selectedPointIndex]
+				: undefined;
+		if (
+			selectedPointSeries == null ||
+			selectedPointIndex == null ||
+			data == null
+		) {
 			this.onSelectionChange(null);
 			return;
 		}
 		this.onSelectionChange({
-			data: this.data[selectedBarIndex],
+			data,
 			positionInfo: this.onSelectionChangeIncludePositionInfo
-				? this.getPositionInfoForBarAt(selectedBarIndex)
+				? this.getPositionInfoForPointAt(
+						selectedPointSeries,
+						selectedPointIndex,
+					)
 				: null,
-			index: selectedBarIndex,
+			series: selectedPointSeries,
+			index: selectedPointIndex,
 		});
 	}
 
 	public onPointerMove(pointerType: string) {
-		const hoveredBarIndex = this.wasmGraphRenderer.getHoveredPointIndex();
+		const hoveredPointSeries = this.wasmGraphRenderer.getHoveredPointSeries();
+		const hoveredPointIndex = this.wasmGraphRenderer.getHoveredPointIndex();
 		if (!this.onHover) {
 			return;
 		}
-		if (hoveredBarIndex == null) {
+		if (hoveredPointSeries == null || hoveredPointIndex == null) {
 			if (this.hoveredPointIndex != null) {
 				this.onHover(null);
+				this.hoveredPointSeries = undefined;
 				this.hoveredPointIndex = undefined;
 			}
 			return;
 		}
-		if (hoveredBarIndex >= this.data.length) {
+		const data = this.data[hoveredPointSeries]?.data[hoveredPointIndex];
+		if (data == null) {
 			return;
 		}
-		this.hoveredPointIndex = hoveredBarIndex;
+		this.hoveredPointSeries = hoveredPointSeries;
+		this.hoveredPointIndex = hoveredPointIndex;
 		this.onHover({
-			data: this.data[hoveredBarIndex],
+			data,
 			positionInfo: this.onHoverIncludePositionInfo
-				? this.getPositionInfoForBarAt(hoveredBarIndex)
+				? this.getPositionInfoForPointAt(hoveredPointSeries, hoveredPointIndex)
 				: null,
-			index: hoveredBarIndex,
+			series: hoveredPointSeries,
+			index: hoveredPointIndex,
 			pointer: {
 				x: this.pointer.x / devicePixelRatio,
 				y: this.pointer.y / devicePixelRatio,
@@ -494,6 +1283,7 @@ export default class LineChart<TLabel>
 
 	public onPointerLeave() {
 		this.onHover?.(null);
+		this.hoveredPointSeries = undefined;
 		this.hoveredPointIndex = undefined;
 	}
 
@@ -503,12 +1293,27 @@ export default class LineChart<TLabel>
 			timestamp,
 			this.pointer,
 		);
+		// A long-press selects while the finger is still down
+		if (this.pointer.touches.length > 0) {
+			this.handleSelectionChange();
+		}
 
 		this.glRenderer.updateGeneralBuffers(
 			lineChartData.vertex_array_general,
 			lineChartData.colors_array_general,
 		);
 
+		this.glRenderer.updateAreaBuffers(
+			lineChartData.vertex_array_area,
+			lineChartData.colors_array_area,
+		);
+
+		this.glRenderer.updateLineBuffers(
+			lineChartData.vertex_array_line,
+			lineChartData.colors_array_line,
+			lineChartData.relative_line_positions,
+		);
+
 		this.glRenderer.updatePointsBuffers(
 			lineChartData.vertex_array_points,
 			lineChartData.colors_array_points,
@@ -522,6 +1327,11 @@ export default class LineChart<TLabel>
 		super.render(timestamp);
 	}
 
+	public setAnimationPolicy(policy: AnimationPolicy) {
+		this.options.animationPolicy = policy;
+		this.wasmGraphRenderer.setAnimationPolicy(policy);
+	}
+
 	public isAnimating() {
 		return this.wasmGraphRenderer.getIsAnimating();
 	}
@@ -1,6 +1,7 @@
 import { trace } from "@izumiano/vite-logger";
 import type { WasmFloat32Array } from "../../graph-renderer/pkg/graph_renderer";
 import GeneralProgram from "../shared/generalProgram";
+import LinesProgram from "./linesProgram";
 import WebGLRenderer, { type IWebGLRenderer } from "../webGLRenderer";
 import { mat4 } from "gl-matrix";
 import type { Color } from "../graphRenderer";
@@ -10,23 +11,33 @@ export default class LineChartGL
 	implements IWebGLRenderer
 {
 	private program_general: GeneralProgram;
+	private program_area: GeneralProgram;
+	private program_line: LinesProgram;
 	private program_points: GeneralProgram;
 
 	constructor({
 		canvas,
 		backgroundColor,
-	}: { canvas: HTMLCanvasElement; backgroundColor: Color }) {
+	}: {
+		canvas: HTMLCanvasElement;
+		backgroundColor: Color;
+	}) {
 		super({
 			canvas,
 			backgroundColor,
 		});
 
-		this.program_general = new GeneralProgram(this.gl, { maxVertices: 600 });
-		this.program_points = new GeneralProgram(this.gl, { maxVertices: 600 });
+		// Sized by setMaxVertices once the wasm side is made
+		this.program_general = new GeneralProgram(this.gl);
+		this.program_area = new GeneralProgram(this.gl);
+		this.program_line = new LinesProgram(this.gl, { maxVertices: 600 });
+		this.program_points = new GeneralProgram(this.gl);
 	}
 
 	public init(memory: WebAssembly.Memory): void {
 		this.program_general.init(memory);
+		this.program_area.init(memory);
+		this.program_line.init(memory);
 		this.program_points.init(memory);
 	}
 
@@ -37,6 +48,42 @@ export default class LineChartGL
 		this.program_general.updateBuffers(positions, colors);
 	}
 
+	public updateAreaBuffers(
+		positions: WasmFloat32Array,
+		colors: WasmFloat32Array,
+	) {
+		this.program_area.updateBuffers(positions, colors);
+	}
+
+	public updateLineBuffers(
+		positions: WasmFloat32Array,
+		colors: WasmFloat32Array,
+		relativeLinePositions: WasmFloat32Array,
+	) {
+		this.program_line.updateBuffers(positions, colors, relativeLinePositions);
+	}
+
+	public setMaxVertices({
+		general,
+		area,
+		line,
+		points,
+	}: {
+		general: number;
+		area: number;
+		line: number;
+		points: number;
+	}) {
+		this.program_general.setMaxVertices(general);
+		this.program_area.setMaxVertices(area);
+		this.program_line.setMaxVertices(line);
+		this.program_points.setMaxVertices(points);
+	}
+
+	public setLineHalfWidth(halfWidth: number) {
+		this.program_line.halfWidth = halfWidth;
+	}
+
 	public updatePointsBuffers(
 		positions: WasmFloat32Array,
 		colors: WasmFloat32Array,
@@ -52,6 +99,8 @@ export default class LineChartGL
 		const modelViewMatrix = Array.from(mat4.create());
 
 		this.program_general.draw(timestamp, projectionMatrix, modelViewMatrix);
+		this.program_area.draw(timestamp, projectionMatrix, modelViewMatrix);
+		this.program_line.draw(timestamp, projectionMatrix, modelViewMatrix);
 		this.program_points.draw(timestamp, projectionMatrix, modelViewMatrix);
 	}
 }
@@ -0,0 +1,15 @@
+precision mediump float;
+
+// Half the line width in pixels
+uniform float uHalfWidth;
+
+varying lowp vec4 vColor;
+// -1 on the right edge of the line, 1 on the left and 0 in the middle
+varying float vRelativeLinePosition;
+
+void main(void) {
+	// fade out over the last pixel on either side so the edges are not jagged
+	float distanceToEdge = (1.0 - abs(vRelativeLinePosition)) * uHalfWidth;
+	float coverage = clamp(distanceToEdge, 0.0, 1.0);
+	gl_FragColor = vec4(vColor.rgb, vColor.a * coverage);
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+attribute vec4 aVertexPosition;
+attribute vec4 aVertexColor;
+attribute float aVertexRelativeLinePosition;
+
+uniform mat4 uModelViewMatrix;
+uniform mat4 uProjectionMatrix;
+
+varying lowp vec4 vColor;
+varying mediump float vRelativeLinePosition;
+
+void main(void) {
+	gl_Position = uProjectionMatrix * uModelViewMatrix * aVertexPosition;
+	vColor = aVertexColor;
+	vRelativeLinePosition = aVertexRelativeLinePosition;
+}
\ No newline at end of file
@@ -0,0 +1,234 @@
+import { trace } from "@izumiano/vite-logger";
+import type { WasmFloat32Array } from "../../graph-renderer/pkg/graph_renderer";
+import type { IShaderProgram, WebGLBufferInfo } from "../shaderProgram";
+import ShaderProgram from "../shaderProgram";
+
+import fsSource from "./lines.frag";
+import vsSource from "./lines.vert";
+
+type AttribLocations = {
+	vertexPosition: number;
+	vertexColor: number;
+	vertexRelativeLinePosition: number;
+};
+
+type UniformLocations = {
+	projectionMatrix: WebGLUniformLocation;
+	modelViewMatrix: WebGLUniformLocation;
+	halfWidth: WebGLUniformLocation;
+};
+
+type Buffers = {
+	positions: WebGLBufferInfo;
+	colors: WebGLBufferInfo;
+	relativeLinePositions: WebGLBufferInfo;
+};
+
+export default class LinesProgram
+	extends ShaderProgram<AttribLocations, UniformLocations, Buffers, object>
+	implements IShaderProgram
+{
+	public halfWidth: number = 1;
+
+	constructor(gl: WebGL2RenderingContext, options: { maxVertices: number }) {
+		super(
+			gl,
+			vsSource,
+			fsSource,
+			["vertexColor", "vertexPosition", "vertexRelativeLinePosition"],
+			["modelViewMatrix", "projectionMatrix", "halfWidth"],
+			options,
+		);
+	}
+
+	initBuffers(gl: WebGL2RenderingContext): Buffers {
+		const positionsBuffer_lines = gl.createBuffer();
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, positionsBuffer_lines);
+
+		gl.bufferData(
+			gl.ARRAY_BUFFER,
+			new Float32Array(this.options.maxVertices * 2),
+			gl.DYNAMIC_DRAW,
+		);
+
+		const colorsBuffer_lines = gl.createBuffer();
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, colorsBuffer_lines);
+
+		gl.bufferData(
+			gl.ARRAY_BUFFER,
+			new Float32Array(this.options.maxVertices * 4),
+			gl.DYNAMIC_DRAW,
+		);
+
+		const relativeLinePositionsBuffer = gl.createBuffer();
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, relativeLinePositionsBuffer);
+
+		gl.bufferData(
+			gl.ARRAY_BUFFER,
+			new Float32Array(this.options.maxVertices),
+			gl.DYNAMIC_DRAW,
+		);
+
+		return {
+			positions: { buf: positionsBuffer_lines, size: 0 },
+			colors: { buf: colorsBuffer_lines, size: 0 },
+			relativeLinePositions: { buf: relativeLinePositionsBuffer, size: 0 },
+		};
+	}
+
+	// Remakes the buffers once the wasm side knows how many vertices it can write
+	public setMaxVertices(maxVertices: number) {
+		if (maxVertices === this.options.maxVertices) {
+			return;
+		}
+
+		this.gl.deleteBuffer(this.buffers.positions.buf);
+		this.gl.deleteBuffer(this.buffers.colors.buf);
+		this.gl.deleteBuffer(this.buffers.relativeLinePositions.buf);
+		this.options.maxVertices = maxVertices;
+		this.buffers = this.initBuffers(this.gl);
+	}
+
+	private updatePositionsBuffer(positions: WasmFloat32Array) {
+		trace({ positions });
+		this.gl.bindBuffer(this.gl.ARRAY_BUFFER, this.buffers.positions.buf);
+		this.gl.bufferSubData(
+			this.gl.ARRAY_BUFFER,
+			0,
+			this.wasmArrayToFloat32Array(positions),
+		);
+		this.buffers.positions.size = positions.size;
+	}
+
+	private updateColorsBuffer(colors: WasmFloat32Array) {
+		trace({ colors });
+		this.gl.bindBuffer(this.gl.ARRAY_BUFFER, this.buffers.colors.buf);
+		this.gl.bufferSubData(
+			this.gl.ARRAY_BUFFER,
+			0,
+			this.wasmArrayToFloat32Array(colors),
+		);
+		this.buffers.colors.size = colors.size;
+	}
+
+	private updateRelativeLinePositionsBuffer(
+		relativeLinePositions: WasmFloat32Array,
+	) {
+		trace({ relativeLinePositions });
+		this.gl.bindBuffer(
+			this.gl.ARRAY_BUFFER,
+			this.buffers.relativeLinePositions.buf,
+		);
+		this.gl.bufferSubData(
+			this.gl.ARRAY_BUFFER,
+			0,
+			this.wasmArrayToFloat32Array(relativeLinePositions),
+		);
+		this.buffers.relativeLinePositions.size = relativeLinePositions.size;
+	}
+
+	public updateBuffers(
+		positions: WasmFloat32Array,
+		colors: WasmFloat32Array,
+		relativeLinePositions: WasmFloat32Array,
+	) {
+		trace();
+		this.gl.useProgram(this.program);
+		this.updatePositionsBuffer(positions);
+		this.updateColorsBuffer(colors);
+		this.updateRelativeLinePositionsBuffer(relativeLinePositions);
+	}
+
+	private setPositionAttribute(gl: WebGL2RenderingContext) {
+		const numComponents = 2;
+		const type = gl.FLOAT;
+		const normalize = false;
+		const stride = 0;
+		const offset = 0;
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, this.buffers.positions.buf);
+		gl.vertexAttribPointer(
+			this.attribLocations.vertexPosition,
+			numComponents,
+			type,
+			normalize,
+			stride,
+			offset,
+		);
+		gl.enableVertexAttribArray(this.attribLocations.vertexPosition);
+	}
+
+	private setColorAttribute(gl: WebGL2RenderingContext) {
+		const numComponents = 4;
+		const type = gl.FLOAT;
+		const normalize = false;
+		const stride = 0;
+		const offset = 0;
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, this.buffers.colors.buf);
+		gl.vertexAttribPointer(
+			this.attribLocations.vertexColor,
+			numComponents,
+			type,
+			normalize,
+			stride,
+			offset,
+		);
+		gl.enableVertexAttribArray(this.attribLocations.vertexColor);
+	}
+
+	private setRelativeLinePositionAttribute(gl: WebGL2RenderingContext) {
+		const numComponents = 1;
+		const type = gl.FLOAT;
+		const normalize = false;
+		const stride = 0;
+		const offset = 0;
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, this.buffers.relativeLinePositions.buf);
+		gl.vertexAttribPointer(
+			this.attribLocations.vertexRelativeLinePosition,
+			numComponents,
+			type,
+			normalize,
+			stride,
+			offset,
+		);
+		gl.enableVertexAttribArray(
+			this.attribLocations.vertexRelativeLinePosition,
+		);
+	}
+
+	public setAttributes(gl: WebGL2RenderingContext) {
+		this.setPositionAttribute(gl);
+		this.setColorAttribute(gl);
+		this.setRelativeLinePositionAttribute(gl);
+	}
+
+	public draw(
+		timestamp: number,
+		projectionMatrix: number[],
+		modelViewMatrix: number[],
+	) {
+		const gl = this.gl;
+		const offset = 0;
+		const vertexCount = this.buffers.positions.size / 2;
+
+		super.draw(timestamp, projectionMatrix, modelViewMatrix, vertexCount);
+
+		gl.uniform1f(this.uniformLocations.halfWidth, this.halfWidth);
+
+		// The faded edges are blended over what is already drawn, alpha is kept as it was
+		gl.enable(gl.BLEND);
+		gl.blendFuncSeparate(
+			gl.SRC_ALPHA,
+			gl.ONE_MINUS_SRC_ALPHA,
+			gl.ONE,
+			gl.ONE_MINUS_SRC_ALPHA,
+		);
+		gl.drawArrays(gl.TRIANGLES, offset, vertexCount);
+		gl.disable(gl.BLEND);
+	}
+}
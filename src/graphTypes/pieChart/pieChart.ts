@@ -0,0 +1,564 @@
+import { logWarn, trace } from "@izumiano/vite-logger";
+import {
+	ClickingState as WasmClickingState,
+	PieChart as WasmPieChart,
+	PieChartDataPoint as WasmDataPoint,
+	PieChartLayout as WasmPieChartLayout,
+	PieChartOptions as WasmPieChartOptions,
+	PieLayout as WasmPieLayout,
+	Positioning as WasmPositioning,
+	SliceOptions as WasmSliceOptions,
+} from "../../graph-renderer/pkg/graph_renderer.js";
+
+import {
+	type Color,
+	devicePixelRatio,
+	type GraphData,
+	GraphRenderer,
+	type GraphRendererOptions,
+	type IGraphRenderer,
+	type InternalGraphRendererOptions,
+	type PointerType,
+	type WasmGraphRendererInterop,
+} from "../graphRenderer.js";
+import {
+	colorToWasmColor,
+	gestureOptionsToWasmGestureOptions,
+	gestureOptionsWithDefaults,
+} from "../wasmUtils.js";
+import PieChartGL from "./pieChartGL.js";
+import type { DeepRequired } from "../../utils.js";
+import type {
+	AnimationOptions,
+	AnimationPolicy,
+	GestureOptions,
+	PointerCallback,
+} from "../shared/types.js";
+import {
+	animationOptionsToWasmAnimationOptions,
+	animationPolicyToWasmAnimationPolicy,
+	defaultAnimationPolicy,
+	animationOptionsWithDefaults,
+} from "../shared/animation.js";
+
+export type PieChartData<TLabel> = DataPoint<TLabel>[] & GraphData;
+type InternalPieChartData<TLabel> = DataPoint<TLabel>[] & GraphData;
+
+interface PieOptions {
+	// Ratio of the outer radius, 0 draws a pie and anything above draws a donut
+	innerRadius?: number;
+	// Number of segments a full circle is split into
+	segments?: number;
+}
+
+interface SliceOptions {
+	hoverScale?: number;
+	hoverColor?: Color;
+	selectedColor?: Color;
+	maxSlices?: number;
+}
+
+export interface PieChartOptions extends GraphRendererOptions {
+	pieOptions?: PieOptions;
+	sliceOptions?: SliceOptions;
+	gestures?: GestureOptions;
+	animations?: AnimationOptions;
+	animationPolicy?: AnimationPolicy;
+}
+
+class WasmPieChartInterop implements WasmGraphRendererInterop<WasmPieChart> {
+	wasmGraph: WasmPieChart;
+
+	constructor({
+		data,
+		startTimestamp,
+		width,
+		height,
+		options,
+	}: {
+		data: WasmDataPoint[];
+		startTimestamp: number;
+		width: number;
+		height: number;
+		options: InternalPieChartOptions;
+	}) {
+		this.wasmGraph = new WasmPieChart(
+			data,
+			startTimestamp,
+			width,
+			height,
+
+			new WasmPieChartLayout(
+				new WasmPositioning(
+					options.positioning.bottom,
+					options.positioning.top,
+					options.positioning.left,
+					options.positioning.right,
+				),
+				new WasmPieLayout(
+					options.pieOptions.innerRadius,
+					options.pieOptions.segments,
+				),
+			),
+
+			new WasmPieChartOptions(
+				new WasmSliceOptions(
+					colorToWasmColor(options.sliceOptions.hoverColor),
+					colorToWasmColor(options.sliceOptions.selectedColor),
+					options.sliceOptions.hoverScale,
+					options.sliceOptions.maxSlices,
+				),
+				gestureOptionsToWasmGestureOptions(options.gestures),
+				animationOptionsToWasmAnimationOptions(options.animations),
+				animationPolicyToWasmAnimationPolicy(options.animationPolicy),
+			),
+		);
+	}
+	public updateData(data: WasmDataPoint[], timestamp: number) {
+		this.wasmGraph.update_data(data, timestamp);
+	}
+
+	resize(width: number, height: number) {
+		this.wasmGraph.resize(width, height);
+	}
+	update(timestamp: number, pointer: PointerType) {
+		let clickingState: WasmClickingState;
+
+		switch (pointer.clickingState) {
+			case "None":
+				clickingState = WasmClickingState.None;
+				break;
+			case "Holding":
+				clickingState = WasmClickingState.Holding;
+				break;
+			case "JustReleased":
+				clickingState = WasmClickingState.JustReleased;
+				break;
+			default:
+				clickingState = WasmClickingState.None;
+		}
+
+		return this.wasmGraph.update(
+			timestamp,
+			pointer.x,
+			pointer.y,
+			clickingState,
+			new Float32Array(
+				pointer.touches.flatMap((touch) => [touch.id, touch.x, touch.y]),
+			),
+		);
+	}
+
+	setAnimationPolicy(policy: AnimationPolicy) {
+		this.wasmGraph.set_animation_policy(
+			animationPolicyToWasmAnimationPolicy(policy),
+		);
+	}
+	getIsAnimating() {
+		return this.wasmGraph.get_is_animating();
+	}
+	getMaxSliceVertices() {
+		return this.wasmGraph.get_max_slice_vertices();
+	}
+
+	getSlicesLen() {
+		return this.wasmGraph.get_slices_len();
+	}
+	getSliceLabelXAt(i: number) {
+		return this.wasmGraph.get_slice_label_x_at(i);
+	}
+	getSliceLabelYAt(i: number) {
+		return this.wasmGraph.get_slice_label_y_at(i);
+	}
+	getSliceStartAngleAt(i: number) {
+		return this.wasmGraph.get_slice_start_angle_at(i);
+	}
+	getSliceEndAngleAt(i: number) {
+		return this.wasmGraph.get_slice_end_angle_at(i);
+	}
+	getCenterX() {
+		return this.wasmGraph.get_center_x();
+	}
+	getCenterY() {
+		return this.wasmGraph.get_center_y();
+	}
+	getRadius() {
+		return this.wasmGraph.get_radius();
+	}
+	getInnerRadius() {
+		return this.wasmGraph.get_inner_radius();
+	}
+	getSelectedSliceIndex() {
+		return this.wasmGraph.get_selected_slice_index();
+	}
+	getHoveredSliceIndex() {
+		return this.wasmGraph.get_hovered_slice_index();
+	}
+}
+
+function dataToWasmData<TLabel>(data: PieChartData<TLabel>) {
+	return data.map(
+		(item) => new WasmDataPoint(item.value, colorToWasmColor(item.color)),
+	);
+}
+
+function dataToInternalData<TLabel>(data: PieChartData<TLabel>) {
+	return data.map((data) => {
+		return { ...data };
+	});
+}
+
+export interface DataPoint<TLabel> {
+	label: TLabel;
+	value: number;
+	color: Color;
+}
+
+// Angles are in radians, starting at 12 o'clock and going clockwise
+type PositionInfo = {
+	centerX: number;
+	centerY: number;
+	radius: number;
+	innerRadius: number;
+	startAngle: number;
+	endAngle: number;
+} | null;
+
+export type OnSelectionChangeArgs<TLabel> = {
+	data: DataPoint<TLabel>;
+	positionInfo?: PositionInfo;
+	index: number;
+} | null;
+type OnSelectionChange<TLabel> =
+	| ((args: OnSelectionChangeArgs<TLabel>) => void)
+	| undefined;
+
+export type OnHoverArgs<TLabel> = {
+	data: DataPoint<TLabel>;
+	positionInfo?: PositionInfo;
+	pointer: { x: number; y: number; type: string };
+	index: number;
+} | null;
+type OnHover<TLabel> = ((args: OnHoverArgs<TLabel>) => void) | undefined;
+
+type OnLabelsLayout<TLabel> = (args: OnLabelsLayoutParams<TLabel>) => void;
+
+// Middle of each slice, halfway between the inner and outer radius
+export type OnLabelsLayoutParams<TLabel> = {
+	label: TLabel;
+	x: number;
+	y: number;
+}[];
+
+export type PieChartCallbacks<TLabel> = {
+	onSelectionChange?: PointerCallback<OnSelectionChange<TLabel>>;
+	onHover?: PointerCallback<OnHover<TLabel>>;
+	onLabelsLayout?: OnLabelsLayout<TLabel>;
+};
+
+type InternalPieChartOptions = InternalGraphRendererOptions &
+	DeepRequired<PieChartOptions>;
+
+export default class PieChart<TLabel>
+	extends GraphRenderer<
+		WasmPieChart,
+		WasmPieChartInterop,
+		InternalPieChartOptions,
+		PieChartGL,
+		PieChartData<TLabel>
+	>
+	implements IGraphRenderer
+{
+	private data: InternalPieChartData<TLabel>;
+
+	private onSelectionChange: OnSelectionChange<TLabel>;
+	private onSelectionChangeIncludePositionInfo?: boolean;
+	private selectedSliceIndex: number | undefined;
+
+	private onHover: OnHover<TLabel>;
+	private onHoverIncludePositionInfo?: boolean;
+	private hoveredSliceIndex?: number;
+
+	private onLabelsLayout?: OnLabelsLayout<TLabel>;
+
+	constructor(
+		canvas: HTMLCanvasElement,
+		width: number,
+		height: number,
+		data: PieChartData<TLabel>,
+		{
+			options,
+			onSelectionChange,
+			onHover,
+			onLabelsLayout,
+		}: {
+			options?: PieChartOptions;
+		} & PieChartCallbacks<TLabel>,
+	) {
+		trace();
+		options ??= {};
+
+		const backgroundColor = options.backgroundColor ?? {
+			r: 0,
+			g: 0,
+			b: 0,
+		};
+
+		const internalOptions: InternalPieChartOptions = {
+			backgroundColor: { ...backgroundColor, a: backgroundColor.a ?? 255 },
+			positioning:
+				typeof options.positioning !== "number"
+					? {
+							top: (options.positioning?.top ?? 0) * devicePixelRatio,
+							left: (options.positioning?.left ?? 0) * devicePixelRatio,
+							right: (options.positioning?.right ?? 0) * devicePixelRatio,
+							bottom: (options.positioning?.bottom ?? 0) * devicePixelRatio,
+						}
+					: {
+							top: options.positioning * devicePixelRatio,
+							left: options.positioning * devicePixelRatio,
+							right: options.positioning * devicePixelRatio,
+							bottom: options.positioning * devicePixelRatio,
+						},
+			pieOptions: {
+				innerRadius: options.pieOptions?.innerRadius ?? 0,
+				segments: options.pieOptions?.segments ?? 128,
+			},
+			sliceOptions: {
+				hoverScale: options.sliceOptions?.hoverScale ?? 1.05,
+				hoverColor: options.sliceOptions?.hoverColor ?? {
+					r: 150,
+					g: 150,
+					b: 150,
+					a: 127,
+				},
+				selectedColor: options.sliceOptions?.selectedColor ?? {
+					r: 100,
+					g: 100,
+					b: 255,
+				},
+				maxSlices: options.sliceOptions?.maxSlices ?? 100,
+			},
+			gestures: gestureOptionsWithDefaults(options.gestures, devicePixelRatio),
+			animations: animationOptionsWithDefaults(options.animations, {
+				enter: { duration: 500 },
+				hover: { duration: 200 },
+				select: { duration: 200 },
+				click: { duration: 200 },
+				exit: { duration: 500 },
+				viewport: { duration: 300 },
+			}),
+			animationPolicy: options.animationPolicy ?? defaultAnimationPolicy(),
+		};
+
+		super(
+			canvas,
+			width,
+			height,
+			new PieChartGL({
+				canvas,
+				backgroundColor: options.backgroundColor ?? { r: 0, g: 0, b: 0 },
+			}),
+			internalOptions,
+		);
+
+		this.data = dataToInternalData(data);
+		this.onSelectionChange = onSelectionChange?.func;
+		this.onSelectionChangeIncludePositionInfo =
+			onSelectionChange?.includePositionInfo;
+		this.onHover = onHover?.func;
+		this.onHoverIncludePositionInfo = onHover?.includePositionInfo;
+		this.onLabelsLayout = onLabelsLayout;
+	}
+
+	public getPositionInfoForSliceAt(index: number) {
+		return {
+			centerX: this.wasmGraphRenderer.getCenterX() / devicePixelRatio,
+			centerY: this.wasmGraphRenderer.getCenterY() / devicePixelRatio,
+			radius: this.wasmGraphRenderer.getRadius() / devicePixelRatio,
+			innerRadius: this.wasmGraphRenderer.getInnerRadius() / devicePixelRatio,
+			startAngle: this.wasmGraphRenderer.getSliceStartAngleAt(index),
+			endAngle: this.wasmGraphRenderer.getSliceEndAngleAt(index),
+		};
+	}
+
+	public updateData(data: PieChartData<TLabel>, timestamp: number) {
+		trace(data);
+		if (data === this.data) {
+			return;
+		}
+
+		if (data.length > this.options.sliceOptions.maxSlices) {
+			logWarn(
+				`Cannot update data to length greater than maxSlices, {${data.length}}, {${this.options.sliceOptions.maxSlices}}`,
+			);
+			return;
+		}
+
+		if (data.length === this.data.length) {
+			let hasDifference = false;
+			for (let i = 0; i < data.length; i++) {
+				const newDataPoint = data[i];
+				const oldDataPoint = this.data[i];
+
+				if (
+					!oldDataPoint ||
+					newDataPoint.value !== oldDataPoint.value ||
+					newDataPoint.color !== oldDataPoint.color ||
+					newDataPoint.label !== oldDataPoint.label
+				) {
+					hasDifference = true;
+					break;
+				}
+			}
+			if (!hasDifference) {
+				return;
+			}
+		}
+
+		this.data = dataToInternalData(data);
+		this.wasmGraphRenderer.updateData(dataToWasmData(data), timestamp);
+		this.wasmGraphRenderer.update(timestamp, this.pointer);
+
+		super.updateData(data, timestamp);
+	}
+
+	public init(memory: WebAssembly.Memory, startTimestamp: number): void {
+		const wasmGraphRenderer = new WasmPieChartInterop({
+			data: dataToWasmData(this.data),
+			startTimestamp,
+			width: this.canvas.width,
+			height: this.canvas.height,
+			options: this.options,
+		});
+
+		super._init(memory, wasmGraphRenderer);
+		this.glRenderer.setMaxSliceVertices(
+			wasmGraphRenderer.getMaxSliceVertices(),
+		);
+	}
+
+	public dispose() {
+		trace();
+		this.wasmGraphRenderer.wasmGraph.free();
+		this.removeInputEventHandlers();
+	}
+
+	public handleLayout() {
+		trace();
+		const labelsLayout = [];
+
+		const slicesLen = this.wasmGraphRenderer.getSlicesLen();
+		for (let i = 0; i < slicesLen && i < this.data.length; i++) {
+			labelsLayout.push({
+				label: this.data[i].label,
+				x: this.wasmGraphRenderer.getSliceLabelXAt(i) / devicePixelRatio,
+				y: this.wasmGraphRenderer.getSliceLabelYAt(i) / devicePixelRatio,
+			});
+		}
+
+		this.onLabelsLayout?.(labelsLayout);
+	}
+
+	public onPointerDown(pointerType: string) {
+		if (pointerType !== "mouse") {
+			this.onPointerMove(pointerType);
+		}
+	}
+
+	public onPointerUp(_pointerType: string) {
+		this.handleSelectionChange();
+	}
+
+	private handleSelectionChange() {
+		const selectedSliceIndex = this.wasmGraphRenderer.getSelectedSliceIndex();
+
+		if (
+			selectedSliceIndex === this.selectedSliceIndex ||
+			!this.onSelectionChange
+		) {
+			return;
+		}
+		this.selectedSliceIndex = selectedSliceIndex;
+
+		if (selectedSliceIndex == null || selectedSliceIndex >= this.data.length) {
+			this.onSelectionChange(null);
+			return;
+		}
+
+		this.onSelectionChange({
+			data: this.data[selectedSliceIndex],
+			positionInfo: this.onSelectionChangeIncludePositionInfo
+				? this.getPositionInfoForSliceAt(selectedSliceIndex)
+				: null,
+			index: selectedSliceIndex,
+		});
+	}
+
+	public onPointerMove(pointerType: string) {
+		const hoveredSliceIndex = this.wasmGraphRenderer.getHoveredSliceIndex();
+		if (!this.onHover) {
+			return;
+		}
+
+		if (hoveredSliceIndex == null) {
+			if (this.hoveredSliceIndex != null) {
+				this.onHover(null);
+				this.hoveredSliceIndex = undefined;
+			}
+			return;
+		}
+
+		if (hoveredSliceIndex >= this.data.length) {
+			return;
+		}
+		this.hoveredSliceIndex = hoveredSliceIndex;
+		this.onHover({
+			data: this.data[hoveredSliceIndex],
+			positionInfo: this.onHoverIncludePositionInfo
+				? this.getPositionInfoForSliceAt(hoveredSliceIndex)
+				: null,
+			index: hoveredSliceIndex,
+			pointer: {
+				x: this.pointer.x / devicePixelRatio,
+				y: this.pointer.y / devicePixelRatio,
+				type: pointerType,
+			},
+		});
+	}
+
+	public onPointerLeave() {
+		this.onHover?.(null);
+		this.hoveredSliceIndex = undefined;
+	}
+
+	public update(timestamp: number) {
+		trace();
+		const pieChartData = this.wasmGraphRenderer.update(timestamp, this.pointer);
+		// A long-press selects while the finger is still down
+		if (this.pointer.touches.length > 0) {
+			this.handleSelectionChange();
+		}
+
+		this.glRenderer.updateSlicesBuffers(
+			pieChartData.vertex_array_slices,
+			pieChartData.colors_array_slices,
+		);
+
+		super.update(timestamp);
+	}
+
+	public render(timestamp: number) {
+		trace({ width: this.width, height: this.height });
+		super.render(timestamp);
+	}
+
+	public setAnimationPolicy(policy: AnimationPolicy) {
+		this.options.animationPolicy = policy;
+		this.wasmGraphRenderer.setAnimationPolicy(policy);
+	}
+
+	public isAnimating() {
+		return this.wasmGraphRenderer.getIsAnimating();
+	}
+}
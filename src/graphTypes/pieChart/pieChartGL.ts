@@ -0,0 +1,54 @@
+import { trace } from "@izumiano/vite-logger";
+import type { WasmFloat32Array } from "../../graph-renderer/pkg/graph_renderer";
+import GeneralProgram from "../shared/generalProgram";
+import WebGLRenderer, { type IWebGLRenderer } from "../webGLRenderer";
+import { mat4 } from "gl-matrix";
+import type { Color } from "../graphRenderer";
+
+export default class PieChartGL
+	extends WebGLRenderer
+	implements IWebGLRenderer
+{
+	private program_slices: GeneralProgram;
+
+	constructor({
+		canvas,
+		backgroundColor,
+	}: {
+		canvas: HTMLCanvasElement;
+		backgroundColor: Color;
+	}) {
+		super({
+			canvas,
+			backgroundColor,
+		});
+
+		// Resized to what the wasm side allows once it exists
+		this.program_slices = new GeneralProgram(this.gl);
+	}
+
+	public init(memory: WebAssembly.Memory): void {
+		this.program_slices.init(memory);
+	}
+
+	public setMaxSliceVertices(maxVertices: number) {
+		this.program_slices.setMaxVertices(maxVertices);
+	}
+
+	public updateSlicesBuffers(
+		positions: WasmFloat32Array,
+		colors: WasmFloat32Array,
+	) {
+		this.program_slices.updateBuffers(positions, colors);
+	}
+
+	override draw(timestamp: number) {
+		trace();
+		super.draw(timestamp);
+
+		const projectionMatrix = Array.from(mat4.create());
+		const modelViewMatrix = Array.from(mat4.create());
+
+		this.program_slices.draw(timestamp, projectionMatrix, modelViewMatrix);
+	}
+}
@@ -0,0 +1,14 @@
+precision mediump float;
+
+varying lowp vec4 vColor;
+varying vec2 vOffset;
+varying float vRadius;
+
+void main(void) {
+	// fade out over the last pixel of the radius so the edges are not jagged
+	float coverage = clamp(vRadius - length(vOffset) + 0.5, 0.0, 1.0);
+	if (coverage <= 0.0) {
+		discard;
+	}
+	gl_FragColor = vec4(vColor.rgb, vColor.a * coverage);
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+attribute vec2 aCorner;
+attribute vec3 aInstancePosition;
+attribute vec4 aInstanceColor;
+
+uniform mat4 uModelViewMatrix;
+uniform mat4 uProjectionMatrix;
+// Size of the canvas in pixels
+uniform vec2 uResolution;
+
+varying lowp vec4 vColor;
+// Offset from the center of the circle in pixels
+varying mediump vec2 vOffset;
+varying mediump float vRadius;
+
+void main(void) {
+	// One pixel more than the radius leaves room for the faded edge
+	vec2 offset = aCorner * (aInstancePosition.z + 1.0);
+	vec2 center = aInstancePosition.xy + offset / uResolution * 2.0;
+	gl_Position = uProjectionMatrix * uModelViewMatrix * vec4(center, 0.0, 1.0);
+	vColor = aInstanceColor;
+	vOffset = offset;
+	vRadius = aInstancePosition.z;
+}
\ No newline at end of file
@@ -0,0 +1,210 @@
+import { trace } from "@izumiano/vite-logger";
+import type { WasmFloat32Array } from "../../graph-renderer/pkg/graph_renderer";
+import type { IShaderProgram, WebGLBufferInfo } from "../shaderProgram";
+import ShaderProgram from "../shaderProgram";
+
+import fsSource from "./circles.frag";
+import vsSource from "./circles.vert";
+
+type AttribLocations = {
+	corner: number;
+	instancePosition: number;
+	instanceColor: number;
+};
+
+type UniformLocations = {
+	projectionMatrix: WebGLUniformLocation;
+	modelViewMatrix: WebGLUniformLocation;
+	resolution: WebGLUniformLocation;
+};
+
+type Buffers = {
+	corners: WebGLBufferInfo;
+	instancePositions: WebGLBufferInfo;
+	instanceColors: WebGLBufferInfo;
+};
+
+// Two triangles covering the square around a circle, shared by every instance
+const corners = new Float32Array([-1, -1, 1, -1, 1, 1, -1, -1, 1, 1, -1, 1]);
+const FLOATS_PER_INSTANCE = 3;
+
+// Draws every circle as an instance of the same quad, maxVertices counts circles here
+export default class CirclesProgram
+	extends ShaderProgram<AttribLocations, UniformLocations, Buffers, object>
+	implements IShaderProgram
+{
+	constructor(
+		gl: WebGL2RenderingContext,
+		options: { maxVertices: number } = { maxVertices: 100 },
+	) {
+		super(
+			gl,
+			vsSource,
+			fsSource,
+			["corner", "instancePosition", "instanceColor"],
+			["modelViewMatrix", "projectionMatrix", "resolution"],
+			options,
+		);
+	}
+
+	initBuffers(gl: WebGL2RenderingContext): Buffers {
+		const cornersBuffer = gl.createBuffer();
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, cornersBuffer);
+
+		gl.bufferData(gl.ARRAY_BUFFER, corners, gl.STATIC_DRAW);
+
+		const instancePositionsBuffer = gl.createBuffer();
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, instancePositionsBuffer);
+
+		gl.bufferData(
+			gl.ARRAY_BUFFER,
+			new Float32Array(this.options.maxVertices * FLOATS_PER_INSTANCE),
+			gl.DYNAMIC_DRAW,
+		);
+
+		const instanceColorsBuffer = gl.createBuffer();
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, instanceColorsBuffer);
+
+		gl.bufferData(
+			gl.ARRAY_BUFFER,
+			new Float32Array(this.options.maxVertices * 4),
+			gl.DYNAMIC_DRAW,
+		);
+
+		return {
+			corners: { buf: cornersBuffer, size: corners.length },
+			instancePositions: { buf: instancePositionsBuffer, size: 0 },
+			instanceColors: { buf: instanceColorsBuffer, size: 0 },
+		};
+	}
+
+	// Remakes the buffers once the wasm side knows how many circles it can write
+	public setMaxInstances(maxInstances: number) {
+		if (maxInstances === this.options.maxVertices) {
+			return;
+		}
+
+		this.gl.deleteBuffer(this.buffers.corners.buf);
+		this.gl.deleteBuffer(this.buffers.instancePositions.buf);
+		this.gl.deleteBuffer(this.buffers.instanceColors.buf);
+		this.options.maxVertices = maxInstances;
+		this.buffers = this.initBuffers(this.gl);
+	}
+
+	private updateInstancePositionsBuffer(positions: WasmFloat32Array) {
+		trace({ positions });
+		this.gl.bindBuffer(
+			this.gl.ARRAY_BUFFER,
+			this.buffers.instancePositions.buf,
+		);
+		this.gl.bufferSubData(
+			this.gl.ARRAY_BUFFER,
+			0,
+			this.wasmArrayToFloat32Array(positions),
+		);
+		this.buffers.instancePositions.size = positions.size;
+	}
+
+	private updateInstanceColorsBuffer(colors: WasmFloat32Array) {
+		trace({ colors });
+		this.gl.bindBuffer(this.gl.ARRAY_BUFFER, this.buffers.instanceColors.buf);
+		this.gl.bufferSubData(
+			this.gl.ARRAY_BUFFER,
+			0,
+			this.wasmArrayToFloat32Array(colors),
+		);
+		this.buffers.instanceColors.size = colors.size;
+	}
+
+	public updateBuffers(positions: WasmFloat32Array, colors: WasmFloat32Array) {
+		trace();
+		this.gl.useProgram(this.program);
+		this.updateInstancePositionsBuffer(positions);
+		this.updateInstanceColorsBuffer(colors);
+	}
+
+	private setAttribute(
+		gl: WebGL2RenderingContext,
+		location: number,
+		buffer: WebGLBufferInfo,
+		numComponents: number,
+		divisor: number,
+	) {
+		const type = gl.FLOAT;
+		const normalize = false;
+		const stride = 0;
+		const offset = 0;
+
+		gl.bindBuffer(gl.ARRAY_BUFFER, buffer.buf);
+		gl.vertexAttribPointer(
+			location,
+			numComponents,
+			type,
+			normalize,
+			stride,
+			offset,
+		);
+		gl.enableVertexAttribArray(location);
+		gl.vertexAttribDivisor(location, divisor);
+	}
+
+	override setAttributes(gl: WebGL2RenderingContext) {
+		this.setAttribute(
+			gl,
+			this.attribLocations.corner,
+			this.buffers.corners,
+			2,
+			0,
+		);
+		this.setAttribute(
+			gl,
+			this.attribLocations.instancePosition,
+			this.buffers.instancePositions,
+			FLOATS_PER_INSTANCE,
+			1,
+		);
+		this.setAttribute(
+			gl,
+			this.attribLocations.instanceColor,
+			this.buffers.instanceColors,
+			4,
+			1,
+		);
+	}
+
+	public draw(
+		timestamp: number,
+		projectionMatrix: number[],
+		modelViewMatrix: number[],
+	) {
+		const gl = this.gl;
+		const instanceCount =
+			this.buffers.instancePositions.size / FLOATS_PER_INSTANCE;
+
+		super.draw(timestamp, projectionMatrix, modelViewMatrix, instanceCount);
+
+		gl.uniform2f(
+			this.uniformLocations.resolution,
+			gl.drawingBufferWidth,
+			gl.drawingBufferHeight,
+		);
+
+		// The faded edges are blended over what is already drawn, alpha is kept as it was
+		gl.enable(gl.BLEND);
+		gl.blendFuncSeparate(
+			gl.SRC_ALPHA,
+			gl.ONE_MINUS_SRC_ALPHA,
+			gl.ONE,
+			gl.ONE_MINUS_SRC_ALPHA,
+		);
+		gl.drawArraysInstanced(gl.TRIANGLES, 0, corners.length / 2, instanceCount);
+		gl.disable(gl.BLEND);
+
+		// The other programs share these attribute locations and draw one vertex at a time
+		gl.vertexAttribDivisor(this.attribLocations.instancePosition, 0);
+		gl.vertexAttribDivisor(this.attribLocations.instanceColor, 0);
+	}
+}
@@ -0,0 +1,650 @@
+import { logWarn, trace } from "@izumiano/vite-logger";
+import {
+	BubbleLayout as WasmBubbleLayout,
+	ClickingState as WasmClickingState,
+	Positioning as WasmPositioning,
+	ScatterChart as WasmScatterChart,
+	ScatterChartDataPoint as WasmDataPoint,
+	ScatterChartLayout as WasmScatterChartLayout,
+	ScatterChartOptions as WasmScatterChartOptions,
+	ScatterPointOptions as WasmScatterPointOptions,
+	ValueAxisLayout as WasmValueAxisLayout,
+	XAxisLayout as WasmXAxisLayout,
+} from "../../graph-renderer/pkg/graph_renderer.js";
+
+import {
+	type Color,
+	devicePixelRatio,
+	type GraphData,
+	GraphRenderer,
+	type GraphRendererOptions,
+	type IGraphRenderer,
+	type InternalGraphRendererOptions,
+	type PointerType,
+	type WasmGraphRendererInterop,
+} from "../graphRenderer.js";
+import {
+	colorToWasmColor,
+	gestureOptionsToWasmGestureOptions,
+	gestureOptionsWithDefaults,
+} from "../wasmUtils.js";
+import ScatterChartGL from "./scatterChartGL.js";
+import type { DeepRequired } from "../../utils.js";
+import type {
+	AnimationOptions,
+	AnimationPolicy,
+	GestureOptions,
+	OnValueAxisLayout,
+	OnXAxisTicksLayout,
+	PointerCallback,
+	ValueAxisOptions,
+	XAxisOptions,
+} from "../shared/types.js";
+import {
+	scaleLineValue,
+	valueAxisOptionsToWasmValueAxisDomain,
+} from "../shared/scale.js";
+import {
+	animationOptionsToWasmAnimationOptions,
+	animationPolicyToWasmAnimationPolicy,
+	defaultAnimationPolicy,
+	animationOptionsWithDefaults,
+} from "../shared/animation.js";
+
+export type ScatterChartData<TLabel> = DataPoint<TLabel>[] & GraphData;
+type InternalScatterChartData<TLabel> = DataPoint<TLabel>[] & GraphData;
+
+interface BubbleOptions {
+	// Radius of the smallest and largest size in the data, sizes in between scale by area
+	minRadius?: number;
+	maxRadius?: number;
+}
+
+interface PointOptions {
+	color?: Color;
+	// Picked by the category of a data point, points without one use color
+	categoryColors?: Color[];
+	hoverScale?: number;
+	hoverColor?: Color;
+	selectedColor?: Color;
+	maxPoints?: number;
+}
+
+export interface ScatterChartOptions extends GraphRendererOptions {
+	bubbleOptions?: BubbleOptions;
+	pointOptions?: PointOptions;
+	valueAxis?: ValueAxisOptions;
+	xAxis?: XAxisOptions;
+	gestures?: GestureOptions;
+	animations?: AnimationOptions;
+	animationPolicy?: AnimationPolicy;
+}
+
+class WasmScatterChartInterop
+	implements WasmGraphRendererInterop<WasmScatterChart>
+{
+	wasmGraph: WasmScatterChart;
+
+	constructor({
+		data,
+		startTimestamp,
+		width,
+		height,
+		options,
+	}: {
+		data: WasmDataPoint[];
+		startTimestamp: number;
+		width: number;
+		height: number;
+		options: InternalScatterChartOptions;
+	}) {
+		this.wasmGraph = new WasmScatterChart(
+			data,
+			startTimestamp,
+			width,
+			height,
+
+			new WasmScatterChartLayout(
+				new WasmPositioning(
+					options.positioning.bottom,
+					options.positioning.top,
+					options.positioning.left,
+					options.positioning.right,
+				),
+				new WasmBubbleLayout(
+					options.bubbleOptions.minRadius,
+					options.bubbleOptions.maxRadius,
+				),
+				new WasmValueAxisLayout(
+					options.valueAxis.width,
+					options.valueAxis.smallestScale,
+					options.valueAxis.minPixelDistance,
+					valueAxisOptionsToWasmValueAxisDomain(options.valueAxis),
+				),
+				new WasmXAxisLayout(
+					options.xAxis.tickLength,
+					options.xAxis.tickThickness,
+					options.xAxis.minTickDistance,
+				),
+			),
+
+			new WasmScatterChartOptions(
+				colorToWasmColor(options.backgroundColor),
+				new WasmScatterPointOptions(
+					colorToWasmColor(options.pointOptions.color),
+					options.pointOptions.categoryColors.map(colorToWasmColor),
+					colorToWasmColor(options.pointOptions.hoverColor),
+					colorToWasmColor(options.pointOptions.selectedColor),
+					options.pointOptions.hoverScale,
+					options.pointOptions.maxPoints,
+				),
+				colorToWasmColor(options.valueAxis.color),
+				gestureOptionsToWasmGestureOptions(options.gestures),
+				animationOptionsToWasmAnimationOptions(options.animations),
+				animationPolicyToWasmAnimationPolicy(options.animationPolicy),
+			),
+		);
+	}
+	public updateData(data: WasmDataPoint[], timestamp: number) {
+		this.wasmGraph.update_data(data, timestamp);
+	}
+
+	resize(width: number, height: number) {
+		this.wasmGraph.resize(width, height);
+	}
+	update(timestamp: number, pointer: PointerType) {
+		let clickingState: WasmClickingState;
+
+		switch (pointer.clickingState) {
+			case "None":
+				clickingState = WasmClickingState.None;
+				break;
+			case "Holding":
+				clickingState = WasmClickingState.Holding;
+				break;
+			case "JustReleased":
+				clickingState = WasmClickingState.JustReleased;
+				break;
+			default:
+				clickingState = WasmClickingState.None;
+		}
+
+		return this.wasmGraph.update(
+			timestamp,
+			pointer.x,
+			pointer.y,
+			clickingState,
+			new Float32Array(
+				pointer.touches.flatMap((touch) => [touch.id, touch.x, touch.y]),
+			),
+		);
+	}
+
+	setAnimationPolicy(policy: AnimationPolicy) {
+		this.wasmGraph.set_animation_policy(
+			animationPolicyToWasmAnimationPolicy(policy),
+		);
+	}
+	getIsAnimating() {
+		return this.wasmGraph.get_is_animating();
+	}
+	getMaxGeneralVertices() {
+		return this.wasmGraph.get_max_general_vertices();
+	}
+	getMaxPointInstances() {
+		return this.wasmGraph.get_max_point_instances();
+	}
+
+	getPointsLen() {
+		return this.wasmGraph.get_points_len();
+	}
+	getPointXAt(i: number) {
+		return this.wasmGraph.get_point_x_at(i);
+	}
+	getPointYAt(i: number) {
+		return this.wasmGraph.get_point_y_at(i);
+	}
+	getPointRadiusAt(i: number) {
+		return this.wasmGraph.get_point_radius_at(i);
+	}
+	getScaleLinesCount() {
+		return this.wasmGraph.get_scale_lines_count();
+	}
+	getScaleLineXAt(i: number) {
+		return this.wasmGraph.get_scale_line_x_at(i);
+	}
+	getScaleLineYAt(i: number) {
+		return this.wasmGraph.get_scale_line_y_at(i);
+	}
+	getScaleLineValueAt(i: number) {
+		return this.wasmGraph.get_scale_line_value_at(i);
+	}
+	getXAxisTicksCount() {
+		return this.wasmGraph.get_x_axis_ticks_count();
+	}
+	getXAxisTickXAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_x_at(i);
+	}
+	getXAxisTickYAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_y_at(i);
+	}
+	getXAxisTickValueAt(i: number) {
+		return this.wasmGraph.get_x_axis_tick_value_at(i);
+	}
+	getSelectedPointIndex() {
+		return this.wasmGraph.get_selected_point_index();
+	}
+	getHoveredPointIndex() {
+		return this.wasmGraph.get_hovered_point_index();
+	}
+}
+
+function dataToWasmData<TLabel>(data: ScatterChartData<TLabel>) {
+	return data.map(
+		(item) =>
+			new WasmDataPoint(item.x, item.y, item.size ?? 1, item.category),
+	);
+}
+
+function dataToInternalData<TLabel>(data: ScatterChartData<TLabel>) {
+	return data.map((data) => {
+		return { ...data };
+	});
+}
+
+export interface DataPoint<TLabel> {
+	label: TLabel;
+	x: number;
+	y: number;
+	// Bubble size, defaults to 1. Only matters when the min and max radius differ
+	size?: number;
+	category?: number;
+}
+
+type PositionInfo = {
+	x: number;
+	y: number;
+	radius: number;
+} | null;
+
+export type OnSelectionChangeArgs<TLabel> = {
+	data: DataPoint<TLabel>;
+	positionInfo?: PositionInfo;
+	index: number;
+} | null;
+type OnSelectionChange<TLabel> =
+	| ((args: OnSelectionChangeArgs<TLabel>) => void)
+	| undefined;
+
+export type OnHoverArgs<TLabel> = {
+	data: DataPoint<TLabel>;
+	positionInfo?: PositionInfo;
+	pointer: { x: number; y: number; type: string };
+	index: number;
+} | null;
+type OnHover<TLabel> = ((args: OnHoverArgs<TLabel>) => void) | undefined;
+
+export type ScatterChartCallbacks<TLabel> = {
+	onSelectionChange?: PointerCallback<OnSelectionChange<TLabel>>;
+	onHover?: PointerCallback<OnHover<TLabel>>;
+	onValueAxisLayout?: OnValueAxisLayout;
+	onXAxisTicksLayout?: OnXAxisTicksLayout;
+};
+
+type InternalScatterChartOptions = InternalGraphRendererOptions &
+	DeepRequired<ScatterChartOptions>;
+
+export default class ScatterChart<TLabel>
+	extends GraphRenderer<
+		WasmScatterChart,
+		WasmScatterChartInterop,
+		InternalScatterChartOptions,
+		ScatterChartGL,
+		ScatterChartData<TLabel>
+	>
+	implements IGraphRenderer
+{
+	private data: InternalScatterChartData<TLabel>;
+
+	private onSelectionChange: OnSelectionChange<TLabel>;
+	private onSelectionChangeIncludePositionInfo?: boolean;
+	private selectedPointIndex: number | undefined;
+
+	private onHover: OnHover<TLabel>;
+	private onHoverIncludePositionInfo?: boolean;
+	private hoveredPointIndex?: number;
+
+	private onValueAxisLayout?: OnValueAxisLayout;
+	private onXAxisTicksLayout?: OnXAxisTicksLayout;
+
+	constructor(
+		canvas: HTMLCanvasElement,
+		width: number,
+		height: number,
+		data: ScatterChartData<TLabel>,
+		{
+			options,
+			onSelectionChange,
+			onHover,
+			onValueAxisLayout,
+			onXAxisTicksLayout,
+		}: {
+			options?: ScatterChartOptions;
+		} & ScatterChartCallbacks<TLabel>,
+	) {
+		trace();
+		options ??= {};
+
+		const backgroundColor = options.backgroundColor ?? {
+			r: 0,
+			g: 0,
+			b: 0,
+		};
+
+		const internalOptions: InternalScatterChartOptions = {
+			backgroundColor: { ...backgroundColor, a: backgroundColor.a ?? 255 },
+			positioning:
+				typeof options.positioning !== "number"
+					? {
+							top: (options.positioning?.top ?? 0) * devicePixelRatio,
+							left: (options.positioning?.left ?? 0) * devicePixelRatio,
+							right: (options.positioning?.right ?? 0) * devicePixelRatio,
+							bottom: (options.positioning?.bottom ?? 0) * devicePixelRatio,
+						}
+					: {
+							top: options.positioning * devicePixelRatio,
+							left: options.positioning * devicePixelRatio,
+							right: options.positioning * devicePixelRatio,
+							bottom: options.positioning * devicePixelRatio,
+						},
+			bubbleOptions: {
+				minRadius:
+					(options.bubbleOptions?.minRadius ?? 5) * devicePixelRatio,
+				maxRadius:
+					(options.bubbleOptions?.maxRadius ?? 5) * devicePixelRatio,
+			},
+			pointOptions: {
+				color: options.pointOptions?.color ?? { r: 255, g: 255, b: 255 },
+				categoryColors: options.pointOptions?.categoryColors ?? [],
+				hoverScale: options.pointOptions?.hoverScale ?? 1.2,
+				hoverColor: options.pointOptions?.hoverColor ?? {
+					r: 150,
+					g: 150,
+					b: 150,
+					a: 127,
+				},
+				selectedColor: options.pointOptions?.selectedColor ?? {
+					r: 100,
+					g: 100,
+					b: 255,
+				},
+				maxPoints: options.pointOptions?.maxPoints ?? 1000,
+			},
+			valueAxis: {
+				width: (options.valueAxis?.width ?? 0) * devicePixelRatio,
+				color: options.valueAxis?.color ?? { r: 255, g: 255, b: 255 },
+				smallestScale:
+					(options.valueAxis?.smallestScale ?? 1) * devicePixelRatio,
+				minPixelDistance:
+					(options.valueAxis?.minPixelDistance ?? 20) * devicePixelRatio,
+				scale: options.valueAxis?.scale ?? "linear",
+				ticks: options.valueAxis?.ticks ?? 10,
+				min: options.valueAxis?.min ?? null,
+				max: options.valueAxis?.max ?? null,
+				niceBounds: options.valueAxis?.niceBounds ?? false,
+			},
+			xAxis: {
+				tickLength: (options.xAxis?.tickLength ?? 5) * devicePixelRatio,
+				tickThickness: (options.xAxis?.tickThickness ?? 2) * devicePixelRatio,
+				minTickDistance:
+					(options.xAxis?.minTickDistance ?? 50) * devicePixelRatio,
+			},
+			gestures: gestureOptionsWithDefaults(options.gestures, devicePixelRatio),
+			animations: animationOptionsWithDefaults(options.animations, {
+				enter: { duration: 500 },
+				hover: { duration: 200 },
+				select: { duration: 200 },
+				click: { duration: 200 },
+				exit: { duration: 500 },
+				viewport: { duration: 300 },
+			}),
+			animationPolicy: options.animationPolicy ?? defaultAnimationPolicy(),
+		};
+
+		super(
+			canvas,
+			width,
+			height,
+			new ScatterChartGL({
+				canvas,
+				backgroundColor: options.backgroundColor ?? { r: 0, g: 0, b: 0 },
+			}),
+			internalOptions,
+		);
+
+		this.data = dataToInternalData(data);
+		this.onSelectionChange = onSelectionChange?.func;
+		this.onSelectionChangeIncludePositionInfo =
+			onSelectionChange?.includePositionInfo;
+		this.onHover = onHover?.func;
+		this.onHoverIncludePositionInfo = onHover?.includePositionInfo;
+		this.onValueAxisLayout = onValueAxisLayout;
+		this.onXAxisTicksLayout = onXAxisTicksLayout;
+	}
+
+	public getPositionInfoForPointAt(index: number) {
+		return {
+			x: this.wasmGraphRenderer.getPointXAt(index) / devicePixelRatio,
+			y: this.wasmGraphRenderer.getPointYAt(index) / devicePixelRatio,
+			radius: this.wasmGraphRenderer.getPointRadiusAt(index) / devicePixelRatio,
+		};
+	}
+
+	public updateData(data: ScatterChartData<TLabel>, timestamp: number) {
+		trace(data);
+		if (data === this.data) {
+			return;
+		}
+
+		if (data.length > this.options.pointOptions.maxPoints) {
+			logWarn(
+				`Cannot update data to length greater than maxPoints, {${data.length}}, {${this.options.pointOptions.maxPoints}}`,
+			);
+			return;
+		}
+
+		if (data.length === this.data.length) {
+			let hasDifference = false;
+			for (let i = 0; i < data.length; i++) {
+				const newDataPoint = data[i];
+				const oldDataPoint = this.data[i];
+
+				if (
+					!oldDataPoint ||
+					newDataPoint.x !== oldDataPoint.x ||
+					newDataPoint.y !== oldDataPoint.y ||
+					newDataPoint.size !== oldDataPoint.size ||
+					newDataPoint.category !== oldDataPoint.category ||
+					newDataPoint.label !== oldDataPoint.label
+				) {
+					hasDifference = true;
+					break;
+				}
+			}
+			if (!hasDifference) {
+				return;
+			}
+		}
+
+		this.data = dataToInternalData(data);
+		this.wasmGraphRenderer.updateData(dataToWasmData(data), timestamp);
+		this.wasmGraphRenderer.update(timestamp, this.pointer);
+
+		super.updateData(data, timestamp);
+	}
+
+	public init(memory: WebAssembly.Memory, startTimestamp: number): void {
+		const wasmGraphRenderer = new WasmScatterChartInterop({
+			data: dataToWasmData(this.data),
+			startTimestamp,
+			width: this.canvas.width,
+			height: this.canvas.height,
+			options: this.options,
+		});
+
+		super._init(memory, wasmGraphRenderer);
+		this.glRenderer.setMaxGeneralVertices(
+			wasmGraphRenderer.getMaxGeneralVertices(),
+		);
+		this.glRenderer.setMaxPointInstances(
+			wasmGraphRenderer.getMaxPointInstances(),
+		);
+	}
+
+	public dispose() {
+		trace();
+		this.wasmGraphRenderer.wasmGraph.free();
+		this.removeInputEventHandlers();
+	}
+
+	public handleLayout() {
+		trace();
+		const scaleLinesLen = this.wasmGraphRenderer.getScaleLinesCount();
+		const valueAxisLayout = [];
+		for (let i = 0; i < scaleLinesLen; i++) {
+			valueAxisLayout.push({
+				value: scaleLineValue(
+					this.wasmGraphRenderer.getScaleLineValueAt(i),
+					this.options.valueAxis.scale,
+					this.options.valueAxis.smallestScale,
+				),
+				x: 0,
+				y: this.wasmGraphRenderer.getScaleLineYAt(i) / devicePixelRatio,
+				width: this.wasmGraphRenderer.getScaleLineXAt(i) / devicePixelRatio,
+			});
+		}
+		this.options.valueAxis.width > 0 &&
+			this.onValueAxisLayout?.(valueAxisLayout);
+
+		const xAxisTicksCount = this.wasmGraphRenderer.getXAxisTicksCount();
+		const xAxisTicksLayout = [];
+		for (let i = 0; i < xAxisTicksCount; i++) {
+			xAxisTicksLayout.push({
+				value: this.wasmGraphRenderer.getXAxisTickValueAt(i),
+				x: this.wasmGraphRenderer.getXAxisTickXAt(i) / devicePixelRatio,
+				y: this.wasmGraphRenderer.getXAxisTickYAt(i) / devicePixelRatio,
+			});
+		}
+		this.onXAxisTicksLayout?.(xAxisTicksLayout);
+	}
+
+	public onPointerDown(pointerType: string) {
+		if (pointerType !== "mouse") {
+			this.onPointerMove(pointerType);
+		}
+	}
+
+	public onPointerUp(_pointerType: string) {
+		this.handleSelectionChange();
+	}
+
+	private handleSelectionChange() {
+		const selectedPointIndex = this.wasmGraphRenderer.getSelectedPointIndex();
+
+		if (
+			selectedPointIndex === this.selectedPointIndex ||
+			!this.onSelectionChange
+		) {
+			return;
+		}
+		this.selectedPointIndex = selectedPointIndex;
+
+		if (selectedPointIndex == null || selectedPointIndex >= this.data.length) {
+			this.onSelectionChange(null);
+			return;
+		}
+
+		this.onSelectionChange({
+			data: this.data[selectedPointIndex],
+			positionInfo: this.onSelectionChangeIncludePositionInfo
+				? this.getPositionInfoForPointAt(selectedPointIndex)
+				: null,
+			index: selectedPointIndex,
+		});
+	}
+
+	public onPointerMove(pointerType: string) {
+		const hoveredPointIndex = this.wasmGraphRenderer.getHoveredPointIndex();
+		if (!this.onHover) {
+			return;
+		}
+
+		if (hoveredPointIndex == null) {
+			if (this.hoveredPointIndex != null) {
+				this.onHover(null);
+				this.hoveredPointIndex = undefined;
+			}
+			return;
+		}
+
+		if (hoveredPointIndex >= this.data.length) {
+			return;
+		}
+		this.hoveredPointIndex = hoveredPointIndex;
+		this.onHover({
+			data: this.data[hoveredPointIndex],
+			positionInfo: this.onHoverIncludePositionInfo
+				? this.getPositionInfoForPointAt(hoveredPointIndex)
+				: null,
+			index: hoveredPointIndex,
+			pointer: {
+				x: this.pointer.x / devicePixelRatio,
+				y: this.pointer.y / devicePixelRatio,
+				type: pointerType,
+			},
+		});
+	}
+
+	public onPointerLeave() {
+		this.onHover?.(null);
+		this.hoveredPointIndex = undefined;
+	}
+
+	public update(timestamp: number) {
+		trace();
+		const scatterChartData = this.wasmGraphRenderer.update(
+			timestamp,
+			this.pointer,
+		);
+		// A long-press selects while the finger is still down
+		if (this.pointer.touches.length > 0) {
+			this.handleSelectionChange();
+		}
+
+		this.glRenderer.updateGeneralBuffers(
+			scatterChartData.vertex_array_general,
+			scatterChartData.colors_array_general,
+		);
+
+		this.glRenderer.updatePointsBuffers(
+			scatterChartData.instance_array_points,
+			scatterChartData.colors_array_points,
+		);
+
+		super.update(timestamp);
+	}
+
+	public render(timestamp: number) {
+		trace({ width: this.width, height: this.height });
+		super.render(timestamp);
+	}
+
+	public setAnimationPolicy(policy: AnimationPolicy) {
+		this.options.animationPolicy = policy;
+		this.wasmGraphRenderer.setAnimationPolicy(policy);
+	}
+
+	public isAnimating() {
+		return this.wasmGraphRenderer.getIsAnimating();
+	}
+}
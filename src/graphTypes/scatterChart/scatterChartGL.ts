@@ -0,0 +1,70 @@
+import { trace } from "@izumiano/vite-logger";
+import type { WasmFloat32Array } from "../../graph-renderer/pkg/graph_renderer";
+import GeneralProgram from "../shared/generalProgram";
+import CirclesProgram from "./circlesProgram";
+import WebGLRenderer, { type IWebGLRenderer } from "../webGLRenderer";
+import { mat4 } from "gl-matrix";
+import type { Color } from "../graphRenderer";
+
+export default class ScatterChartGL
+	extends WebGLRenderer
+	implements IWebGLRenderer
+{
+	private program_general: GeneralProgram;
+	private program_points: CirclesProgram;
+
+	constructor({
+		canvas,
+		backgroundColor,
+	}: {
+		canvas: HTMLCanvasElement;
+		backgroundColor: Color;
+	}) {
+		super({
+			canvas,
+			backgroundColor,
+		});
+
+		// Resized to what the wasm side allows once it exists
+		this.program_general = new GeneralProgram(this.gl);
+		this.program_points = new CirclesProgram(this.gl);
+	}
+
+	public init(memory: WebAssembly.Memory): void {
+		this.program_general.init(memory);
+		this.program_points.init(memory);
+	}
+
+	public setMaxGeneralVertices(maxVertices: number) {
+		this.program_general.setMaxVertices(maxVertices);
+	}
+
+	public setMaxPointInstances(maxInstances: number) {
+		this.program_points.setMaxInstances(maxInstances);
+	}
+
+	public updateGeneralBuffers(
+		positions: WasmFloat32Array,
+		colors: WasmFloat32Array,
+	) {
+		this.program_general.updateBuffers(positions, colors);
+	}
+
+	public updatePointsBuffers(
+		instances: WasmFloat32Array,
+		colors: WasmFloat32Array,
+	) {
+		this.program_points.updateBuffers(instances, colors);
+	}
+
+	override draw(timestamp: number) {
+		trace();
+		super.draw(timestamp);
+
+		const projectionMatrix = Array.from(mat4.create());
+		const modelViewMatrix = Array.from(mat4.create());
+
+		this.program_general.draw(timestamp, projectionMatrix, modelViewMatrix);
+		this.program_points.draw(timestamp, projectionMatrix, modelViewMatrix);
+	}
+}
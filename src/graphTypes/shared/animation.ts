@@ -0,0 +1,131 @@
+import {
+	AnimationOptions as WasmAnimationOptions,
+	AnimationPolicy as WasmAnimationPolicy,
+	AnimationTiming as WasmAnimationTiming,
+	Easing as WasmEasing,
+	EasingType as WasmEasingType,
+} from "../../graph-renderer/pkg/graph_renderer.js";
+import type { DeepRequired } from "../../utils.js";
+import type {
+	AnimationOptions,
+	AnimationPolicy,
+	AnimationTiming,
+	Easing,
+} from "./types.js";
+
+type AnimationChannel = keyof AnimationOptions;
+type AnimationDefaults = Record<
+	AnimationChannel,
+	{ duration: number; stagger?: number }
+>;
+
+function easingTypeToWasmEasingType(
+	easing: Exclude<Easing, { cubicBezier: unknown } | { spring: unknown }>,
+) {
+	switch (easing) {
+		case "linear":
+			return WasmEasingType.Linear;
+		case "easeInCubic":
+			return WasmEasingType.EaseInCubic;
+		case "easeOutCubic":
+			return WasmEasingType.EaseOutCubic;
+		case "easeInOutCubic":
+			return WasmEasingType.EaseInOutCubic;
+		case "easeOutSine":
+			return WasmEasingType.EaseOutSine;
+		case "easeOutBack":
+			return WasmEasingType.EaseOutBack;
+		case "easeOutElastic":
+			return WasmEasingType.EaseOutElastic;
+		case "easeOutBounce":
+			return WasmEasingType.EaseOutBounce;
+	}
+}
+
+function easingToWasmEasing(easing: Easing) {
+	if (typeof easing !== "object") {
+		return new WasmEasing(easingTypeToWasmEasingType(easing), 0, 0, 0, 0, 0, 0);
+	}
+
+	if ("cubicBezier" in easing) {
+		const [x1, y1, x2, y2] = easing.cubicBezier;
+		return new WasmEasing(WasmEasingType.CubicBezier, x1, y1, x2, y2, 0, 0);
+	}
+
+	return new WasmEasing(
+		WasmEasingType.Spring,
+		0,
+		0,
+		0,
+		0,
+		easing.spring.stiffness,
+		easing.spring.damping,
+	);
+}
+
+function animationTimingToWasmAnimationTiming(
+	timing: DeepRequired<AnimationTiming>,
+) {
+	return new WasmAnimationTiming(
+		timing.duration,
+		timing.delay,
+		timing.stagger,
+		easingToWasmEasing(timing.easing),
+	);
+}
+
+export function animationOptionsToWasmAnimationOptions(
+	animations: DeepRequired<AnimationOptions>,
+) {
+	return new WasmAnimationOptions(
+		animationTimingToWasmAnimationTiming(animations.enter),
+		animationTimingToWasmAnimationTiming(animations.hover),
+		animationTimingToWasmAnimationTiming(animations.select),
+		animationTimingToWasmAnimationTiming(animations.click),
+		animationTimingToWasmAnimationTiming(animations.exit),
+		animationTimingToWasmAnimationTiming(animations.viewport),
+	);
+}
+
+export function animationPolicyToWasmAnimationPolicy(policy: AnimationPolicy) {
+	switch (policy) {
+		case "full":
+			return WasmAnimationPolicy.Full;
+		case "reduced":
+			return WasmAnimationPolicy.Reduced;
+		case "none":
+			return WasmAnimationPolicy.None;
+	}
+}
+
+// Follows the user's reduced motion setting unless a policy is given
+export function defaultAnimationPolicy(): AnimationPolicy {
+	return window.matchMedia?.("(prefers-reduced-motion: reduce)").matches
+		? "reduced"
+		: "full";
+}
+
+// Every chart animates a little differently, so the durations it falls back to are its own
+export function animationOptionsWithDefaults(
+	animations: AnimationOptions | undefined,
+	defaults: AnimationDefaults,
+): DeepRequired<AnimationOptions> {
+	const timing = (channel: AnimationChannel) => {
+		const options = animations?.[channel];
+		return {
+			duration: options?.duration ?? defaults[channel].duration,
+			delay: options?.delay ?? 0,
+			stagger: options?.stagger ?? defaults[channel].stagger ?? 0,
+			easing: options?.easing ?? "easeOutSine",
+		};
+	};
+
+	return {
+		enter: timing("enter"),
+		hover: timing("hover"),
+		select: timing("select"),
+		click: timing("click"),
+		exit: timing("exit"),
+		viewport: timing("viewport"),
+	};
+}
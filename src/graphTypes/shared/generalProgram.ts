@@ -66,6 +66,18 @@ export default class GeneralProgram
 		};
 	}
 
+	// Remakes the buffers once the wasm side knows how many vertices it can write
+	public setMaxVertices(maxVertices: number) {
+		if (maxVertices === this.options.maxVertices) {
+			return;
+		}
+
+		this.gl.deleteBuffer(this.buffers.positions.buf);
+		this.gl.deleteBuffer(this.buffers.colors.buf);
+		this.options.maxVertices = maxVertices;
+		this.buffers = this.initBuffers(this.gl);
+	}
+
 	private updatePositionsBuffer(positions: WasmFloat32Array) {
 		trace({ positions });
 		this.gl.bindBuffer(this.gl.ARRAY_BUFFER, this.buffers.positions.buf);
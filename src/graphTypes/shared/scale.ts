@@ -0,0 +1,46 @@
+import {
+	ScaleType as WasmScaleType,
+	ValueAxisDomain as WasmValueAxisDomain,
+} from "../../graph-renderer/pkg/graph_renderer.js";
+import { type DeepRequired, roundToNearestMultiple } from "../../utils.js";
+import type { ValueAxisOptions, ValueAxisScale } from "./types.js";
+
+export function valueAxisScaleToWasmScaleType(scale: ValueAxisScale) {
+	switch (scale) {
+		case "linear":
+			return WasmScaleType.Linear;
+		case "log10":
+			return WasmScaleType.Log10;
+		case "symlog":
+			return WasmScaleType.SymLog;
+		case "sqrt":
+			return WasmScaleType.Sqrt;
+	}
+}
+
+export function valueAxisOptionsToWasmValueAxisDomain(
+	valueAxis: DeepRequired<ValueAxisOptions>,
+) {
+	return new WasmValueAxisDomain(
+		valueAxisScaleToWasmScaleType(valueAxis.scale),
+		valueAxis.ticks,
+		valueAxis.min,
+		valueAxis.max,
+		valueAxis.niceBounds,
+	);
+}
+
+// Ticks on logarithmic scales are powers of ten which can be smaller than the smallest scale
+export function scaleLineValue(
+	value: number,
+	scale: ValueAxisScale,
+	smallestScale: number,
+) {
+	switch (scale) {
+		case "log10":
+		case "symlog":
+			return value;
+		default:
+			return roundToNearestMultiple(value, smallestScale);
+	}
+}
@@ -12,9 +12,110 @@ export type OnValueAxisLayoutParams = {
 }[];
 export type OnValueAxisLayout = (args: OnValueAxisLayoutParams) => void;
 
+// Identifies a data point across updates so it animates from where it was, even when it moves
+export type DataKey = string | number;
+
+export type ValueAxisScale = "linear" | "log10" | "symlog" | "sqrt";
+
 export type ValueAxisOptions = {
 	width?: number;
 	color?: Color;
 	smallestScale?: number;
 	minPixelDistance?: number;
+	scale?: ValueAxisScale;
+	ticks?: number;
+	min?: number | null;
+	max?: number | null;
+	niceBounds?: boolean;
+};
+
+export type Easing =
+	| "linear"
+	| "easeInCubic"
+	| "easeOutCubic"
+	| "easeInOutCubic"
+	| "easeOutSine"
+	| "easeOutBack"
+	| "easeOutElastic"
+	| "easeOutBounce"
+	| { cubicBezier: [number, number, number, number] }
+	| { spring: { stiffness: number; damping: number } };
+
+export type AnimationTiming = {
+	duration?: number;
+	delay?: number;
+	// Total time over which a group of elements start one after another, like bars growing in
+	stagger?: number;
+	easing?: Easing;
+};
+
+// Outline drawn around the element focused with focusNext and focusPrevious
+export type FocusRingOptions = {
+	color?: Color;
+	thickness?: number;
+	// Space between the element and the ring
+	gap?: number;
+};
+
+export type SelectionOptions = {
+	// Shift, ctrl or cmd clicks add to the selection instead of replacing it
+	multiSelect?: boolean;
+	// Dragging selects everything inside the rectangle it covers
+	brush?: boolean;
+	brushColor?: Color;
+};
+
+// "nearestX" reports every series at the X closest to the pointer instead of only the hovered element
+export type TooltipMode = "hovered" | "nearestX";
+
+export type TooltipOptions = {
+	mode?: TooltipMode;
+	// Space between the anchor and the tooltip
+	offset?: number;
+};
+
+// Where to show a tooltip and what to show in it, in CSS pixels
+export type Tooltip<TEntry> = {
+	anchor: { x: number; y: number };
+	x: number;
+	y: number;
+	width: number;
+	height: number;
+	entries: TEntry[];
+};
+
+// Distances are in CSS pixels and durations in milliseconds
+export type GestureOptions = {
+	// How far a finger can move and still tap or long-press, past it a single finger pans
+	tapMaxDistance?: number;
+	tapMaxDuration?: number;
+	longPressDuration?: number;
+	// How far two fingers have to move or spread before they pan or pinch
+	twoFingerThreshold?: number;
+};
+
+// "reduced" keeps only short color fades, "none" turns every animation off
+export type AnimationPolicy = "full" | "reduced" | "none";
+
+export type AnimationOptions = {
+	enter?: AnimationTiming;
+	hover?: AnimationTiming;
+	select?: AnimationTiming;
+	click?: AnimationTiming;
+	exit?: AnimationTiming;
+	// Zooming and panning line charts
+	viewport?: AnimationTiming;
+};
+
+export type OnXAxisTicksLayoutParams = {
+	value: number;
+	x: number;
+	y: number;
+}[];
+export type OnXAxisTicksLayout = (args: OnXAxisTicksLayoutParams) => void;
+
+export type XAxisOptions = {
+	tickLength?: number;
+	tickThickness?: number;
+	minTickDistance?: number;
 };
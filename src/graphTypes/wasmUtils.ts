@@ -1,6 +1,20 @@
-import { Color as WasmColor } from "../graph-renderer/pkg/graph_renderer";
-import { clamp } from "../utils";
+import {
+	Color as WasmColor,
+	FocusRingOptions as WasmFocusRingOptions,
+	GestureOptions as WasmGestureOptions,
+	SelectionOptions as WasmSelectionOptions,
+	TooltipMode as WasmTooltipMode,
+	TooltipOptions as WasmTooltipOptions,
+} from "../graph-renderer/pkg/graph_renderer";
+import { clamp, type DeepRequired } from "../utils";
 import type { Color } from "./graphRenderer";
+import type {
+	DataKey,
+	FocusRingOptions,
+	GestureOptions,
+	SelectionOptions,
+	TooltipOptions,
+} from "./shared/types";
 
 export function colorToWasmColor(color: Color) {
 	return new WasmColor(
@@ -10,3 +24,102 @@ export function colorToWasmColor(color: Color) {
 		clamp(Math.floor(color.a ?? 255), { min: 0, max: 255 }),
 	);
 }
+
+export function focusRingOptionsToWasmFocusRingOptions(
+	focusRing: DeepRequired<FocusRingOptions>,
+) {
+	return new WasmFocusRingOptions(
+		colorToWasmColor(focusRing.color),
+		focusRing.thickness,
+		focusRing.gap,
+	);
+}
+
+// Sizes are given in CSS pixels
+export function focusRingOptionsWithDefaults(
+	focusRing: FocusRingOptions | undefined,
+	devicePixelRatio: number,
+): DeepRequired<FocusRingOptions> {
+	const color = focusRing?.color ?? { r: 100, g: 150, b: 255 };
+	return {
+		color: { ...color, a: color.a ?? 255 },
+		thickness: (focusRing?.thickness ?? 2) * devicePixelRatio,
+		gap: (focusRing?.gap ?? 2) * devicePixelRatio,
+	};
+}
+
+export function selectionOptionsToWasmSelectionOptions(
+	selection: DeepRequired<SelectionOptions>,
+) {
+	return new WasmSelectionOptions(
+		selection.multiSelect,
+		selection.brush,
+		colorToWasmColor(selection.brushColor),
+	);
+}
+
+export function selectionOptionsWithDefaults(
+	selection: SelectionOptions | undefined,
+): DeepRequired<SelectionOptions> {
+	const brushColor = selection?.brushColor ?? { r: 100, g: 150, b: 255, a: 60 };
+	return {
+		multiSelect: selection?.multiSelect ?? true,
+		brush: selection?.brush ?? false,
+		brushColor: { ...brushColor, a: brushColor.a ?? 255 },
+	};
+}
+
+export function tooltipOptionsToWasmTooltipOptions(
+	tooltip: DeepRequired<TooltipOptions>,
+) {
+	return new WasmTooltipOptions(
+		tooltip.mode === "nearestX"
+			? WasmTooltipMode.NearestX
+			: WasmTooltipMode.Hovered,
+		tooltip.offset,
+	);
+}
+
+// Sizes are given in CSS pixels
+export function tooltipOptionsWithDefaults(
+	tooltip: TooltipOptions | undefined,
+	devicePixelRatio: number,
+): DeepRequired<TooltipOptions> {
+	return {
+		mode: tooltip?.mode ?? "hovered",
+		offset: (tooltip?.offset ?? 8) * devicePixelRatio,
+	};
+}
+
+export function gestureOptionsToWasmGestureOptions(
+	gestures: DeepRequired<GestureOptions>,
+) {
+	return new WasmGestureOptions(
+		gestures.tapMaxDistance,
+		gestures.tapMaxDuration,
+		gestures.longPressDuration,
+		gestures.twoFingerThreshold,
+	);
+}
+
+// Distances are given in CSS pixels
+export function gestureOptionsWithDefaults(
+	gestures: GestureOptions | undefined,
+	devicePixelRatio: number,
+): DeepRequired<GestureOptions> {
+	return {
+		tapMaxDistance: (gestures?.tapMaxDistance ?? 10) * devicePixelRatio,
+		tapMaxDuration: gestures?.tapMaxDuration ?? 300,
+		longPressDuration: gestures?.longPressDuration ?? 500,
+		twoFingerThreshold: (gestures?.twoFingerThreshold ?? 10) * devicePixelRatio,
+	};
+}
+
+// Whether the click should add to the selection rather than replace it
+export function isAdditivePointerEvent(e: PointerEvent) {
+	return e.shiftKey || e.ctrlKey || e.metaKey;
+}
+
+export function dataKeyToWasmDataKey(key: DataKey | undefined) {
+	return key === undefined ? undefined : String(key);
+}
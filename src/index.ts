@@ -16,6 +16,7 @@ export {
 	type OnHoverArgs as BarChart_OnHoverArgs,
 	type OnSelectionChangeArgs as BarChart_OnSelectionChangeArgs,
 	type OnLabelsLayoutParams as BarChart_OnLabelsLayoutParams,
+	type TooltipEntry as BarChart_TooltipEntry,
 	default as BarChart,
 } from "./graphTypes/barChart/barChart";
 export {
@@ -25,9 +26,44 @@ export {
 	type OnHoverArgs as LineChart_OnHoverArgs,
 	type OnSelectionChangeArgs as LineChart_OnSelectionChangeArgs,
 	type OnXAxisLayoutParams as LineChart_OnLabelsLayoutParams,
+	type TooltipEntry as LineChart_TooltipEntry,
 	default as LineChart,
 } from "./graphTypes/lineChart/lineChart";
-export type { OnValueAxisLayoutParams } from "./graphTypes/shared/types";
+export {
+	type PieChartData,
+	type PieChartOptions,
+	type PieChartCallbacks,
+	type DataPoint as PieChart_DataPoint,
+	type OnHoverArgs as PieChart_OnHoverArgs,
+	type OnSelectionChangeArgs as PieChart_OnSelectionChangeArgs,
+	type OnLabelsLayoutParams as PieChart_OnLabelsLayoutParams,
+	default as PieChart,
+} from "./graphTypes/pieChart/pieChart";
+export {
+	type ScatterChartData,
+	type ScatterChartOptions,
+	type ScatterChartCallbacks,
+	type DataPoint as ScatterChart_DataPoint,
+	type OnHoverArgs as ScatterChart_OnHoverArgs,
+	type OnSelectionChangeArgs as ScatterChart_OnSelectionChangeArgs,
+	default as ScatterChart,
+} from "./graphTypes/scatterChart/scatterChart";
+export type {
+	AnimationOptions,
+	AnimationPolicy,
+	AnimationTiming,
+	DataKey,
+	Easing,
+	FocusRingOptions,
+	GestureOptions,
+	OnValueAxisLayoutParams,
+	OnXAxisTicksLayoutParams,
+	SelectionOptions,
+	Tooltip,
+	TooltipMode,
+	TooltipOptions,
+	ValueAxisScale,
+} from "./graphTypes/shared/types";
 export { GraphRenderer } from "./graphTypes/graphRenderer";
 
 export const __assembledGraphLogger__ = {
@@ -70,6 +70,8 @@ pub fn wasm_struct(_attr: TokenStream, input: TokenStream) -> TokenStream {
 
 		#[wasm_bindgen]
 		impl #struct_name {
+			// Takes every field, however many the struct has
+			#[allow(clippy::too_many_arguments)]
 			#[wasm_bindgen(constructor)]
 			pub fn new(#(#struc2)*) -> Self {
 				Self{ #(#imple)* }